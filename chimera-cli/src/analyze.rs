@@ -0,0 +1,85 @@
+//! `analyze` subcommand: density-evolution threshold and EXIT curves of an
+//! LDPC ensemble.
+
+use clap::Args;
+use color_eyre::eyre::{bail, Context, Result};
+use std::path::PathBuf;
+
+use chimera_core::ldpc::analysis::{sigma_to_ebn0_db, DegreeDistribution};
+use chimera_core::ldpc::{exit_chart, ga_threshold, LDPCSuite};
+
+use crate::config::CliConfig;
+
+#[derive(Args, Debug)]
+pub struct AnalyzeArgs {
+    /// Variable-node degree of a regular ensemble (instead of the configured code)
+    #[arg(long, requires = "dc")]
+    pub dv: Option<usize>,
+
+    /// Check-node degree of a regular ensemble
+    #[arg(long, requires = "dv")]
+    pub dc: Option<usize>,
+
+    /// Channel noise sigma for the EXIT chart (default: the threshold)
+    #[arg(long)]
+    pub sigma: Option<f64>,
+
+    /// A-priori information levels in the EXIT chart
+    #[arg(long, default_value_t = 51)]
+    pub points: usize,
+
+    /// Write the EXIT curves as CSV instead of printing them
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+pub fn run(args: &AnalyzeArgs, config: Option<&CliConfig>) -> Result<()> {
+    let (label, ensemble) = match (args.dv, args.dc, config) {
+        (Some(dv), Some(dc), _) => (
+            format!("regular ({dv}, {dc}) ensemble"),
+            DegreeDistribution::regular(dv, dc),
+        ),
+        (_, _, Some(config)) => {
+            let suite = LDPCSuite::from_config(
+                &config.protocol.frame_layout.mother_layout(),
+                &config.ldpc,
+            )?;
+            (
+                format!(
+                    "configured {}x{} parity-check matrix",
+                    suite.matrices.parity_bits, suite.matrices.codeword_bits
+                ),
+                DegreeDistribution::from_parity_check(&suite.matrices.parity_check)?,
+            )
+        }
+        _ => bail!("analyze needs --dv and --dc, or --config to analyze the configured code"),
+    };
+
+    let rate = ensemble.design_rate();
+    let threshold = ga_threshold(&ensemble)?;
+    let sigma = args.sigma.unwrap_or(threshold);
+    let chart = exit_chart(&ensemble, sigma, args.points)?;
+
+    println!("Ensemble: {label}");
+    println!("  variable degrees (edge fractions): {:?}", ensemble.variable);
+    println!("  check degrees (edge fractions):    {:?}", ensemble.check);
+    println!("  design rate: {rate:.4}");
+    println!(
+        "GA density-evolution threshold: sigma = {threshold:.4} (Eb/N0 = {:.2} dB)",
+        sigma_to_ebn0_db(threshold, rate)
+    );
+    println!(
+        "EXIT chart at sigma = {sigma:.4}: tunnel {}",
+        if chart.tunnel_open(&ensemble) { "open" } else { "closed" }
+    );
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, chart.to_csv())
+                .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+            println!("Wrote {} EXIT points to {}", chart.points.len(), path.display());
+        }
+        None => print!("{}", chart.to_csv()),
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//! `arq` subcommand: stop-and-wait ARQ goodput and retransmissions versus SNR.
+
+use clap::Args;
+use color_eyre::eyre::Result;
+
+use chimera_core::arq::{sweep_snr, ArqConfig};
+use chimera_core::config::{SystemConfig, UserSimulationConfig};
+use chimera_core::processor::ProcessorConfig;
+
+use crate::config::CliConfig;
+
+#[derive(Args, Debug)]
+pub struct ArqArgs {
+    /// Channel Es/N0 values to sweep, in dB
+    #[arg(long, value_delimiter = ',', default_values_t = [20.0, 10.0, 6.0, 3.0])]
+    pub snr_db: Vec<f64>,
+
+    /// Message to transfer (default: the configured simulation message)
+    #[arg(short = 'm', long)]
+    pub message: Option<String>,
+
+    /// Retransmissions of one packet before the transfer is abandoned
+    #[arg(long, default_value_t = ArqConfig::default().max_retransmissions)]
+    pub max_retransmissions: usize,
+
+    /// Seconds the sender waits for a reply before retransmitting
+    #[arg(long, default_value_t = ArqConfig::default().ack_timeout_secs)]
+    pub ack_timeout: f64,
+}
+
+pub fn run(args: &ArqArgs, config: Option<&CliConfig>) -> Result<()> {
+    let mut processor = ProcessorConfig {
+        sample_rate: SystemConfig::SAMPLE_RATE,
+        optimize_for_latency: false,
+        ..ProcessorConfig::default()
+    };
+    let mut message = UserSimulationConfig::default().message;
+    if let Some(config) = config {
+        config.protocol.validate()?;
+        processor.symbol_rate = config.protocol.qpsk_symbol_rate;
+        processor.carrier_freq = config.protocol.carrier_freq_hz;
+        processor.protocol = config.protocol.clone();
+        processor.ldpc = config.ldpc.clone();
+        processor.address_filter = config.address_filter.clone();
+        message = config.simulation.message.clone();
+    }
+    let message = args.message.clone().unwrap_or(message);
+    let arq = ArqConfig {
+        max_retransmissions: args.max_retransmissions,
+        ack_timeout_secs: args.ack_timeout,
+    };
+
+    println!(
+        "Stop-and-wait transfer of {} bytes, {} retransmissions max, {:.1} s ACK timeout",
+        message.len(),
+        arq.max_retransmissions,
+        arq.ack_timeout_secs
+    );
+    println!(
+        "  {:>8} {:>9} {:>8} {:>7} {:>7} {:>6} {:>9} {:>10} {:>14}",
+        "SNR (dB)", "delivered", "packets", "frames", "resent", "NACKs", "timeouts", "time (s)", "goodput (bps)"
+    );
+    for point in sweep_snr(message.as_bytes(), &args.snr_db, &processor, &arq)? {
+        let report = &point.report;
+        println!(
+            "  {:>8.1} {:>9} {:>8} {:>7} {:>7} {:>6} {:>9} {:>10.1} {:>14.3}{}",
+            point.snr_db,
+            report.delivered.len(),
+            report.packets,
+            report.transmissions,
+            report.retransmissions,
+            report.nacks,
+            report.timeouts,
+            report.elapsed_secs,
+            report.goodput_bps(),
+            if report.complete { "" } else { "  (abandoned)" }
+        );
+    }
+    Ok(())
+}
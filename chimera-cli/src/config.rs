@@ -1,8 +1,8 @@
 //! CLI-specific configuration structures for TOML-based configuration.
 
 use chimera_core::config::{
-    LDPCConfig, InternalProtocolConfig, UserSimulationConfig,
-    ChannelParams, ThzModulationParams, SignalProcessingParams
+    AddressFilter, LDPCConfig, InternalProtocolConfig, UserSimulationConfig,
+    ChannelParams, ThzModulationParams, SignalProcessingParams, ProtocolPreset, ProtocolRegistry
 };
 use color_eyre::eyre::{Context, Result};
 use serde::{Deserialize, Deserializer, Serialize};
@@ -73,6 +73,10 @@ pub struct CliConfig {
     #[serde(default, skip_serializing_if = "is_default_ldpc")]
     pub ldpc: LDPCConfig,
     
+    /// Target IDs the receiver delivers frames for (default: all)
+    #[serde(default)]
+    pub address_filter: AddressFilter,
+    
     /// Channel parameters (runtime adjustable)
     #[serde(default)]
     pub channel: ChannelParams,
@@ -224,6 +228,26 @@ pub enum LogLevel {
 }
 
 impl CliConfig {
+    /// Load a configuration file, or the protocol preset of that name if no such file exists
+    pub fn from_file_or_preset(config: &str, registry: &ProtocolRegistry) -> Result<Self> {
+        if !Path::new(config).exists() {
+            if let Some(preset) = registry.get(config) {
+                return Ok(Self::from_preset(preset));
+            }
+        }
+        Self::from_file(config)
+            .wrap_err_with(|| format!("Failed to load config from {}", config))
+    }
+    
+    /// Default configuration running a protocol preset
+    pub fn from_preset(preset: &ProtocolPreset) -> Self {
+        Self {
+            protocol: preset.protocol.clone(),
+            ldpc: preset.ldpc.clone(),
+            ..Self::default()
+        }
+    }
+    
     /// Load configuration from TOML file with include support
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
@@ -300,6 +324,7 @@ impl CliConfig {
             protocol: InternalProtocolConfig::default(),
             simulation: UserSimulationConfig::default(),
             ldpc: LDPCConfig::default(),
+            address_filter: AddressFilter::default(),
             channel: ChannelParams::default(),
             thz_modulation: ThzModulationParams::default(),
             signal_processing: SignalProcessingParams::default(),
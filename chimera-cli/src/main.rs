@@ -1,11 +1,15 @@
+mod analyze;
+mod arq_sweep;
 mod config;
 mod frame_decoder;
 mod logging;
+mod sync_report;
 mod telemetry;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use color_eyre::eyre::{Context, Result};
 
+use chimera_core::config::ProtocolRegistry;
 use chimera_core::processor::{ChimeraProcessor, ProcessorConfig};
 use config::CliConfig;
 use logging::{LogEvent, StatisticsEvent, StructuredLogger};
@@ -15,10 +19,14 @@ use telemetry::TelemetryAggregator;
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Chimera modulation pipeline simulator", long_about = None)]
 struct Cli {
-    /// Path to TOML configuration file (or preset name: raman-whisper, burst-telemetry, deep-space-probe)
-    #[arg(short, long)]
+    /// Path to TOML configuration file (or preset name: whisper, burst-telemetry, deep-space-probe)
+    #[arg(short, long, global = true)]
     config: Option<String>,
 
+    /// Directory of protocol preset TOML files to add to the built-in presets
+    #[arg(long, global = true)]
+    preset_dir: Option<PathBuf>,
+
     /// Enable verbose diagnostic output
     #[arg(short, long)]
     verbose: bool,
@@ -30,20 +38,66 @@ struct Cli {
     /// Message to encode (overrides config message)
     #[arg(short = 'm', long)]
     message: Option<String>,
+
+    /// Send the raw bytes of this file instead of the text message
+    #[arg(long, conflicts_with = "message")]
+    payload_file: Option<PathBuf>,
+
+    /// Write the recovered payload bytes to this file
+    #[arg(long)]
+    payload_output: Option<PathBuf>,
+
+    /// Write the parity-check matrix of the configured LDPC code in alist format
+    #[arg(long)]
+    export_alist: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compute the BP threshold and EXIT curves of an LDPC ensemble
+    Analyze(analyze::AnalyzeArgs),
+    /// Report the peak-to-sidelobe ratio of the sync word and its alternatives
+    Sync(sync_report::SyncArgs),
+    /// Sweep stop-and-wait ARQ goodput and retransmissions over channel SNR
+    Arq(arq_sweep::ArqArgs),
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     let args = Cli::parse();
 
-    // Load configuration from file
+    // Built-in protocol presets, overridden by the user's own
+    let mut presets = ProtocolRegistry::builtin();
+    if let Some(dir) = &args.preset_dir {
+        presets.load_dir(dir)?;
+    }
+
+    if let Some(command) = &args.command {
+        let config = args
+            .config
+            .as_deref()
+            .map(|config| CliConfig::from_file_or_preset(config, &presets))
+            .transpose()
+            .wrap_err("Failed to load config")?;
+        return match command {
+            Command::Analyze(analyze_args) => analyze::run(analyze_args, config.as_ref()),
+            Command::Sync(sync_args) => sync_report::run(sync_args, config.as_ref()),
+            Command::Arq(arq_args) => arq_sweep::run(arq_args, config.as_ref()),
+        };
+    }
+
+    // Load configuration from file or preset
     let mut config = if let Some(config_path) = &args.config {
-        CliConfig::from_file(config_path)
-            .wrap_err_with(|| format!("Failed to load config from {}", config_path))?
+        CliConfig::from_file_or_preset(config_path, &presets)?
     } else {
         eprintln!("Error: --config parameter is required. Example: --config configs/long_message.toml");
         std::process::exit(1);
     };
+    config.protocol.validate()?;
+    config.address_filter.validate()?;
     
     // Override with CLI arguments
     if let Some(wav_path) = args.wav_output {
@@ -54,45 +108,79 @@ fn main() -> Result<()> {
         config.simulation.message = message;
     }
     
+    // Text is sent as its UTF-8 bytes
+    let payload = match &args.payload_file {
+        Some(path) => std::fs::read(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?,
+        None => config.simulation.message.clone().into_bytes(),
+    };
+    
+    if let Some(alist_path) = &args.export_alist {
+        let suite = chimera_core::ldpc::LDPCSuite::from_config(
+            &config.protocol.frame_layout.mother_layout(),
+            &config.ldpc,
+        )?;
+        std::fs::write(alist_path, suite.matrices.to_alist())
+            .wrap_err_with(|| format!("Failed to write {}", alist_path.display()))?;
+    }
+    
     // Initialize structured logger
     let mut logger = StructuredLogger::new(config.terminal.logging.clone())?;
     
     logger.log(LogEvent::Info {
-        message: format!("Chimera CLI starting with message: \"{}\"", config.simulation.message),
+        message: match &args.payload_file {
+            Some(path) => format!("Chimera CLI starting with payload file {}", path.display()),
+            None => format!("Chimera CLI starting with message: \"{}\"", config.simulation.message),
+        },
     })?;
     
-    // SINGLE FRAME MODE: Calculate max message size
-    let ldpc = chimera_core::ldpc::LDPCSuite::new(&config.protocol.frame_layout, &config.ldpc);
-    let max_bytes = ldpc.matrices.message_bits / 8;
-    let message_bytes = config.simulation.message.len().min(max_bytes);
-    let payload_bits = chimera_core::utils::string_to_bitstream(&config.simulation.message[..message_bytes]);
+    // The payload and its length field are split over as many frames as they need
+    let codec = chimera_core::fec::build_codec(&config.protocol.frame_layout, &config.ldpc)?;
+    let bytes_per_frame = codec
+        .message_bits()
+        .saturating_sub(config.protocol.frame_layout.checksum.bits())
+        / 8;
+    let message_bytes = payload.len();
+    let frame_count = (chimera_core::payload::LENGTH_FIELD_BYTES + message_bytes)
+        .div_ceil(bytes_per_frame.max(1));
     
     logger.log(LogEvent::Info {
         message: format!(
-            "Encoding {} bytes ({} bits) into 1 frame (max {} bytes)",
+            "Encoding {} bytes ({} bits) into {} frame(s) of {} bytes",
             message_bytes,
-            payload_bits.len(),
-            max_bytes
+            message_bytes * 8,
+            frame_count,
+            bytes_per_frame
         ),
     })?;
+    let max_frames = config.protocol.max_frames_per_message();
+    if frame_count > max_frames {
+        logger.log(LogEvent::Info {
+            message: format!("Warning: message truncated to {} frames", max_frames),
+        })?;
+    }
     
     // Create processor with configuration from config file
     let processor_config = ProcessorConfig {
         sample_rate: chimera_core::config::SystemConfig::SAMPLE_RATE,
         symbol_rate: config.protocol.qpsk_symbol_rate,
         carrier_freq: config.protocol.carrier_freq_hz,
+        protocol: config.protocol.clone(),
+        ldpc: config.ldpc.clone(),
+        address_filter: config.address_filter.clone(),
+        channel: None,
         logging: config.terminal.logging.to_core_log_config(),
         optimize_for_latency: false, // Batch mode
         min_chunk_size: None,
     };
     
-    let mut processor = ChimeraProcessor::new(processor_config);
+    let mut processor = ChimeraProcessor::new(processor_config)?;
     if args.verbose {
         processor.enable_diagnostics();
     }
     
     // Run the batch processing
-    let result = processor.process_batch(&config.simulation.message);
+    let result = processor.process_batch_bytes(&payload);
     
     // Initialize telemetry aggregator
     let mut telemetry = TelemetryAggregator::new(config.terminal.telemetry_interval_secs);
@@ -103,7 +191,7 @@ fn main() -> Result<()> {
         result.post_fec_ber,
         config.protocol.carrier_freq_hz,
         result.tx_symbols.len(),
-        1, // Single frame mode
+        result.frames.iter().filter(|frame| frame.received).count(),
         result.post_fec_errors == 0, // Synced if no errors
     );
     
@@ -112,9 +200,25 @@ fn main() -> Result<()> {
         logger.log(LogEvent::Telemetry(telemetry_event))?;
     }
     
-    // Decode and log frames (processor doesn't expose frame-by-frame data yet)
-    // TODO: Add frame diagnostics to processor if needed
-    // For now, we'll skip frame-by-frame logging
+    // Log the receive status of every frame
+    for frame in &result.frames {
+        logger.log(LogEvent::Info {
+            message: format!(
+                "Frame {}/{}: {} (pre-FEC errors: {}, post-FEC errors: {}){}{}",
+                frame.index + 1,
+                result.frames.len(),
+                if frame.success { "ok" } else if frame.received { "failed" } else { "missing" },
+                frame.pre_fec_errors,
+                frame.post_fec_errors,
+                frame.header.as_ref().map(|header| format!(
+                    " [target {}, {}]",
+                    header.target_id_hex,
+                    header.command.map_or("unknown command", |command| command.as_str())
+                )).unwrap_or_default(),
+                frame.error.as_ref().map(|err| format!(" - {err}")).unwrap_or_default()
+            ),
+        })?;
+    }
     
     // Compute and log final statistics
     let (pre_fec_stats, post_fec_stats, fsk_stats) = telemetry.compute_statistics();
@@ -132,11 +236,10 @@ fn main() -> Result<()> {
     logger.log(LogEvent::Statistics(stats_event))?;
     
     // Log summary metrics
-    let errors_corrected = if result.pre_fec_errors > result.post_fec_errors {
-        result.pre_fec_errors - result.post_fec_errors
-    } else {
-        0
-    };
+    let errors_corrected = result
+        .decode_outcome
+        .as_ref()
+        .map_or(0, |outcome| outcome.bits_flipped);
     
     logger.log(LogEvent::Info {
         message: format!(
@@ -147,10 +250,30 @@ fn main() -> Result<()> {
         ),
     })?;
     
+    if result.truncated_bytes > 0 {
+        logger.log(LogEvent::Warn {
+            message: format!("Dropped the last {} payload bytes: the message exceeds the frame counters", result.truncated_bytes),
+        })?;
+    }
+    
+    if result.foreign_frames > 0 {
+        logger.log(LogEvent::Info {
+            message: format!("Ignored {} frame(s) addressed to other receivers", result.foreign_frames),
+        })?;
+    }
+    
     logger.log(LogEvent::Info {
-        message: format!("Recovered message: {}", result.recovered_message),
+        message: match &args.payload_file {
+            Some(_) => format!("Recovered {} payload bytes", result.recovered_payload.len()),
+            None => format!("Recovered message: {}", result.recovered_message),
+        },
     })?;
     
+    if let Some(path) = &args.payload_output {
+        std::fs::write(path, &result.recovered_payload)
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+    }
+    
     // If WAV output is requested, write the audio from the simulation
     if let Some(wav_path) = &config.terminal.wav_output {
         if !result.audio.is_empty() {
@@ -0,0 +1,61 @@
+//! `sync` subcommand: autocorrelation sidelobes of the configured sync word
+//! and of the other sync word families at the same length.
+
+use clap::Args;
+use color_eyre::eyre::Result;
+
+use chimera_core::config::{InternalProtocolConfig, SyncWord};
+use chimera_core::signal_processing::demodulation::generate_sync_template;
+use chimera_core::sync::{self, sidelobe_report};
+
+use crate::config::CliConfig;
+
+#[derive(Args, Debug)]
+pub struct SyncArgs {
+    /// Sync field length in symbols (default: the configured layout's)
+    #[arg(long)]
+    pub symbols: Option<usize>,
+
+    /// Zadoff-Chu root to compare (default: the configured root, or 1)
+    #[arg(long)]
+    pub root: Option<usize>,
+}
+
+pub fn run(args: &SyncArgs, config: Option<&CliConfig>) -> Result<()> {
+    let protocol = config.map_or_else(InternalProtocolConfig::default, |config| config.protocol.clone());
+    let symbols = args.symbols.unwrap_or(protocol.frame_layout.sync_symbols);
+    let root = args.root.unwrap_or(match protocol.sync_word {
+        SyncWord::ZadoffChu { root } => root,
+        _ => 1,
+    });
+
+    println!(
+        "Sync field of {symbols} symbols (configured: {:?}, hex {})",
+        protocol.sync_word, protocol.sync_sequence_hex
+    );
+    println!("  {:<22} {:>9} {:>7} {:>13}", "word", "PSLR (dB)", "peak", "sidelobe@lag");
+    let words = [
+        SyncWord::Hex,
+        SyncWord::Barker,
+        SyncWord::MSequence,
+        SyncWord::ZadoffChu { root },
+    ];
+    for word in words {
+        let label = format!("{word:?}");
+        if let Err(err) = sync::validate(&word, &protocol.sync_sequence_hex, symbols) {
+            println!("  {label:<22} unavailable: {err}");
+            continue;
+        }
+        let bits = sync::sync_field_bits(&word, &protocol.sync_sequence_hex, symbols);
+        let report = sidelobe_report(&generate_sync_template(&bits));
+        println!(
+            "  {label:<22} {:>9.2} {:>7.2} {:>8.2}@{:<4}{}",
+            report.pslr_db,
+            report.peak,
+            report.max_sidelobe,
+            report.worst_lag,
+            if word == protocol.sync_word { " (configured)" } else { "" }
+        );
+    }
+    Ok(())
+}
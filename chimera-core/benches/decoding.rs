@@ -38,7 +38,7 @@ fn benchmark_symbol_decoding(c: &mut Criterion) {
         &payload_bits,
         protocol.clone(),
         ldpc_suite.matrices.clone(),
-    );
+    ).unwrap();
     
     let (tx_symbols, _, _, _, _) = encoder.get_next_symbols(128);
     
@@ -49,7 +49,7 @@ fn benchmark_symbol_decoding(c: &mut Criterion) {
             let mut decoder = StreamingSymbolDecoder::new(
                 protocol.clone(),
                 ldpc_suite.matrices.clone(),
-            );
+            ).unwrap();
             
             let symbols = &tx_symbols[..size.min(tx_symbols.len())];
             
@@ -74,7 +74,7 @@ fn benchmark_sync_search(c: &mut Criterion) {
         &payload_bits,
         protocol.clone(),
         ldpc_suite.matrices.clone(),
-    );
+    ).unwrap();
     
     let (tx_symbols, _, _, _, _) = encoder.get_next_symbols(protocol.frame_layout.total_symbols);
     
@@ -83,7 +83,7 @@ fn benchmark_sync_search(c: &mut Criterion) {
             let mut decoder = StreamingSymbolDecoder::new(
                 protocol.clone(),
                 ldpc_suite.matrices.clone(),
-            );
+            ).unwrap();
             
             decoder.process_symbols(black_box(&tx_symbols))
         })
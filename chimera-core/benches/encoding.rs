@@ -20,7 +20,7 @@ fn benchmark_frame_encoding(c: &mut Criterion) {
                 black_box(&payload_bits),
                 black_box(protocol.clone()),
                 black_box(ldpc_suite.matrices.clone()),
-            )
+            ).unwrap()
         })
     });
     
@@ -28,7 +28,7 @@ fn benchmark_frame_encoding(c: &mut Criterion) {
         &payload_bits,
         protocol.clone(),
         ldpc_suite.matrices.clone(),
-    );
+    ).unwrap();
     
     c.bench_function("generate_16_symbols", |b| {
         b.iter(|| {
@@ -59,7 +59,7 @@ fn benchmark_symbol_generation_sizes(c: &mut Criterion) {
                 &payload_bits,
                 protocol.clone(),
                 ldpc_suite.matrices.clone(),
-            );
+            ).unwrap();
             
             b.iter(|| {
                 encoder.get_next_symbols(black_box(size))
@@ -1,7 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use chimera_core::{
-    config::{ProtocolConfig, LDPCConfig, FrameLayout},
-    ldpc::{LDPCSuite, decode_ldpc},
+    config::{LDPCConfig, FrameLayout},
+    encoder::encode_with_generator,
+    ldpc::{LDPCSuite, decode_ldpc, syndrome_weight},
 };
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
@@ -29,6 +30,15 @@ fn benchmark_ldpc_encoding(c: &mut Criterion) {
             black_box(codeword)
         })
     });
+    
+    let mut group = c.benchmark_group("ldpc_encode");
+    group.bench_function("dense", |b| {
+        b.iter(|| encode_with_generator(&suite.matrices.generator, black_box(&message)))
+    });
+    group.bench_function("packed", |b| {
+        b.iter(|| suite.packed.encode(black_box(&message)))
+    });
+    group.finish();
 }
 
 fn benchmark_ldpc_decoding(c: &mut Criterion) {
@@ -82,6 +92,7 @@ fn benchmark_ldpc_construction(c: &mut Criterion) {
             command_type_symbols: total_symbols / 8,
             data_payload_symbols: total_symbols / 2,
             ecc_symbols: total_symbols / 8,
+            ..FrameLayout::default()
         };
         
         group.bench_with_input(
@@ -121,11 +132,42 @@ fn benchmark_matrix_operations(c: &mut Criterion) {
     });
 }
 
+fn benchmark_packed_decode_path(c: &mut Criterion) {
+    let layout = FrameLayout::default();
+    let suite = LDPCSuite::new(&layout, &LDPCConfig::default());
+    
+    let mut rng = StdRng::seed_from_u64(42);
+    let message: Vec<u8> = (0..layout.message_bits())
+        .map(|_| if rng.gen_bool(0.5) { 1 } else { 0 })
+        .collect();
+    let mut codeword = suite.packed.encode(&message);
+    codeword[3] ^= 1;
+    
+    let mut group = c.benchmark_group("ldpc_syndrome");
+    group.bench_function("dense", |b| {
+        b.iter(|| syndrome_weight(&suite.matrices.parity_check, black_box(&codeword)))
+    });
+    group.bench_function("packed", |b| {
+        b.iter(|| suite.packed.syndrome_weight(black_box(&codeword)))
+    });
+    group.finish();
+    
+    let mut group = c.benchmark_group("ldpc_message_recovery");
+    group.bench_function("gaussian_elimination", |b| {
+        b.iter(|| decode_ldpc(&suite.matrices, black_box(&codeword), 0.0))
+    });
+    group.bench_function("packed", |b| {
+        b.iter(|| suite.packed.recover_message(black_box(&codeword)))
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_ldpc_encoding,
     benchmark_ldpc_decoding,
     benchmark_ldpc_construction,
-    benchmark_matrix_operations
+    benchmark_matrix_operations,
+    benchmark_packed_decode_path
 );
 criterion_main!(benches);
@@ -0,0 +1,469 @@
+//! Stop-and-wait ARQ between two simulated endpoints.
+//!
+//! The sender splits a message into packets that each fit one frame. A packet
+//! starts with a sequence number byte and is sent with the `send_data`
+//! command; the receiver answers `ack` with the sequence number it received,
+//! or `nack` with the one it expects when a frame arrived but failed to
+//! decode. The sender retransmits on a NACK, or when no usable reply arrives
+//! within the ACK timeout, up to `max_retransmissions` times per packet.
+//!
+//! Time is simulated: it advances by each transmission's airtime and by the
+//! timeouts the sender waits out. Each direction of the link is an
+//! [`ArqLink`]. [`ChimeraProcessor`] is one, so frames go through the full
+//! modulator, channel and demodulator. The sender resends a packet with
+//! [`ArqLink::retransmit`], so the processor's decoder chase-combines the
+//! copies of a frame it failed to decode; a new packet starts afresh.
+//!
+//! Sequence numbers are one byte and wrap after 256 packets. Stop-and-wait
+//! only ever has the current packet and a duplicate of the previous one in
+//! flight, so a wrapped number is never ambiguous.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ChannelParams;
+use crate::errors::{ConfigError, Result};
+use crate::processor::{BatchResult, ChimeraProcessor, ProcessorConfig};
+use crate::protocol::CommandType;
+
+/// Stop-and-wait settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArqConfig {
+    /// Retransmissions of one packet before the transfer is abandoned
+    pub max_retransmissions: usize,
+    /// Time the sender waits for a reply after its transmission ends; should
+    /// exceed the airtime of a reply
+    pub ack_timeout_secs: f64,
+}
+
+impl Default for ArqConfig {
+    fn default() -> Self {
+        Self {
+            max_retransmissions: 8,
+            // A training frame plus one 8 s frame of the default protocol
+            ack_timeout_secs: 20.0,
+        }
+    }
+}
+
+/// What the far end of a link made of one transmission
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reception {
+    /// Nothing the receiver could synchronize to
+    Lost,
+    /// A frame arrived but failed to decode; its command if the header survived
+    Corrupted { command: Option<CommandType> },
+    /// A frame decoded
+    Delivered {
+        command: Option<CommandType>,
+        payload: Vec<u8>,
+    },
+}
+
+/// One transmission over a link
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transmission {
+    /// Time on air, in seconds
+    pub airtime_secs: f64,
+    pub reception: Reception,
+}
+
+/// One direction of a simulated link
+pub trait ArqLink {
+    /// Most payload bytes one transmission carries
+    fn max_payload_bytes(&self) -> usize;
+
+    /// Send `payload` with `command` and report what the far end received
+    fn transmit(&mut self, command: CommandType, payload: &[u8]) -> Transmission;
+
+    /// Send the previous `payload` and `command` again
+    ///
+    /// The far end may combine it with the earlier copies; by default it is
+    /// an independent transmission.
+    fn retransmit(&mut self, command: CommandType, payload: &[u8]) -> Transmission {
+        self.transmit(command, payload)
+    }
+}
+
+impl ArqLink for ChimeraProcessor {
+    fn max_payload_bytes(&self) -> usize {
+        self.frame_payload_bytes()
+    }
+
+    fn transmit(&mut self, command: CommandType, payload: &[u8]) -> Transmission {
+        self.set_command(command);
+        let result = self.process_batch_bytes(payload);
+        batch_transmission(self, result)
+    }
+
+    fn retransmit(&mut self, command: CommandType, payload: &[u8]) -> Transmission {
+        self.set_command(command);
+        let result = self.retransmit_batch_bytes(payload);
+        batch_transmission(self, result)
+    }
+}
+
+/// What the far end of `processor` made of `result`
+fn batch_transmission(processor: &ChimeraProcessor, result: BatchResult) -> Transmission {
+    let received_command = result
+        .frames
+        .iter()
+        .find_map(|frame| frame.header.as_ref())
+        .and_then(|header| header.command);
+    let reception = if result.success {
+        Reception::Delivered {
+            command: received_command,
+            payload: result.recovered_payload,
+        }
+    } else if result.frames.iter().any(|frame| frame.received) {
+        Reception::Corrupted { command: received_command }
+    } else {
+        Reception::Lost
+    };
+    Transmission {
+        airtime_secs: result.audio.len() as f64 / processor.config().sample_rate as f64,
+        reception,
+    }
+}
+
+/// Outcome of one ARQ transfer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArqReport {
+    /// Bytes the receiver delivered, in order and without duplicates
+    pub delivered: Vec<u8>,
+    /// Every packet was delivered and acknowledged
+    pub complete: bool,
+    /// Packets the message was split into
+    pub packets: usize,
+    /// Data frames sent, including retransmissions
+    pub transmissions: usize,
+    pub retransmissions: usize,
+    /// ACK and NACK frames the receiver sent
+    pub replies: usize,
+    /// NACKs the sender received
+    pub nacks: usize,
+    /// Waits that ended without a usable reply
+    pub timeouts: usize,
+    /// Simulated time from the first transmission to the last reply or timeout
+    pub elapsed_secs: f64,
+}
+
+impl ArqReport {
+    /// Delivered payload bits per second of simulated time
+    pub fn goodput_bps(&self) -> f64 {
+        if self.elapsed_secs > 0.0 {
+            self.delivered.len() as f64 * 8.0 / self.elapsed_secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Send `message` from the sender to the receiver over `forward`, with
+/// replies over `reverse`
+///
+/// # Errors
+///
+/// Returns [`ConfigError::InvalidFrameLayout`] if a `forward` frame cannot
+/// carry a sequence number and a data byte.
+pub fn run_stop_and_wait(
+    message: &[u8],
+    forward: &mut impl ArqLink,
+    reverse: &mut impl ArqLink,
+    config: &ArqConfig,
+) -> Result<ArqReport> {
+    let max_payload_bytes = forward.max_payload_bytes();
+    let chunk_bytes = max_payload_bytes.saturating_sub(1);
+    if chunk_bytes == 0 {
+        return Err(ConfigError::InvalidFrameLayout {
+            reason: format!("{max_payload_bytes}-byte frame payload cannot carry a sequence number and data"),
+        }.into());
+    }
+
+    let chunks: Vec<&[u8]> = message.chunks(chunk_bytes).collect();
+    let mut report = ArqReport {
+        packets: chunks.len(),
+        ..ArqReport::default()
+    };
+    // Next sequence number the receiver will deliver
+    let mut expected = 0u8;
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        // Wraps after 256 packets, see the module docs
+        let seq = index as u8;
+        let mut packet = vec![seq];
+        packet.extend_from_slice(chunk);
+
+        let mut acknowledged = false;
+        for attempt in 0..=config.max_retransmissions {
+            let data = if attempt == 0 {
+                forward.transmit(CommandType::SendData, &packet)
+            } else {
+                report.retransmissions += 1;
+                forward.retransmit(CommandType::SendData, &packet)
+            };
+            report.transmissions += 1;
+            report.elapsed_secs += data.airtime_secs;
+
+            // The receiver delivers new packets once and acknowledges
+            // duplicates (whose ACK was lost) again
+            let reply = match data.reception {
+                Reception::Delivered { command: Some(CommandType::SendData), payload } => {
+                    payload.split_first().map(|(&rx_seq, body)| {
+                        if rx_seq == expected {
+                            report.delivered.extend_from_slice(body);
+                            expected = expected.wrapping_add(1);
+                        }
+                        (CommandType::Ack, rx_seq)
+                    })
+                }
+                Reception::Delivered { .. } | Reception::Corrupted { .. } => Some((CommandType::Nack, expected)),
+                Reception::Lost => None,
+            };
+
+            let Some((command, reply_seq)) = reply else {
+                report.elapsed_secs += config.ack_timeout_secs;
+                report.timeouts += 1;
+                continue;
+            };
+            let answer = reverse.transmit(command, &[reply_seq]);
+            report.replies += 1;
+            match answer.reception {
+                Reception::Delivered { command: Some(CommandType::Ack), payload } if payload == [seq] => {
+                    report.elapsed_secs += answer.airtime_secs;
+                    acknowledged = true;
+                    break;
+                }
+                Reception::Delivered { command: Some(CommandType::Nack), payload } if payload == [seq] => {
+                    report.elapsed_secs += answer.airtime_secs;
+                    report.nacks += 1;
+                }
+                _ => {
+                    report.elapsed_secs += config.ack_timeout_secs.max(answer.airtime_secs);
+                    report.timeouts += 1;
+                }
+            }
+        }
+
+        if !acknowledged {
+            return Ok(report);
+        }
+    }
+
+    report.complete = true;
+    Ok(report)
+}
+
+/// ARQ outcome at one channel SNR
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArqSweepPoint {
+    /// Es/N0 of both directions, in dB
+    pub snr_db: f64,
+    pub report: ArqReport,
+}
+
+/// Run [`run_stop_and_wait`] between two [`ChimeraProcessor`] endpoints at
+/// each of `snrs_db`
+///
+/// # Errors
+///
+/// Returns the [`ChimeraProcessor::new`] error for an invalid `processor`
+/// configuration, and the [`run_stop_and_wait`] error if its frames are too
+/// small for ARQ packets.
+pub fn sweep_snr(
+    message: &[u8],
+    snrs_db: &[f64],
+    processor: &ProcessorConfig,
+    config: &ArqConfig,
+) -> Result<Vec<ArqSweepPoint>> {
+    snrs_db
+        .iter()
+        .map(|&snr_db| {
+            let endpoint = || {
+                ChimeraProcessor::new(ProcessorConfig {
+                    channel: Some(ChannelParams {
+                        snr_db,
+                        ..ChannelParams::default()
+                    }),
+                    ..processor.clone()
+                })
+            };
+            let (mut forward, mut reverse) = (endpoint()?, endpoint()?);
+            Ok(ArqSweepPoint {
+                snr_db,
+                report: run_stop_and_wait(message, &mut forward, &mut reverse, config)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// What happens to the next transmission over a [`ScriptedLink`]
+    #[derive(Clone, Copy)]
+    enum Fate {
+        Pass,
+        Corrupt,
+        Lose,
+    }
+
+    /// Link whose transmissions meet scripted fates, passing once the script runs out
+    struct ScriptedLink {
+        fates: VecDeque<Fate>,
+        airtime_secs: f64,
+        max_payload_bytes: usize,
+        /// Whether each transmission so far was a retransmission
+        resent: Vec<bool>,
+    }
+
+    impl ScriptedLink {
+        fn new(fates: &[Fate], airtime_secs: f64) -> Self {
+            Self {
+                fates: fates.iter().copied().collect(),
+                airtime_secs,
+                max_payload_bytes: 5,
+                resent: Vec::new(),
+            }
+        }
+    }
+
+    impl ArqLink for ScriptedLink {
+        fn max_payload_bytes(&self) -> usize {
+            self.max_payload_bytes
+        }
+
+        fn transmit(&mut self, command: CommandType, payload: &[u8]) -> Transmission {
+            self.resent.push(false);
+            self.deliver(command, payload)
+        }
+
+        fn retransmit(&mut self, command: CommandType, payload: &[u8]) -> Transmission {
+            self.resent.push(true);
+            self.deliver(command, payload)
+        }
+    }
+
+    impl ScriptedLink {
+        fn deliver(&mut self, command: CommandType, payload: &[u8]) -> Transmission {
+            let reception = match self.fates.pop_front().unwrap_or(Fate::Pass) {
+                Fate::Pass => Reception::Delivered {
+                    command: Some(command),
+                    payload: payload.to_vec(),
+                },
+                Fate::Corrupt => Reception::Corrupted { command: Some(command) },
+                Fate::Lose => Reception::Lost,
+            };
+            Transmission {
+                airtime_secs: self.airtime_secs,
+                reception,
+            }
+        }
+    }
+
+    const MESSAGE: &[u8] = b"stop and wait";
+
+    fn run(forward: &[Fate], reverse: &[Fate]) -> ArqReport {
+        run_stop_and_wait(
+            MESSAGE,
+            &mut ScriptedLink::new(forward, 8.0),
+            &mut ScriptedLink::new(reverse, 4.0),
+            &ArqConfig {
+                max_retransmissions: 2,
+                ack_timeout_secs: 10.0,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn clean_link_sends_each_packet_once() {
+        let report = run(&[], &[]);
+        assert!(report.complete);
+        assert_eq!(report.delivered, MESSAGE);
+        // Four data bytes per packet after the sequence number
+        assert_eq!(report.packets, 4);
+        assert_eq!(report.transmissions, 4);
+        assert_eq!(report.retransmissions, 0);
+        assert_eq!(report.elapsed_secs, 4.0 * (8.0 + 4.0));
+        assert_eq!(report.goodput_bps(), MESSAGE.len() as f64 * 8.0 / 48.0);
+    }
+
+    #[test]
+    fn corrupted_frame_is_nacked_and_resent() {
+        let report = run(&[Fate::Pass, Fate::Corrupt], &[]);
+        assert!(report.complete);
+        assert_eq!(report.delivered, MESSAGE);
+        assert_eq!(report.nacks, 1);
+        assert_eq!(report.retransmissions, 1);
+        assert_eq!(report.timeouts, 0);
+    }
+
+    #[test]
+    fn only_resent_packets_are_retransmissions() {
+        let mut forward = ScriptedLink::new(&[Fate::Pass, Fate::Corrupt, Fate::Lose], 8.0);
+        let report = run_stop_and_wait(
+            MESSAGE,
+            &mut forward,
+            &mut ScriptedLink::new(&[], 4.0),
+            &ArqConfig::default(),
+        )
+        .unwrap();
+        assert!(report.complete);
+        assert_eq!(forward.resent, [false, false, true, true, false, false]);
+    }
+
+    #[test]
+    fn sequence_numbers_wrap_after_256_packets() {
+        // Four data bytes per packet; the ACK of packet 256 (sequence 0) is lost
+        let message: Vec<u8> = (0..260 * 4).map(|i| i as u8).collect();
+        let mut reverse_fates = vec![Fate::Pass; 256];
+        reverse_fates.push(Fate::Lose);
+        let report = run_stop_and_wait(
+            &message,
+            &mut ScriptedLink::new(&[], 8.0),
+            &mut ScriptedLink::new(&reverse_fates, 4.0),
+            &ArqConfig::default(),
+        )
+        .unwrap();
+        assert!(report.complete);
+        // The duplicate of sequence 0 is acknowledged, not delivered again
+        assert_eq!(report.delivered, message);
+        assert_eq!(report.retransmissions, 1);
+    }
+
+    #[test]
+    fn lost_frames_and_replies_time_out() {
+        // The first data frame is lost; then the ACK of the resent frame is
+        let report = run(&[Fate::Lose], &[Fate::Lose]);
+        assert!(report.complete);
+        // The duplicate after the lost ACK is acknowledged but not delivered twice
+        assert_eq!(report.delivered, MESSAGE);
+        assert_eq!(report.timeouts, 2);
+        assert_eq!(report.retransmissions, 2);
+        assert_eq!(report.elapsed_secs, 6.0 * 8.0 + 2.0 * 10.0 + 4.0 * 4.0);
+    }
+
+    #[test]
+    fn transfer_is_abandoned_after_max_retransmissions() {
+        let report = run(&[Fate::Pass, Fate::Lose, Fate::Lose, Fate::Lose], &[]);
+        assert!(!report.complete);
+        assert_eq!(report.delivered, &MESSAGE[..4]);
+        assert_eq!(report.transmissions, 4);
+        assert_eq!(report.timeouts, 3);
+    }
+
+    #[test]
+    fn frames_without_room_for_data_are_rejected() {
+        let mut forward = ScriptedLink::new(&[], 8.0);
+        forward.max_payload_bytes = 1;
+        let result = run_stop_and_wait(MESSAGE, &mut forward, &mut ScriptedLink::new(&[], 4.0), &ArqConfig::default());
+        assert!(matches!(
+            result,
+            Err(crate::errors::ChimeraError::Config(ConfigError::InvalidFrameLayout { .. }))
+        ));
+        assert!(forward.resent.is_empty());
+    }
+}
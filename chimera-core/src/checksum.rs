@@ -0,0 +1,74 @@
+//! Frame checksums.
+//!
+//! The checksum covers the target ID, command and payload bits of a frame.
+//! Bits are packed MSB first into bytes (the last byte zero-padded) and the
+//! CRC is computed over those bytes with the standard parameters, so values
+//! match common CRC tools for byte-aligned input.
+
+use crate::config::FrameChecksum;
+use crate::utils::pack_bits;
+
+/// CRC-16/CCITT-FALSE: polynomial 0x1021, initial value 0xFFFF, no reflection
+pub fn crc16_ccitt(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0xFFFF, |crc, &byte| {
+        (0..8).fold(crc ^ (u16::from(byte) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// CRC-32 (IEEE 802.3): reflected polynomial 0xEDB88320, initial value and
+/// final XOR 0xFFFFFFFF
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(0xFFFF_FFFF, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+/// Computes `kind` over `bits` (0 for [`FrameChecksum::None`]).
+pub fn compute(kind: FrameChecksum, bits: &[u8]) -> u32 {
+    match kind {
+        FrameChecksum::None => 0,
+        FrameChecksum::Crc16Ccitt => u32::from(crc16_ccitt(&pack_bits(bits))),
+        FrameChecksum::Crc32 => crc32(&pack_bits(bits)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bytes_to_bitstream;
+
+    #[test]
+    fn standard_check_values() {
+        assert_eq!(crc16_ccitt(b"123456789"), 0x29B1);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            compute(FrameChecksum::Crc32, &bytes_to_bitstream(b"123456789")),
+            0xCBF4_3926
+        );
+    }
+
+    #[test]
+    fn single_bit_errors_change_the_checksum() {
+        let bits = bytes_to_bitstream(b"target/command/payload");
+        for kind in [FrameChecksum::Crc16Ccitt, FrameChecksum::Crc32] {
+            let reference = compute(kind, &bits);
+            for pos in 0..bits.len() {
+                let mut corrupted = bits.clone();
+                corrupted[pos] ^= 1;
+                assert_ne!(compute(kind, &corrupted), reference, "{kind:?} bit {pos}");
+            }
+        }
+    }
+}
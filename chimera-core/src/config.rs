@@ -1,9 +1,15 @@
 //! Configuration types for the Chimera pipeline.
 use serde::{Deserialize, Serialize};
-use crate::errors::{ConfigError, Result};
+use crate::errors::{ConfigError, EncodingError, Result};
 use crate::protocol::CommandType;
+use std::collections::VecDeque;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
+mod presets;
+
+pub use presets::{ProtocolPreset, ProtocolRegistry};
+
 // Default value functions for serde
 fn default_true() -> bool { true }
 
@@ -213,16 +219,23 @@ pub struct RuntimeParams {
     pub channel: ChannelParams,
     pub thz_modulation: ThzModulationParams,
     pub signal_processing: SignalProcessingParams,
-    pub message: Option<String>, // Current message (updates wait for transmission complete)
+    /// Message being transmitted (kept current by an attached pipeline)
+    pub message: Option<String>,
+    /// Messages waiting for an attached pipeline, oldest first
+    pub queued_messages: VecDeque<String>,
 }
 
 impl RuntimeParams {
+    /// Most messages `queued_messages` holds
+    pub const MAX_QUEUED_MESSAGES: usize = 16;
+    
     pub fn new(config: &UserConfig, simulation: Option<&UserSimulationConfig>) -> Self {
         Self {
             channel: config.channel.clone(),
             thz_modulation: config.thz_modulation.clone(),
             signal_processing: config.signal_processing.clone(),
             message: simulation.map(|s| s.message.clone()),
+            queued_messages: VecDeque::new(),
         }
     }
 }
@@ -290,14 +303,36 @@ impl RealtimeController {
         Ok(())
     }
     
-    /// Update message (this should wait for current transmission to complete)
+    /// Queue a message; a pipeline attached with
+    /// `RealtimePipeline::attach_controller` sends it once the current
+    /// message's last frame has been sent
+    ///
+    /// Fails once [`RuntimeParams::MAX_QUEUED_MESSAGES`] messages are waiting.
     pub fn set_message(&self, message: String) -> Result<()> {
+        if message.len() > crate::payload::MAX_PAYLOAD_BYTES {
+            return Err(EncodingError::MessageTooLong {
+                message_len: message.len(),
+                max_len: crate::payload::MAX_PAYLOAD_BYTES,
+            }.into());
+        }
         let mut params = self.params.write()
             .map_err(|_| ConfigError::InvalidSnr { snr_db: 0.0 })?;
-        params.message = Some(message);
+        if params.queued_messages.len() >= RuntimeParams::MAX_QUEUED_MESSAGES {
+            return Err(EncodingError::QueueFull {
+                capacity: RuntimeParams::MAX_QUEUED_MESSAGES,
+            }.into());
+        }
+        params.queued_messages.push_back(message);
         Ok(())
     }
     
+    /// Remove and return the queued messages, oldest first
+    pub fn take_queued_messages(&self) -> Result<Vec<String>> {
+        let mut params = self.params.write()
+            .map_err(|_| ConfigError::InvalidSnr { snr_db: 0.0 })?;
+        Ok(params.queued_messages.drain(..).collect())
+    }
+    
     /// Batch update multiple parameters
     pub fn update<F>(&self, updater: F) -> Result<()>
     where
@@ -322,7 +357,10 @@ impl RealtimeController {
 // ============================================================================
 
 /// Internal protocol configuration - NOT exposed to users
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Fields missing from a serialized config take their default values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct InternalProtocolConfig {
     pub carrier_freq_hz: f64,
     pub qpsk_symbol_rate: usize,
@@ -331,6 +369,8 @@ pub struct InternalProtocolConfig {
     pub fsk_freq_zero_hz: f64,
     pub fsk_freq_one_hz: f64,
     pub sync_sequence_hex: String,
+    /// Sequence filling the sync field
+    pub sync_word: SyncWord,
     pub frame_layout: FrameLayout,
     pub current_frame_shift: usize,
     pub total_frames_shift: usize,
@@ -355,6 +395,34 @@ impl InternalProtocolConfig {
             .unwrap_or(0x0001) // Default to send_data
     }
     
+    /// Raw bits of the sync field, before differential encoding
+    pub fn sync_bits(&self) -> Vec<u8> {
+        crate::sync::sync_field_bits(&self.sync_word, &self.sync_sequence_hex, self.frame_layout.sync_symbols)
+    }
+    
+    /// Bits of the command field holding the frame index
+    pub fn frame_index_bits(&self) -> usize {
+        self.total_frames_shift.saturating_sub(self.current_frame_shift).min(8)
+    }
+    
+    /// Bits of the command field holding the frame count
+    ///
+    /// The command value is 32 bits wide, whatever the field size.
+    pub fn frame_count_bits(&self) -> usize {
+        (self.frame_layout.command_type_symbols * 2)
+            .min(32)
+            .saturating_sub(self.total_frames_shift)
+            .min(8)
+    }
+    
+    /// Most frames one message can span: `max_frames`, limited by what the
+    /// command field's counters can number
+    pub fn max_frames_per_message(&self) -> usize {
+        self.max_frames
+            .min((1 << self.frame_count_bits()) - 1)
+            .min(1 << self.frame_index_bits())
+    }
+    
     /// Validate protocol configuration
     pub fn validate(&self) -> Result<()> {
         // Check sample rate validity
@@ -386,15 +454,39 @@ impl InternalProtocolConfig {
         // Validate frame layout
         self.frame_layout.validate()?;
         
+        crate::sync::validate(&self.sync_word, &self.sync_sequence_hex, self.frame_layout.sync_symbols)?;
+        
+        // The opcode sits below the frame index, which sits below the frame count
+        let largest_opcode = CommandType::Nack.to_opcode();
+        if (largest_opcode >> self.current_frame_shift.min(31)) != 0
+            || self.frame_index_bits() == 0
+            || self.frame_count_bits() == 0
+        {
+            return Err(ConfigError::InvalidFrameLayout {
+                reason: format!(
+                    "Frame counters (shifts {} and {}) do not fit the {}-bit command field",
+                    self.current_frame_shift,
+                    self.total_frames_shift,
+                    self.frame_layout.command_type_symbols * 2
+                ),
+            }.into());
+        }
+        
         Ok(())
     }
 }
 
-/// Get hardcoded protocol preset by name
+/// Get a built-in protocol preset by name
+///
+/// See [`ProtocolRegistry`] for presets loaded from TOML files.
 pub fn get_protocol_preset(name: &str) -> Result<InternalProtocolConfig> {
-    match name {
-        "whisper" => Ok(InternalProtocolConfig {
-            // Hardcoded whisper protocol parameters
+    ProtocolRegistry::builtin().protocol(name)
+}
+
+impl Default for InternalProtocolConfig {
+    /// The "whisper" protocol
+    fn default() -> Self {
+        Self {
             carrier_freq_hz: 12000.0,
             qpsk_symbol_rate: 16,
             qpsk_bandwidth_hz: 20.0,
@@ -402,6 +494,7 @@ pub fn get_protocol_preset(name: &str) -> Result<InternalProtocolConfig> {
             fsk_freq_zero_hz: 11999.0,
             fsk_freq_one_hz: 12001.0,
             sync_sequence_hex: "A5A5A5A5".to_string(),
+            sync_word: SyncWord::Hex,
             frame_layout: FrameLayout::default(),
             current_frame_shift: 16,
             total_frames_shift: 24,
@@ -412,19 +505,103 @@ pub fn get_protocol_preset(name: &str) -> Result<InternalProtocolConfig> {
             // Runtime defaults
             enable_qpsk: true,
             enable_fsk: true,
-        }),
-        _ => Err(ConfigError::InvalidFrameLayout {
-            reason: format!("Unknown protocol: {}", name)
-        }.into()),
+        }
     }
 }
 
-impl Default for InternalProtocolConfig {
+/// Receiver address filter over the frame target ID
+///
+/// With `accept_all` set (the default) every frame is delivered. Otherwise a
+/// frame is delivered if its target ID equals one of `ids`, matches one of
+/// `masks`, or is `broadcast_id`; with no IDs and no masks that leaves only
+/// broadcasts. Addresses are hex strings like `target_id_hex` and are
+/// compared as numbers, so target fields up to 64 bits wide are supported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AddressFilter {
+    /// Deliver every frame, ignoring the entries below
+    pub accept_all: bool,
+    /// Target IDs accepted exactly
+    pub ids: Vec<String>,
+    /// Target ID patterns compared on the mask's set bits only
+    pub masks: Vec<AddressMask>,
+    /// Target ID every receiver accepts (`None` disables broadcast)
+    pub broadcast_id: Option<String>,
+}
+
+/// Target ID pattern: `id` and the received ID must agree where `mask` is 1
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressMask {
+    pub id: String,
+    pub mask: String,
+}
+
+impl Default for AddressFilter {
     fn default() -> Self {
-        get_protocol_preset("whisper").unwrap()
+        Self {
+            accept_all: true,
+            ids: Vec::new(),
+            masks: Vec::new(),
+            broadcast_id: Some("FFFFFFFF".to_string()),
+        }
+    }
+}
+
+impl AddressFilter {
+    /// Accepts `ids` exactly, plus the default broadcast ID
+    pub fn exact<S: Into<String>>(ids: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            accept_all: false,
+            ids: ids.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+    
+    /// Accepts only the default broadcast ID
+    pub fn broadcast_only() -> Self {
+        Self::exact(Vec::<String>::new())
+    }
+    
+    /// Whether a frame with target ID `target_id` is delivered
+    ///
+    /// Entries that are not valid addresses never match; see [`Self::validate`].
+    pub fn accepts(&self, target_id: u64) -> bool {
+        let matches = |address: &str| parse_address(address).ok() == Some(target_id);
+        self.accept_all
+            || self.ids.iter().any(|id| matches(id))
+            || self.broadcast_id.as_deref().is_some_and(matches)
+            || self.masks.iter().any(|pattern| {
+                match (parse_address(&pattern.id), parse_address(&pattern.mask)) {
+                    (Ok(id), Ok(mask)) => (id ^ target_id) & mask == 0,
+                    _ => false,
+                }
+            })
+    }
+    
+    /// Check every address parses as a hex value of up to 64 bits
+    pub fn validate(&self) -> Result<()> {
+        let addresses = self
+            .ids
+            .iter()
+            .chain(&self.broadcast_id)
+            .chain(self.masks.iter().flat_map(|pattern| [&pattern.id, &pattern.mask]));
+        for address in addresses {
+            parse_address(address)?;
+        }
+        Ok(())
     }
 }
 
+/// Parse a hex address of up to 64 bits (an optional `0x` prefix is allowed)
+pub fn parse_address(address: &str) -> Result<u64> {
+    let digits = address.trim_start_matches("0x").trim_start_matches("0X");
+    if digits.is_empty() || digits.len() > 16 {
+        return Err(ConfigError::InvalidAddress { address: address.to_string() }.into());
+    }
+    u64::from_str_radix(digits, 16)
+        .map_err(|_| ConfigError::InvalidAddress { address: address.to_string() }.into())
+}
+
 // ============================================================================
 // CONFIG BUILDER API
 // ============================================================================
@@ -535,6 +712,22 @@ pub struct FrameLayout {
     pub command_type_symbols: usize,
     pub data_payload_symbols: usize,
     pub ecc_symbols: usize,
+    /// Permutation applied to the FEC codeword before it is mapped into the
+    /// payload and ECC sections
+    #[serde(default)]
+    pub interleaving: BitInterleaving,
+    /// Mother code adapted to the payload/ECC split by shortening and
+    /// puncturing
+    #[serde(default)]
+    pub rate_matching: RateMatching,
+    /// CRC over the target ID, command and payload, carried in the last bits
+    /// of the FEC message
+    #[serde(default)]
+    pub checksum: FrameChecksum,
+    /// Whitening sequence added to the payload and ECC sections so long runs
+    /// of identical bits still produce phase transitions
+    #[serde(default)]
+    pub scrambling: Scrambling,
 }
 
 impl Default for FrameLayout {
@@ -546,10 +739,133 @@ impl Default for FrameLayout {
             command_type_symbols: 16,
             data_payload_symbols: 64,
             ecc_symbols: 16,
+            interleaving: BitInterleaving::default(),
+            rate_matching: RateMatching::default(),
+            checksum: FrameChecksum::default(),
+            scrambling: Scrambling::default(),
         }
     }
 }
 
+/// Sequence transmitted in the sync field
+///
+/// Its length is the layout's `sync_symbols`. The structured families are
+/// defined on the transmitted (differentially encoded) symbols, one chip per
+/// symbol, which is what the preamble correlator matches; see
+/// [`crate::sync::sidelobe_report`] for their peak-to-sidelobe ratios.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SyncWord {
+    /// `sync_sequence_hex`, left-padded with zeros to the sync field
+    #[default]
+    Hex,
+    /// Barker code (2, 3, 4, 5, 7, 11 or 13 symbols)
+    Barker,
+    /// Maximal-length LFSR sequence, truncated to the sync field
+    MSequence,
+    /// Zadoff–Chu sequence of `root` rounded to QPSK phases
+    ZadoffChu { root: usize },
+}
+
+/// Bit interleaving of the frame codeword
+///
+/// Spreads bursts of adjacent channel errors (cycle slips, fades) across the
+/// codeword so the FEC decoder sees scattered errors instead. Sequential
+/// decoders (Viterbi) gain the most; PEG already places consecutive LDPC
+/// codeword bits far apart in the Tanner graph.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BitInterleaving {
+    /// Codeword bits are sent in order
+    #[default]
+    None,
+    /// Written row by row into a `rows`-row matrix and read column by column,
+    /// so adjacent channel bits are `columns` apart in the codeword
+    Block { rows: usize },
+    /// Seeded pseudo-random permutation
+    PseudoRandom { seed: u64 },
+}
+
+/// Frame integrity check verified by the receiver after FEC decoding
+///
+/// Frames whose checksum does not match are reported and dropped instead of
+/// being delivered.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameChecksum {
+    /// Frames are delivered whenever FEC decoding produces a message
+    #[default]
+    None,
+    /// CRC-16/CCITT (polynomial 0x1021, initial value 0xFFFF)
+    Crc16Ccitt,
+    /// CRC-32 (IEEE 802.3)
+    Crc32,
+}
+
+impl FrameChecksum {
+    /// Checksum bits taken from each frame's FEC message
+    pub fn bits(&self) -> usize {
+        match self {
+            Self::None => 0,
+            Self::Crc16Ccitt => 16,
+            Self::Crc32 => 32,
+        }
+    }
+}
+
+/// Additive scrambler applied to the transmitted codeword
+///
+/// An LFSR sequence is XORed onto the payload and ECC sections and removed by
+/// the receiver before decoding. Repetitive payloads such as text, padding or
+/// constant bytes otherwise send long runs of identical phase steps: 0° steps
+/// (data bits 11) leave the Gardner timing loop without transitions, and any
+/// periodic pattern concentrates the spectrum in lines that bias the Costas
+/// loop. The LFSR restarts from `seed` in every frame, so a lost frame does
+/// not desynchronize later ones.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Scrambling {
+    /// Codeword bits are sent as they are
+    #[default]
+    None,
+    /// x^7 + x^4 + 1 (period 127, as in IEEE 802.11); `seed` is the non-zero
+    /// 7-bit initial state
+    Lfsr7 { seed: u16 },
+    /// x^15 + x^14 + 1 (period 32767, as in DVB); `seed` is the non-zero
+    /// 15-bit initial state
+    Lfsr15 { seed: u16 },
+}
+
+impl Scrambling {
+    /// LFSR degree and feedback tap, `None` when scrambling is off
+    pub fn polynomial(&self) -> Option<(u32, u32)> {
+        match self {
+            Self::None => None,
+            Self::Lfsr7 { .. } => Some((7, 4)),
+            Self::Lfsr15 { .. } => Some((15, 14)),
+        }
+    }
+}
+
+/// Adaptation of a fixed mother code to the layout's payload/ECC split
+///
+/// The mother code carries `mother_payload_symbols * 2` message bits in
+/// `(mother_payload_symbols + mother_ecc_symbols) * 2` code bits. Surplus
+/// message bits are shortened (fixed to zero and not sent) and surplus parity
+/// bits are punctured (not sent, decoded as erasures).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RateMatching {
+    /// The code is built for the layout's payload and ECC sizes directly
+    #[default]
+    None,
+    /// Shorten and puncture a code built for a larger payload/ECC split
+    Mother {
+        mother_payload_symbols: usize,
+        mother_ecc_symbols: usize,
+    },
+}
+
 impl FrameLayout {
     pub fn message_bits(&self) -> usize {
         self.data_payload_symbols * 2
@@ -566,6 +882,23 @@ impl FrameLayout {
     pub fn frame_bits(&self) -> usize {
         self.total_symbols * 2
     }
+
+    /// Layout the FEC code is constructed for: the mother code's payload and
+    /// ECC sizes under rate matching, otherwise this layout.
+    pub fn mother_layout(&self) -> FrameLayout {
+        match self.rate_matching {
+            RateMatching::None => self.clone(),
+            RateMatching::Mother {
+                mother_payload_symbols,
+                mother_ecc_symbols,
+            } => FrameLayout {
+                data_payload_symbols: mother_payload_symbols,
+                ecc_symbols: mother_ecc_symbols,
+                rate_matching: RateMatching::None,
+                ..self.clone()
+            },
+        }
+    }
     
     /// Validate frame layout consistency
     pub fn validate(&self) -> Result<()> {
@@ -595,6 +928,52 @@ impl FrameLayout {
                 reason: "total_symbols cannot be zero".to_string()
             }.into());
         }
+
+        if self.checksum.bits() >= self.message_bits() {
+            return Err(ConfigError::InvalidFrameLayout {
+                reason: format!(
+                    "{}-bit checksum leaves no room in a {}-bit payload",
+                    self.checksum.bits(),
+                    self.message_bits()
+                )
+            }.into());
+        }
+
+        if let Scrambling::Lfsr7 { seed } | Scrambling::Lfsr15 { seed } = self.scrambling {
+            let (degree, _) = self.scrambling.polynomial().unwrap_or_default();
+            if seed == 0 || u32::from(seed) >= 1 << degree {
+                return Err(ConfigError::InvalidFrameLayout {
+                    reason: format!("scrambler seed {seed:#x} is not a non-zero {degree}-bit state"),
+                }.into());
+            }
+        }
+
+        if let RateMatching::Mother { mother_payload_symbols, mother_ecc_symbols } = self.rate_matching {
+            // Shortening removes message bits and puncturing removes parity
+            // bits; neither can add bits the mother code does not have
+            if self.data_payload_symbols == 0 || self.data_payload_symbols > mother_payload_symbols {
+                return Err(ConfigError::InvalidFrameLayout {
+                    reason: format!(
+                        "rate {}/{} not achievable: payload of {} symbols cannot be shortened from the mother code's {}",
+                        self.message_bits(),
+                        self.codeword_bits(),
+                        self.data_payload_symbols,
+                        mother_payload_symbols
+                    )
+                }.into());
+            }
+            if self.ecc_symbols == 0 || self.ecc_symbols > mother_ecc_symbols {
+                return Err(ConfigError::InvalidFrameLayout {
+                    reason: format!(
+                        "rate {}/{} not achievable: ECC of {} symbols cannot be punctured from the mother code's {}",
+                        self.message_bits(),
+                        self.codeword_bits(),
+                        self.ecc_symbols,
+                        mother_ecc_symbols
+                    )
+                }.into());
+            }
+        }
         
         Ok(())
     }
@@ -709,14 +1088,168 @@ pub struct LDPCConfig {
     pub dv: usize,
     pub dc: usize,
     pub seed: Option<u64>,
+    /// Belief-propagation decoder settings
+    #[serde(default)]
+    pub decoder: LDPCDecoderConfig,
+    /// Code family carried in the frame codeword
+    #[serde(default)]
+    pub fec: FecScheme,
+    /// Optional outer code spanning the frames of a message
+    #[serde(default)]
+    pub outer_code: OuterCode,
+    /// Parity-check matrix to load instead of the PEG construction
+    /// (`.alist`, or sparse `row col` text for any other extension)
+    #[serde(default)]
+    pub parity_check_file: Option<PathBuf>,
+    /// How the parity-check matrix is constructed (ignored when
+    /// `parity_check_file` is set)
+    #[serde(default)]
+    pub construction: LdpcConstruction,
+    /// Most copies of a repeated frame soft-combined before decoding
+    /// (1 decodes every copy on its own)
+    #[serde(default = "default_combining_depth")]
+    pub combining_depth: usize,
 }
 
+fn default_combining_depth() -> usize { 4 }
+
 impl Default for LDPCConfig {
     fn default() -> Self {
         Self {
             dv: 2,
             dc: 10,
             seed: Some(42),
+            decoder: LDPCDecoderConfig::default(),
+            fec: FecScheme::default(),
+            outer_code: OuterCode::default(),
+            parity_check_file: None,
+            construction: LdpcConstruction::default(),
+            combining_depth: default_combining_depth(),
+        }
+    }
+}
+
+/// Parity-check matrix construction for the frame LDPC code
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LdpcConstruction {
+    /// Progressive edge growth with degrees `dv`/`dc` over a staircase parity part
+    #[default]
+    ProgressiveEdgeGrowth,
+    /// Quasi-cyclic code lifted from `base_graph`, shortened to the layout
+    QuasiCyclic { base_graph: BaseGraph },
+}
+
+/// Base matrix of a quasi-cyclic LDPC code
+///
+/// Shifts are cyclic shifts of a `lifting x lifting` identity, `-1` a zero
+/// block. The last base rows' columns must form the parity part.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BaseGraph {
+    /// IEEE 802.11n rate-1/2 matrix (12 x 24, Z0 = 27)
+    Ieee80211nHalf,
+    /// IEEE 802.11n rate-5/6 matrix (4 x 24, Z0 = 27)
+    Ieee80211nFiveSixths,
+    /// User-supplied shifts defined for lifting size `lifting`
+    Custom { shifts: Vec<Vec<i32>>, lifting: usize },
+}
+
+/// Forward error correction code used for the frame codeword
+///
+/// Every scheme fills the layout's payload + ECC sections; only LDPC uses
+/// `dv`/`dc`/`seed` and the belief-propagation `decoder` settings.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FecScheme {
+    /// LDPC code built per `LDPCConfig::construction` (k = payload bits)
+    #[default]
+    Ldpc,
+    /// Terminated rate-1/2, K=7 (133, 171) convolutional code with soft Viterbi decoding
+    Convolutional,
+    /// Every message bit sent `factor` times and soft-combined
+    Repetition { factor: usize },
+    /// Shortened polar code (k = payload bits, plus an 8-bit CRC) with
+    /// CRC-aided successive-cancellation list decoding (`list_size` 1 is SC)
+    Polar { list_size: usize },
+}
+
+/// Outer code across the frames of a multi-frame message
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OuterCode {
+    /// Frames are independent; losing one loses the message
+    #[default]
+    None,
+    /// GF(256) Reed-Solomon code adding `parity_frames` frames, recovering up
+    /// to that many frames lost by the inner decoder
+    ReedSolomon { parity_frames: usize },
+}
+
+impl OuterCode {
+    /// Parity frames appended to every message (0 without an outer code)
+    pub fn parity_frames(&self) -> usize {
+        match self {
+            Self::None => 0,
+            Self::ReedSolomon { parity_frames } => *parity_frames,
+        }
+    }
+}
+
+/// Check-node update rule used by the belief-propagation decoder
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BeliefPropagationAlgorithm {
+    /// Exact sum-product (tanh rule)
+    SumProduct,
+    /// Min-sum with check messages scaled by `alpha` (0 < alpha <= 1)
+    NormalizedMinSum { alpha: f64 },
+    /// Min-sum with `beta` subtracted from check message magnitudes
+    OffsetMinSum { beta: f64 },
+}
+
+impl Default for BeliefPropagationAlgorithm {
+    fn default() -> Self {
+        Self::NormalizedMinSum { alpha: 0.75 }
+    }
+}
+
+/// Iterative LDPC decoder configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LDPCDecoderConfig {
+    /// Check-node update rule
+    pub algorithm: BeliefPropagationAlgorithm,
+
+    /// Maximum number of message-passing iterations
+    pub max_iterations: usize,
+
+    /// Stop as soon as the hard decision satisfies every parity check
+    pub early_stop: bool,
+
+    /// Order in which check nodes are updated
+    pub schedule: DecodingSchedule,
+}
+
+/// Message-passing schedule of the belief-propagation decoder
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DecodingSchedule {
+    /// Update every check, then every variable, per iteration
+    #[default]
+    Flooding,
+    /// Update checks one at a time against the latest posteriors, which
+    /// typically converges in about half the iterations
+    Layered,
+}
+
+impl Default for LDPCDecoderConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: BeliefPropagationAlgorithm::default(),
+            max_iterations: 50,
+            early_stop: true,
+            schedule: DecodingSchedule::default(),
         }
     }
 }
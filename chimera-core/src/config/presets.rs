@@ -0,0 +1,258 @@
+//! Named protocol presets loaded from TOML.
+//!
+//! A preset file names one protocol and holds its physical layer, framing and
+//! FEC:
+//!
+//! ```toml
+//! name = "burst-telemetry"
+//! description = "High-rate bursts"
+//!
+//! [protocol]
+//! qpsk_symbol_rate = 32
+//! sync_sequence_hex = "7E7E7E7E"
+//!
+//! [protocol.frame_layout]
+//! total_symbols = 96
+//! # ...
+//!
+//! [ldpc]
+//! dv = 3
+//! dc = 9
+//! seed = 42
+//! ```
+//!
+//! Fields left out of `[protocol]` take the "whisper" defaults; a missing
+//! `[ldpc]` table selects the default code.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{InternalProtocolConfig, LDPCConfig};
+use crate::errors::{ChimeraError, ConfigError, Result};
+use crate::fec::build_codec;
+
+/// Presets compiled into the library, in the order [`ProtocolRegistry::builtin`] lists them
+const BUILTIN_PRESETS: [(&str, &str); 3] = [
+    ("whisper.toml", include_str!("../../presets/whisper.toml")),
+    ("burst-telemetry.toml", include_str!("../../presets/burst-telemetry.toml")),
+    ("deep-space-probe.toml", include_str!("../../presets/deep-space-probe.toml")),
+];
+
+/// A named protocol: carrier, symbol rate, FSK tones, sync word, frame layout and FEC
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProtocolPreset {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub protocol: InternalProtocolConfig,
+    #[serde(default)]
+    pub ldpc: LDPCConfig,
+}
+
+impl ProtocolPreset {
+    /// Parse a preset from TOML; `source_name` identifies it in errors
+    pub fn from_toml(text: &str, source_name: &str) -> Result<Self> {
+        let preset: Self = toml::from_str(text).map_err(|err| invalid(source_name, err.message()))?;
+        if preset.name.is_empty() {
+            return Err(invalid(source_name, "preset name is empty"));
+        }
+        validate(&preset, source_name)?;
+        Ok(preset)
+    }
+
+    /// Serialize the preset in the format [`Self::from_toml`] reads
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).map_err(|err| invalid(&self.name, err.to_string()))
+    }
+}
+
+/// [`InternalProtocolConfig::validate`] plus building the preset's codec for
+/// its frame layout, naming the preset in the error
+fn validate(preset: &ProtocolPreset, source_name: &str) -> Result<()> {
+    preset
+        .protocol
+        .validate()
+        .and_then(|()| build_codec(&preset.protocol.frame_layout, &preset.ldpc).map(drop))
+        .map_err(|err| match err {
+            ChimeraError::Config(reason) => invalid(source_name, reason.to_string()),
+            ChimeraError::Ldpc(reason) => invalid(source_name, reason.to_string()),
+            other => other,
+        })
+}
+
+fn invalid(source_name: &str, reason: impl Into<String>) -> ChimeraError {
+    ConfigError::InvalidPreset {
+        source_name: source_name.to_string(),
+        reason: reason.into(),
+    }
+    .into()
+}
+
+/// Protocol presets by name
+///
+/// Starts from the built-in presets or empty; presets registered later
+/// replace earlier ones of the same name, so user files can override the
+/// built-ins. Every preset is validated when it is registered.
+#[derive(Debug, Clone, Default)]
+pub struct ProtocolRegistry {
+    presets: Vec<ProtocolPreset>,
+}
+
+impl ProtocolRegistry {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The presets compiled into the library: "whisper", "burst-telemetry"
+    /// and "deep-space-probe"
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for (file_name, text) in BUILTIN_PRESETS {
+            let preset = ProtocolPreset::from_toml(text, file_name)
+                .expect("built-in protocol presets are valid");
+            registry.presets.push(preset);
+        }
+        registry
+    }
+
+    /// Add `preset`, replacing any preset of the same name
+    pub fn register(&mut self, preset: ProtocolPreset) -> Result<()> {
+        validate(&preset, &preset.name)?;
+        match self.presets.iter_mut().find(|existing| existing.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+        Ok(())
+    }
+
+    /// Parse and register a preset from TOML text, returning its name
+    pub fn load_str(&mut self, text: &str, source_name: &str) -> Result<String> {
+        let preset = ProtocolPreset::from_toml(text, source_name)?;
+        let name = preset.name.clone();
+        self.register(preset)?;
+        Ok(name)
+    }
+
+    /// Parse and register the preset in a TOML file, returning its name
+    pub fn load_file(&mut self, path: &Path) -> Result<String> {
+        let source_name = path.display().to_string();
+        let text = std::fs::read_to_string(path).map_err(|err| invalid(&source_name, err.to_string()))?;
+        self.load_str(&text, &source_name)
+    }
+
+    /// Register every `.toml` file in `dir`, in file-name order, returning
+    /// the preset names
+    pub fn load_dir(&mut self, dir: &Path) -> Result<Vec<String>> {
+        let source_name = dir.display().to_string();
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(|err| invalid(&source_name, err.to_string()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml")))
+            .collect();
+        paths.sort();
+        paths.iter().map(|path| self.load_file(path)).collect()
+    }
+
+    /// The preset called `name`
+    pub fn get(&self, name: &str) -> Option<&ProtocolPreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    /// Protocol configuration of the preset called `name`
+    pub fn protocol(&self, name: &str) -> Result<InternalProtocolConfig> {
+        self.get(name)
+            .map(|preset| preset.protocol.clone())
+            .ok_or_else(|| ConfigError::UnknownProtocol { name: name.to_string() }.into())
+    }
+
+    /// Preset names in registration order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.iter().map(|preset| preset.name.as_str())
+    }
+
+    /// Presets in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &ProtocolPreset> {
+        self.presets.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FrameLayout;
+
+    #[test]
+    fn builtin_presets_are_listed_in_order() {
+        let registry = ProtocolRegistry::builtin();
+        let names: Vec<_> = registry.names().collect();
+        assert_eq!(names, ["whisper", "burst-telemetry", "deep-space-probe"]);
+    }
+
+    #[test]
+    fn whisper_preset_is_the_default_protocol() {
+        let registry = ProtocolRegistry::builtin();
+        let whisper = registry.get("whisper").unwrap();
+        assert_eq!(whisper.protocol, InternalProtocolConfig::default());
+        assert_eq!(whisper.ldpc, LDPCConfig::default());
+    }
+
+    #[test]
+    fn unknown_preset_is_an_error() {
+        let err = ProtocolRegistry::builtin().protocol("shout").unwrap_err();
+        assert!(err.to_string().contains("shout"));
+    }
+
+    #[test]
+    fn partial_protocol_tables_keep_the_defaults() {
+        let mut registry = ProtocolRegistry::new();
+        let name = registry
+            .load_str("name = \"slow\"\n[protocol]\nqpsk_symbol_rate = 8\n", "slow.toml")
+            .unwrap();
+        let protocol = registry.protocol(&name).unwrap();
+        assert_eq!(protocol.qpsk_symbol_rate, 8);
+        assert_eq!(protocol.carrier_freq_hz, InternalProtocolConfig::default().carrier_freq_hz);
+        assert_eq!(protocol.frame_layout, FrameLayout::default());
+    }
+
+    #[test]
+    fn later_presets_replace_earlier_ones() {
+        let mut registry = ProtocolRegistry::builtin();
+        registry
+            .load_str("name = \"whisper\"\n[protocol]\ncarrier_freq_hz = 10000.0\n", "whisper.toml")
+            .unwrap();
+        assert_eq!(registry.names().count(), 3);
+        assert_eq!(registry.protocol("whisper").unwrap().carrier_freq_hz, 10000.0);
+    }
+
+    #[test]
+    fn invalid_presets_are_rejected() {
+        let mut registry = ProtocolRegistry::new();
+        let bad_sync = "name = \"bad\"\n[protocol]\nsync_sequence_hex = \"A5A5A5A5A5\"\n";
+        assert!(registry.load_str(bad_sync, "bad.toml").is_err());
+        assert!(registry.load_str("description = \"no name\"\n", "anon.toml").is_err());
+        assert!(registry.get("bad").is_none());
+    }
+
+    #[test]
+    fn presets_whose_code_does_not_fit_the_layout_are_rejected() {
+        let mut registry = ProtocolRegistry::new();
+        let no_repeats = "name = \"bad\"\n[ldpc.fec]\ntype = \"repetition\"\nfactor = 0\n";
+        let err = registry.load_str(no_repeats, "bad.toml").unwrap_err();
+        assert!(
+            matches!(&err, ChimeraError::Config(ConfigError::InvalidPreset { source_name, .. }) if source_name == "bad.toml"),
+            "{err}"
+        );
+        assert!(registry.get("bad").is_none());
+    }
+
+    #[test]
+    fn presets_round_trip_through_toml() {
+        for preset in ProtocolRegistry::builtin().iter() {
+            let text = preset.to_toml().unwrap();
+            assert_eq!(&ProtocolPreset::from_toml(&text, &preset.name).unwrap(), preset);
+        }
+    }
+}
@@ -1,12 +1,18 @@
 //! Demodulation and decoding stage implementations.
+use std::collections::HashMap;
 use std::f64::consts::FRAC_1_SQRT_2;
+use std::sync::Arc;
 
 use num_complex::Complex64;
 
-use crate::config::InternalProtocolConfig;
-use crate::diagnostics::{DemodulationDiagnostics, SymbolDecision};
-use crate::ldpc::{decode_ldpc, LDPCMatrices};
-use crate::utils::{hex_to_bitstream, LogCollector};
+use crate::checksum;
+use crate::config::{parse_address, AddressFilter, FrameChecksum, InternalProtocolConfig, LDPCDecoderConfig, OuterCode};
+use crate::diagnostics::{DemodulationDiagnostics, FrameDescriptor, SymbolDecision};
+use crate::errors::DecodingError;
+use crate::fec::{check_codeword_fits, FecCodec, Interleaver, LdpcCodec, OuterCodeAssembler, Scrambler};
+use crate::ldpc::{DecodeOutcome, LDPCMatrices};
+use crate::protocol::CommandType;
+use crate::utils::{pack_bits, LogCollector};
 
 /// Convert Gray-coded bits to phase index (0-3)
 /// Made public for ambiguity resolution logic
@@ -120,14 +126,255 @@ pub fn demodulate_qpsk_symbol(symbol: Complex64) -> [u8; 2] {
     best_bits
 }
 
+/// How the soft demapper combines constellation-point likelihoods into LLRs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LlrMethod {
+    /// Log-sum-exp over every hypothesis (true log-likelihood ratio)
+    #[default]
+    Exact,
+    /// Keep only the most likely hypothesis on each side (max-log approximation)
+    MaxLog,
+}
+
+/// Smallest noise variance used when computing LLRs, so clean symbols give
+/// large but finite values.
+const MIN_NOISE_VARIANCE: f64 = 1e-4;
+
+/// Combine log-domain terms according to `method`
+fn log_sum(terms: impl Iterator<Item = f64>, method: LlrMethod) -> f64 {
+    let terms: Vec<f64> = terms.collect();
+    let max = terms.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if method == LlrMethod::MaxLog || !max.is_finite() {
+        return max;
+    }
+    max + terms.iter().map(|t| (t - max).exp()).sum::<f64>().ln()
+}
+
+/// Soft-demap a QPSK symbol to per-bit LLRs
+///
+/// LLRs follow the `ln(P(b = 0) / P(b = 1))` convention used by the LDPC
+/// decoder. The symbol is expected at unit average energy (see
+/// [`normalize_symbols`]) and `noise_variance` is the complex noise variance
+/// N0 per symbol (see [`estimate_noise_variance`]).
+pub fn qpsk_symbol_llrs(symbol: Complex64, noise_variance: f64, method: LlrMethod) -> [f64; 2] {
+    let n0 = noise_variance.max(MIN_NOISE_VARIANCE);
+    let reference = qpsk_constellation();
+    let mut llrs = [0.0; 2];
+
+    for (bit, llr) in llrs.iter_mut().enumerate() {
+        let metric = |value: u8| {
+            log_sum(
+                reference
+                    .iter()
+                    .filter(|(_, bits)| bits[bit] == value)
+                    .map(|(point, _)| -(symbol - point).norm_sqr() / n0),
+                method,
+            )
+        };
+        *llr = metric(0) - metric(1);
+    }
+
+    llrs
+}
+
+/// Scale symbols to unit average energy
+pub fn normalize_symbols(symbols: &[Complex64]) -> Vec<Complex64> {
+    if symbols.is_empty() {
+        return Vec::new();
+    }
+    let rms = (symbols.iter().map(|s| s.norm_sqr()).sum::<f64>() / symbols.len() as f64).sqrt();
+    if rms <= 0.0 {
+        return symbols.to_vec();
+    }
+    symbols.iter().map(|s| s / rms).collect()
+}
+
+/// Loop gain of [`track_residual_phase`]: fast enough to follow the carrier
+/// loop's residual wander, slow enough to average over a few symbols of noise
+const PHASE_TRACKING_GAIN: f64 = 0.3;
+
+/// Remove the residual carrier rotation left by the Costas loop
+///
+/// A first-order decision-directed loop nudges its phase estimate towards
+/// each symbol's nearest constellation point. The carrier loop leaves the
+/// constellation wandering by tens of degrees within a frame; untracked,
+/// every crossing of a decision boundary flips a differential decision,
+/// which the FEC can absorb but the uncoded header cannot. Whole quarter
+/// turns are left in place: the differential code is blind to them.
+pub fn track_residual_phase(symbols: &[Complex64], gain: f64) -> Vec<Complex64> {
+    let mut phase = 0.0;
+    symbols
+        .iter()
+        .map(|&symbol| {
+            let derotated = symbol * Complex64::from_polar(1.0, -phase);
+            let nearest = Complex64::new(
+                FRAC_1_SQRT_2.copysign(derotated.re),
+                FRAC_1_SQRT_2.copysign(derotated.im),
+            );
+            phase += gain * (derotated * nearest.conj()).arg();
+            derotated
+        })
+        .collect()
+}
+
+/// Decision-directed estimate of the complex noise variance N0
+///
+/// Symbols should already be normalized to unit average energy. The estimate
+/// is the mean squared distance to the nearest constellation point, so it
+/// under-reads at very low SNR where decisions are unreliable.
+pub fn estimate_noise_variance(symbols: &[Complex64]) -> f64 {
+    if symbols.is_empty() {
+        return 1.0;
+    }
+    let reference = qpsk_constellation();
+    let total: f64 = symbols
+        .iter()
+        .map(|s| {
+            reference
+                .iter()
+                .map(|(point, _)| (s - point).norm_sqr())
+                .fold(f64::INFINITY, f64::min)
+        })
+        .sum();
+    (total / symbols.len() as f64).max(MIN_NOISE_VARIANCE)
+}
+
+/// Soft differential decoder: the LLR counterpart of [`differential_decode_bits`]
+///
+/// Each symbol's bit LLRs are turned into a distribution over the four phase
+/// indices; the data phase `(current - previous) mod 4` is then marginalised
+/// over both symbols and mapped back to Gray-coded bit LLRs. The first symbol is
+/// referenced to `starting_prev_phase`, exactly as in the hard decoder.
+///
+/// # Arguments
+/// * `llrs` - Channel LLRs, two per symbol
+/// * `starting_prev_phase` - Initial reference phase (0-3)
+/// * `method` - Exact or max-log marginalisation
+///
+/// # Returns
+/// Vector of data-bit LLRs (same length as input, rounded down to whole symbols)
+pub fn differential_decode_llrs(llrs: &[f64], starting_prev_phase: u8, method: LlrMethod) -> Vec<f64> {
+    // ln P(b = 0) and ln P(b = 1) from an LLR
+    let bit_log_probs = |llr: f64| -> [f64; 2] {
+        let neg_softplus = |x: f64| match method {
+            LlrMethod::Exact => -(x.max(0.0) + (-x.abs()).exp().ln_1p()),
+            LlrMethod::MaxLog => -x.max(0.0),
+        };
+        [neg_softplus(-llr), neg_softplus(llr)]
+    };
+
+    let mut decoded = Vec::with_capacity(llrs.len());
+    let mut prev = [f64::NEG_INFINITY; 4];
+    prev[(starting_prev_phase & 0x03) as usize] = 0.0;
+
+    for &[llr0, llr1] in llrs.as_chunks::<2>().0 {
+        let (p0, p1) = (bit_log_probs(llr0), bit_log_probs(llr1));
+        let mut curr = [0.0; 4];
+        for (phase, log_prob) in curr.iter_mut().enumerate() {
+            let (b0, b1) = phase_to_gray(phase as u8);
+            *log_prob = p0[b0 as usize] + p1[b1 as usize];
+        }
+
+        let mut data = [0.0; 4];
+        for (data_phase, log_prob) in data.iter_mut().enumerate() {
+            *log_prob = log_sum((0..4).map(|p| prev[p] + curr[(p + data_phase) & 0x03]), method);
+        }
+
+        for bit in 0..2 {
+            let metric = |value: u8| {
+                log_sum(
+                    (0..4u8)
+                        .filter(|&q| {
+                            let (b0, b1) = phase_to_gray(q);
+                            [b0, b1][bit] == value
+                        })
+                        .map(|q| data[q as usize]),
+                    method,
+                )
+            };
+            decoded.push(metric(0) - metric(1));
+        }
+
+        prev = curr;
+    }
+
+    decoded
+}
+
+/// Hard decision on LLRs (negative → 1)
+pub fn llrs_to_bits(llrs: &[f64]) -> Vec<u8> {
+    llrs.iter().map(|&llr| u8::from(llr < 0.0)).collect()
+}
+
+/// Hex string of bits packed MSB first
+fn to_hex(bits: &[u8]) -> String {
+    pack_bits(bits).iter().map(|byte| format!("{byte:02X}")).collect()
+}
+
+/// Printable ASCII preview of payload bytes ('.' for anything else)
+fn payload_preview(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' })
+        .collect()
+}
+
+/// Header fields shared by every copy of one frame of a message
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CombiningKey {
+    target_id_hex: String,
+    command_opcode: u32,
+    frame_index: usize,
+    total_frames: usize,
+}
+
+impl CombiningKey {
+    fn new(header: &FrameDescriptor) -> Self {
+        Self {
+            target_id_hex: header.target_id_hex.clone(),
+            command_opcode: header.command_opcode,
+            frame_index: header.frame_index,
+            total_frames: header.total_frames,
+        }
+    }
+}
+
+/// Soft information accumulated over repeated copies of one frame
+#[derive(Debug, Clone, Default)]
+struct CombinedFrame {
+    llrs: Vec<f64>,
+    depth: usize,
+}
+
+impl CombinedFrame {
+    fn add(&mut self, llrs: &[f64]) {
+        if self.llrs.len() != llrs.len() {
+            self.llrs = vec![0.0; llrs.len()];
+            self.depth = 0;
+        }
+        for (sum, llr) in self.llrs.iter_mut().zip(llrs) {
+            *sum += llr;
+        }
+        self.depth += 1;
+    }
+}
+
 /// Incremental symbol-by-symbol decoder
 pub struct StreamingSymbolDecoder {
     protocol: InternalProtocolConfig,
-    matrices: LDPCMatrices,
+    codec: Arc<dyn FecCodec>,
+    interleaver: Interleaver,
+    scrambler: Scrambler,
+    llr_method: LlrMethod,
     
     // Buffer for received symbols
     symbol_buffer: Vec<Complex64>,
     demodulated_bits: Vec<u8>,
+    demodulated_llrs: Vec<f64>,
+    
+    // Running channel estimates for soft demapping
+    symbol_energy: Option<f64>,
+    noise_variance: f64,
     
     // Frame synchronization state
     sync_found: bool,
@@ -138,6 +385,30 @@ pub struct StreamingSymbolDecoder {
     symbols_in_current_frame: usize,
     decoded_frames: Vec<Vec<u8>>,
     
+    // FEC statistics
+    last_outcome: Option<DecodeOutcome>,
+    last_frame_header: Option<FrameDescriptor>,
+    total_bits_corrected: usize,
+    failed_frames: usize,
+    
+    // Frame checksum verification
+    checksum_failures: usize,
+    last_frame_error: Option<DecodingError>,
+    
+    // Receiver addressing
+    address_filter: AddressFilter,
+    foreign_frames: usize,
+    
+    // Outer code spanning the frames of a message
+    outer_code: Option<OuterCodeAssembler>,
+    outer_message: Option<Result<Vec<u8>, DecodingError>>,
+    
+    // Chase combining: summed codeword LLRs of undecoded frames of the
+    // current message, keyed by their target, command and frame counters
+    max_combining_depth: usize,
+    combining: HashMap<CombiningKey, CombinedFrame>,
+    combining_depth: usize,
+    
     // FSK demodulator state (detects ±1 Hz shifts)
     fsk_symbol_history: Vec<Complex64>, // Symbols for FSK analysis
     fsk_phase_history: Vec<f64>,        // Instantaneous phase for frequency estimation
@@ -150,20 +421,54 @@ pub struct StreamingSymbolDecoder {
 }
 
 impl StreamingSymbolDecoder {
-    pub fn new(protocol: InternalProtocolConfig, matrices: LDPCMatrices) -> Self {
+    pub fn new(protocol: InternalProtocolConfig, matrices: LDPCMatrices) -> crate::errors::Result<Self> {
+        let codec = LdpcCodec::new(matrices, LDPCDecoderConfig::default());
+        Self::with_codec(protocol, Arc::new(codec))
+    }
+    
+    /// Creates a decoder for frames protected by `codec`, whose codeword must
+    /// fill the layout's payload and ECC sections
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::errors::ConfigError::InvalidFrameLayout`] if the
+    /// codec's codeword does not match the layout's.
+    pub fn with_codec(protocol: InternalProtocolConfig, codec: Arc<dyn FecCodec>) -> crate::errors::Result<Self> {
+        check_codeword_fits(&protocol.frame_layout, codec.as_ref())?;
         let mut logger = LogCollector::new();
         logger.log("Initializing streaming symbol decoder.".to_string());
+        let interleaver = Interleaver::new(&protocol.frame_layout.interleaving, codec.codeword_bits());
+        let scrambler = Scrambler::new(&protocol.frame_layout.scrambling, codec.codeword_bits());
         
-        Self {
+        Ok(Self {
             protocol,
-            matrices,
+            codec,
+            interleaver,
+            scrambler,
+            llr_method: LlrMethod::default(),
             symbol_buffer: Vec::new(),
             demodulated_bits: Vec::new(),
+            demodulated_llrs: Vec::new(),
+            symbol_energy: None,
+            noise_variance: 1.0,
             sync_found: false,
             sync_index: None,
             current_frame_index: 0,
             symbols_in_current_frame: 0,
             decoded_frames: Vec::new(),
+            last_outcome: None,
+            last_frame_header: None,
+            total_bits_corrected: 0,
+            failed_frames: 0,
+            checksum_failures: 0,
+            address_filter: AddressFilter::default(),
+            foreign_frames: 0,
+            last_frame_error: None,
+            outer_code: None,
+            outer_message: None,
+            max_combining_depth: 1,
+            combining: HashMap::new(),
+            combining_depth: 0,
             fsk_symbol_history: Vec::new(),
             fsk_phase_history: Vec::new(),
             fsk_detected_bits: Vec::new(),
@@ -171,7 +476,51 @@ impl StreamingSymbolDecoder {
             fsk_frequency_estimate: 12000.0,
             symbols_since_fsk_update: 0,
             logger,
-        }
+        })
+    }
+    
+    /// Reassemble messages protected by an outer code across their frames
+    ///
+    /// Frames failing the inner decode become erasures of the outer code; the
+    /// recovered message is available from [`Self::take_outer_message`].
+    pub fn with_outer_code(mut self, outer_code: &OuterCode) -> Self {
+        let bytes_per_frame = self.data_bits() / 8;
+        self.outer_code = match outer_code {
+            OuterCode::None => None,
+            OuterCode::ReedSolomon { parity_frames } => {
+                Some(OuterCodeAssembler::new(*parity_frames, bytes_per_frame))
+            }
+        };
+        self
+    }
+    
+    /// Soft-combine up to `max_depth` copies of a repeated frame
+    ///
+    /// Copies are matched by their target ID, command and frame counters. The
+    /// codeword LLRs of every copy that fails to decode are summed with the
+    /// next copy of the same frame, so repetitions decode progressively
+    /// better; a successful decode (or reaching `max_depth`) starts over.
+    /// Different messages can share all of these header fields, so callers
+    /// must [`Self::reset_combining`] when a new message starts.
+    pub fn with_chase_combining(mut self, max_depth: usize) -> Self {
+        self.max_combining_depth = max_depth.max(1);
+        self
+    }
+    
+    /// Deliver only frames whose target ID passes `filter`
+    ///
+    /// Other frames are not decoded; they are counted in
+    /// [`Self::foreign_frames`] and their headers still reported, marked
+    /// `foreign`.
+    pub fn with_address_filter(mut self, filter: AddressFilter) -> Self {
+        self.address_filter = filter;
+        self
+    }
+    
+    /// Select exact or max-log LLR computation for the soft demapper
+    pub fn with_llr_method(mut self, llr_method: LlrMethod) -> Self {
+        self.llr_method = llr_method;
+        self
     }
     
     /// Add received symbols and process them incrementally
@@ -186,11 +535,13 @@ impl StreamingSymbolDecoder {
         let mut new_decoded_bits = Vec::new();
         let mut frame_complete = false;
         
-        // Demodulate new symbols
-        for symbol in symbols {
+        // Demodulate new symbols (hard bits for sync search, LLRs for decoding)
+        let symbol_llrs = self.soft_demodulate(symbols);
+        for (symbol, llrs) in symbols.iter().zip(&symbol_llrs) {
             let bits = demodulate_qpsk_symbol(*symbol);
             self.demodulated_bits.push(bits[0]);
             self.demodulated_bits.push(bits[1]);
+            self.demodulated_llrs.extend_from_slice(llrs);
         }
         
         // Try to find sync if not yet found
@@ -219,30 +570,33 @@ impl StreamingSymbolDecoder {
                 let frame_bit_end = frame_bit_start + frame_bits;
                 
                 if frame_bit_end <= self.demodulated_bits.len() {
-                    let frame_slice_encoded = &self.demodulated_bits[frame_bit_start..frame_bit_end];
+                    let frame_llrs_encoded = &self.demodulated_llrs[frame_bit_start..frame_bit_end];
                     
-                    // Apply differential decoding to recover original bits
+                    // Apply soft differential decoding to recover original bit LLRs
                     // This reverses the differential encoding applied at TX
-                    let frame_slice = differential_decode_bits(frame_slice_encoded);
+                    let frame_slice = differential_decode_llrs(frame_llrs_encoded, 0, self.llr_method);
                     
                     // Extract and decode payload
                     let prefix_bits = (self.protocol.frame_layout.sync_symbols
                         + self.protocol.frame_layout.target_id_symbols
                         + self.protocol.frame_layout.command_type_symbols) * 2;
-                    let codeword_bits = self.matrices.codeword_bits;
+                    let codeword_bits = self.codec.codeword_bits();
                     
                     // Account for 2-bit loss from differential decoding
                     let payload_start = if prefix_bits >= 2 { prefix_bits - 2 } else { 0 };
                     let payload_end = payload_start + codeword_bits;
                     
                     if frame_slice.len() >= payload_end {
-                        let noisy_codeword = &frame_slice[payload_start..payload_end];
-                        let decoded = decode_ldpc(&self.matrices, noisy_codeword, 0.0);
+                        let channel_llrs = self.scrambler.apply_llrs(&frame_slice[payload_start..payload_end]);
+                        let codeword_llrs = self.interleaver.deinterleave(&channel_llrs);
+                        let decoded = self.decode_frame(&frame_slice, 2, codeword_llrs);
                         
                         new_decoded_bits = decoded.clone();
-                        self.decoded_frames.push(decoded);
+                        if self.last_frame_error.is_none() {
+                            self.decoded_frames.push(decoded);
+                        }
                         
-                        if self.current_frame_index < 3 {
+                        if self.current_frame_index < 3 && self.last_frame_error.is_none() {
                             self.logger.log(format!(
                                 "[RX] Frame {} decoded ({} payload bits).",
                                 self.current_frame_index + 1,
@@ -259,7 +613,11 @@ impl StreamingSymbolDecoder {
         }
         
         // Create diagnostics from recent symbols
-        let diagnostics = self.create_diagnostics(symbols);
+        let mut diagnostics = self.create_diagnostics(symbols, &symbol_llrs);
+        if frame_complete {
+            diagnostics.combining_depth = self.combining_depth;
+            diagnostics.frames.extend(self.last_frame_header.clone());
+        }
         
         (new_decoded_bits, frame_complete, self.current_frame_index, self.symbols_in_current_frame, diagnostics)
     }
@@ -278,8 +636,13 @@ impl StreamingSymbolDecoder {
     /// 4. Apply LDPC FEC decoding
     /// 5. Convert bits to bytes
     /// 
-    /// SINGLE FRAME MODE: Only processes the first frame, ignores any additional symbols.
+    /// Decodes one frame and ignores any additional symbols; bursts are split
+    /// into frames by the demodulator and passed in one call per frame.
     pub fn process_synchronized_symbols(&mut self, symbols: &[Complex64]) -> Vec<u8> {
+        self.last_outcome = None;
+        self.last_frame_header = None;
+        self.last_frame_error = None;
+        
         // The demodulator now returns the FULL FRAME including the sync preamble.
         // This preserves the differential encoding chain, so we can decode properly.
         //
@@ -293,13 +656,19 @@ impl StreamingSymbolDecoder {
             symbols
         };
         
-        // 1. Demodulate QPSK symbols to bits (2 bits per symbol)
+        // 1. Demodulate QPSK symbols to bits (2 bits per symbol) and channel LLRs
         let mut bits = Vec::with_capacity(symbols_to_decode.len() * 2);
+        let normalized = track_residual_phase(&normalize_symbols(symbols_to_decode), PHASE_TRACKING_GAIN);
+        let noise_variance = estimate_noise_variance(&normalized);
+        let channel_llrs: Vec<f64> = normalized
+            .iter()
+            .flat_map(|s| qpsk_symbol_llrs(*s, noise_variance, self.llr_method))
+            .collect();
         
         // DEBUG: Show first few symbols and their demodulation
-        if symbols_to_decode.len() >= 10 {
+        if normalized.len() >= 10 {
             eprintln!("[DECODER] First 10 symbols:");
-            for (i, symbol) in symbols_to_decode.iter().take(10).enumerate() {
+            for (i, symbol) in normalized.iter().take(10).enumerate() {
                 let symbol_bits = demodulate_qpsk_symbol(*symbol);
                 eprintln!("  [{}]: ({:.3}, {:.3}) phase={:.1}° → bits=[{}, {}]",
                     i, symbol.re, symbol.im, symbol.arg().to_degrees(), symbol_bits[0], symbol_bits[1]);
@@ -307,13 +676,13 @@ impl StreamingSymbolDecoder {
                 bits.push(symbol_bits[1]);
             }
             // Process remaining symbols
-            for symbol in symbols_to_decode.iter().skip(10) {
+            for symbol in normalized.iter().skip(10) {
                 let symbol_bits = demodulate_qpsk_symbol(*symbol);
                 bits.push(symbol_bits[0]);
                 bits.push(symbol_bits[1]);
             }
         } else {
-            for symbol in symbols_to_decode {
+            for symbol in &normalized {
                 let symbol_bits = demodulate_qpsk_symbol(*symbol);
                 bits.push(symbol_bits[0]);
                 bits.push(symbol_bits[1]);
@@ -348,13 +717,13 @@ impl StreamingSymbolDecoder {
         
         // The true preamble is the RAW, un-encoded bit pattern (e.g., A5A5A5A5)
         // We compare the differentially DECODED received preamble against this raw pattern
-        let true_preamble_bits = hex_to_bitstream(&self.protocol.sync_sequence_hex, sync_bit_len);
+        let true_preamble_bits = self.protocol.sync_bits();
         
         let mut best_rotation = 0;
         let mut min_errors = usize::MAX;
         
         // Try all 4 possible starting phase references (0°, 90°, 180°, 270°)
-        eprintln!("[DECODER] Testing phase ambiguity (preamble should be: {:?})", &true_preamble_bits[0..16]);
+        eprintln!("[DECODER] Testing phase ambiguity (preamble should be: {:?})", &true_preamble_bits[..16.min(sync_bit_len)]);
         for rot_idx in 0..4 {
             // Decode the received preamble bits with this rotation
             let decoded_preamble = differential_decode_with_reference(preamble_bits, rot_idx);
@@ -379,6 +748,7 @@ impl StreamingSymbolDecoder {
         
         // 3. Decode the ENTIRE frame using the resolved starting reference phase
         let decoded_bits = differential_decode_with_reference(&bits, best_rotation);
+        let decoded_llrs = differential_decode_llrs(&channel_llrs, best_rotation, self.llr_method);
         
         // DEBUG: Show first few differentially decoded bits (should now be correct)
         if decoded_bits.len() >= 20 {
@@ -426,31 +796,35 @@ impl StreamingSymbolDecoder {
             return Vec::new();
         }
         
-        // 4. Reconstruct the full LDPC codeword by concatenating payload + ecc sections
-        let payload_section = &decoded_bits[payload_section_start..payload_section_end];
-        let ecc_section = &decoded_bits[ecc_section_start..ecc_section_end];
+        // 4. Reconstruct the full FEC codeword by concatenating payload + ecc sections
+        let payload_section = &decoded_llrs[payload_section_start..payload_section_end];
+        let ecc_section = &decoded_llrs[ecc_section_start..ecc_section_end];
         
-        // Reconstruct the full LDPC codeword
-        let mut codeword = Vec::with_capacity(self.matrices.codeword_bits);
+        let codeword_bits = self.codec.codeword_bits();
+        let mut codeword = Vec::with_capacity(codeword_bits);
         codeword.extend_from_slice(payload_section);
         codeword.extend_from_slice(ecc_section);
         
-        // Pad if needed (shouldn't be necessary with full frame)
-        while codeword.len() < self.matrices.codeword_bits {
-            codeword.push(0);
+        // Pad if needed (shouldn't be necessary with full frame); missing bits are erasures
+        while codeword.len() < codeword_bits {
+            codeword.push(0.0);
         }
         
-        if codeword.len() != self.matrices.codeword_bits {
+        if codeword.len() != codeword_bits {
             self.logger.log(format!(
                 "Codeword size mismatch: have {}, expected {}",
                 codeword.len(),
-                self.matrices.codeword_bits
+                codeword_bits
             ));
             return Vec::new();
         }
         
-        // 5. Apply LDPC decoding to extract the actual message bits
-        let decoded_payload_bits = decode_ldpc(&self.matrices, &codeword, 0.0);
+        // 5. Descramble, deinterleave and apply FEC decoding to extract the actual message bits
+        let codeword = self.interleaver.deinterleave(&self.scrambler.apply_llrs(&codeword));
+        let decoded_payload_bits = self.decode_frame(&decoded_llrs, 0, codeword);
+        if self.last_frame_error.is_some() {
+            return Vec::new();
+        }
         
         // 6. Convert bits to bytes
         let bytes = crate::utils::pack_bits(&decoded_payload_bits);
@@ -467,9 +841,245 @@ impl StreamingSymbolDecoder {
         bytes
     }
     
+    /// Decode one received frame: chase-combine its codeword LLRs with earlier
+    /// copies, decode, verify the frame checksum and pass the result on to the
+    /// outer code
+    ///
+    /// `skipped_bits` is the number of leading frame bits missing from
+    /// `frame_llrs`. Returns the frame's payload bits, or nothing if the
+    /// codeword fails the syndrome check or the checksum does not match.
+    fn decode_frame(&mut self, frame_llrs: &[f64], skipped_bits: usize, codeword_llrs: Vec<f64>) -> Vec<u8> {
+        let mut header = self.parse_header(frame_llrs, skipped_bits);
+        let counters = (header.frame_index, header.total_frames);
+        
+        // Frames for other receivers never reach the combiner or outer code
+        let addressed = self.address_filter.accept_all
+            || parse_address(&header.target_id_hex).is_ok_and(|id| self.address_filter.accepts(id));
+        if !addressed {
+            self.foreign_frames += 1;
+            self.combining_depth = 0;
+            self.logger.log(format!(
+                "[RX] Frame {} for {} ignored.",
+                counters.0 + 1,
+                header.target_id_hex
+            ));
+            self.last_frame_error = Some(DecodingError::ForeignFrame {
+                target_id: header.target_id_hex.clone(),
+            });
+            header.foreign = true;
+            self.last_frame_header = Some(header);
+            return Vec::new();
+        }
+        
+        let checked = if self.max_combining_depth > 1 {
+            // A different message length means a new message; drop stale copies
+            let key = CombiningKey::new(&header);
+            self.combining.retain(|stale, _| stale.total_frames == key.total_frames);
+            let entry = self.combining.entry(key.clone()).or_default();
+            if entry.depth >= self.max_combining_depth {
+                *entry = CombinedFrame::default();
+            }
+            entry.add(&codeword_llrs);
+            self.combining_depth = entry.depth;
+            let combined = entry.llrs.clone();
+            
+            let checked = self
+                .decode_codeword(&combined)
+                .and_then(|decoded| self.verify_checksum(frame_llrs, skipped_bits, decoded));
+            if checked.is_ok() {
+                if self.combining_depth > 1 {
+                    self.logger.log(format!(
+                        "[RX] Frame {} decoded after combining {} copies.",
+                        counters.0 + 1,
+                        self.combining_depth
+                    ));
+                }
+                self.combining.remove(&key);
+            }
+            checked
+        } else {
+            self.combining_depth = 1;
+            self.decode_codeword(&codeword_llrs)
+                .and_then(|decoded| self.verify_checksum(frame_llrs, skipped_bits, decoded))
+        };
+        
+        self.collect_outer_frame(frame_llrs, skipped_bits, checked.as_deref().ok());
+        if let Ok(payload) = &checked {
+            header.payload_preview = payload_preview(&pack_bits(payload));
+        }
+        self.last_frame_header = Some(header);
+        match checked {
+            Ok(payload) => {
+                self.last_frame_error = None;
+                payload
+            }
+            Err(err) => {
+                if matches!(err, DecodingError::ChecksumMismatch { .. }) {
+                    self.checksum_failures += 1;
+                }
+                self.logger.log(format!("[RX] Frame {} dropped: {err}.", counters.0 + 1));
+                self.last_frame_error = Some(err);
+                Vec::new()
+            }
+        }
+    }
+    
+    /// Payload bits per frame message, excluding the frame checksum
+    fn data_bits(&self) -> usize {
+        self.codec
+            .message_bits()
+            .saturating_sub(self.protocol.frame_layout.checksum.bits())
+    }
+    
+    /// Split a decoded frame message into payload and checksum and verify the
+    /// checksum over the received target ID, command and payload
+    ///
+    /// `skipped_bits` is the number of leading frame bits missing from `frame_llrs`.
+    fn verify_checksum(&self, frame_llrs: &[f64], skipped_bits: usize, mut message: Vec<u8>) -> Result<Vec<u8>, DecodingError> {
+        let kind = self.protocol.frame_layout.checksum;
+        if kind == FrameChecksum::None {
+            return Ok(message);
+        }
+        
+        let carried_bits = message.split_off(self.data_bits().min(message.len()));
+        let expected = carried_bits.iter().fold(0u32, |acc, &bit| (acc << 1) | u32::from(bit));
+        
+        let layout = &self.protocol.frame_layout;
+        let start = (layout.sync_symbols * 2).saturating_sub(skipped_bits);
+        let end = (start + (layout.target_id_symbols + layout.command_type_symbols) * 2).min(frame_llrs.len());
+        let mut covered = llrs_to_bits(&frame_llrs[start.min(end)..end]);
+        covered.extend_from_slice(&message);
+        
+        let actual = checksum::compute(kind, &covered);
+        if actual == expected {
+            Ok(message)
+        } else {
+            Err(DecodingError::ChecksumMismatch { expected, actual })
+        }
+    }
+    
+    /// Soft-decode a codeword and record the outcome
+    ///
+    /// A codeword that fails the syndrome check is counted and rejected: the
+    /// decoder's best estimate stays in [`Self::last_decode_outcome`] but is
+    /// never delivered as data.
+    fn decode_codeword(&mut self, codeword_llrs: &[f64]) -> Result<Vec<u8>, DecodingError> {
+        let outcome = self.codec.decode_soft(codeword_llrs);
+        
+        self.total_bits_corrected += outcome.bits_flipped;
+        let message = outcome.clone().into_result();
+        if let Err(err) = &message {
+            self.failed_frames += 1;
+            self.logger.log(format!(
+                "[RX] {} (syndrome weight {} after {} iterations).",
+                err, outcome.syndrome_weight_after, outcome.iterations
+            ));
+        }
+        
+        self.last_outcome = Some(outcome);
+        message
+    }
+    
+    /// Parse the sync word, target ID and command field of a decoded frame
+    ///
+    /// `skipped_bits` is the number of leading frame bits missing from
+    /// `frame_llrs`; they are filled in from the configured sync word.
+    fn parse_header(&self, frame_llrs: &[f64], skipped_bits: usize) -> FrameDescriptor {
+        let layout = &self.protocol.frame_layout;
+        let sync_len = layout.sync_symbols * 2;
+        let target_len = layout.target_id_symbols * 2;
+        let command_len = layout.command_type_symbols * 2;
+        let field = |start: usize, len: usize| {
+            let from = start.saturating_sub(skipped_bits).min(frame_llrs.len());
+            let to = (start + len).saturating_sub(skipped_bits).min(frame_llrs.len());
+            llrs_to_bits(&frame_llrs[from..to])
+        };
+        
+        let expected_sync = self.protocol.sync_bits();
+        let mut sync_bits = expected_sync[..skipped_bits.min(sync_len)].to_vec();
+        sync_bits.extend(field(0, sync_len));
+        let sync_errors = sync_bits.iter().zip(&expected_sync).filter(|(a, b)| a != b).count();
+        
+        let command_value = field(sync_len + target_len, command_len)
+            .iter()
+            .fold(0u64, |acc, &bit| (acc << 1) | bit as u64);
+        let counter = |shift: usize, bits: usize| ((command_value >> shift) & ((1 << bits) - 1)) as usize;
+        let frame_index = counter(self.protocol.current_frame_shift, self.protocol.frame_index_bits());
+        let total_frames = counter(self.protocol.total_frames_shift, self.protocol.frame_count_bits());
+        let command_opcode = (command_value & ((1 << self.protocol.current_frame_shift) - 1)) as u32;
+        let command = CommandType::from_opcode(command_opcode);
+        
+        FrameDescriptor {
+            frame_index,
+            total_frames,
+            command_opcode,
+            command_value: command_value as u32,
+            frame_label: format!(
+                "Frame {}/{} ({})",
+                frame_index + 1,
+                total_frames,
+                command.map_or("unknown command", CommandType::as_str)
+            ),
+            payload_preview: String::new(),
+            sync_hex: to_hex(&sync_bits),
+            sync_errors,
+            target_id_hex: to_hex(&field(sync_len, target_len)),
+            command,
+            foreign: false,
+        }
+    }
+    
+    /// Hand a decoded frame to the outer code, erasing it if the inner decode
+    /// or the checksum failed
+    fn collect_outer_frame(&mut self, frame_llrs: &[f64], skipped_bits: usize, decoded: Option<&[u8]>) {
+        if self.outer_code.is_none() {
+            return;
+        }
+        let header = self.parse_header(frame_llrs, skipped_bits);
+        let (index, total) = (header.frame_index, header.total_frames);
+        let frame = decoded
+            .filter(|_| self.last_outcome.as_ref().is_some_and(|outcome| outcome.success))
+            .map(|decoded| crate::utils::pack_bits(&decoded[..decoded.len() / 8 * 8]));
+        
+        let Some(assembler) = self.outer_code.as_mut() else { return };
+        if let Some(message) = assembler.insert(index, total, frame) {
+            match &message {
+                Ok(bytes) => self.logger.log(format!("[RX] Outer code recovered {} byte message.", bytes.len())),
+                Err(err) => self.logger.log(format!("[RX] Outer code failed: {err}")),
+            }
+            self.outer_message = Some(message);
+        }
+    }
+    
+    /// Update running energy/noise estimates and return channel LLRs per symbol
+    fn soft_demodulate(&mut self, symbols: &[Complex64]) -> Vec<[f64; 2]> {
+        const SMOOTHING: f64 = 0.05;
+        
+        symbols
+            .iter()
+            .map(|symbol| {
+                let energy = symbol.norm_sqr();
+                let mean_energy = match self.symbol_energy {
+                    Some(prev) => prev + SMOOTHING * (energy - prev),
+                    None => energy,
+                };
+                self.symbol_energy = Some(mean_energy);
+                
+                let normalized = if mean_energy > 0.0 {
+                    symbol / mean_energy.sqrt()
+                } else {
+                    *symbol
+                };
+                let error = estimate_noise_variance(std::slice::from_ref(&normalized));
+                self.noise_variance += SMOOTHING * (error - self.noise_variance);
+                
+                qpsk_symbol_llrs(normalized, self.noise_variance, self.llr_method)
+            })
+            .collect()
+    }
+    
     fn search_for_sync(&mut self) {
-        let sync_bit_len = self.protocol.frame_layout.sync_symbols * 2;
-        let sync_bits = hex_to_bitstream(&self.protocol.sync_sequence_hex, sync_bit_len);
+        let sync_bits = self.protocol.sync_bits();
         
         if self.demodulated_bits.len() >= sync_bits.len() {
             // Convert sync pattern to expected symbol sequence for correlation
@@ -558,7 +1168,7 @@ impl StreamingSymbolDecoder {
         }
     }
     
-    fn create_diagnostics(&self, recent_symbols: &[Complex64]) -> DemodulationDiagnostics {
+    fn create_diagnostics(&self, recent_symbols: &[Complex64], recent_llrs: &[[f64; 2]]) -> DemodulationDiagnostics {
         let (received_symbols_i, received_symbols_q): (Vec<f64>, Vec<f64>) =
             recent_symbols.iter().map(|s| (s.re, s.im)).unzip();
         
@@ -583,7 +1193,7 @@ impl StreamingSymbolDecoder {
                 average_q: s.im,
                 min_distance: best_distance,
                 distances,
-                soft_metrics: [0.0, 0.0],
+                soft_metrics: recent_llrs.get(i).copied().unwrap_or_default(),
             }
         }).collect();
         
@@ -591,6 +1201,10 @@ impl StreamingSymbolDecoder {
             received_symbols_i,
             received_symbols_q,
             symbol_decisions,
+            llrs: recent_llrs.iter().flatten().copied().collect(),
+            noise_variance: self.noise_variance,
+            combining_depth: 0,
+            frames: Vec::new(),
             timing_error: vec![0.0; recent_symbols.len()],
             nco_freq_offset: vec![0.0; recent_symbols.len()],
         }
@@ -604,6 +1218,62 @@ impl StreamingSymbolDecoder {
         self.sync_found
     }
     
+    /// Outcome of the most recently decoded codeword
+    pub fn last_decode_outcome(&self) -> Option<&DecodeOutcome> {
+        self.last_outcome.as_ref()
+    }
+    
+    /// Total codeword bits corrected by the FEC decoder so far
+    pub fn total_bits_corrected(&self) -> usize {
+        self.total_bits_corrected
+    }
+    
+    /// Number of frames whose decoded codeword failed the syndrome check
+    pub fn failed_frames(&self) -> usize {
+        self.failed_frames
+    }
+    
+    /// Frames dropped because their checksum did not match
+    pub fn checksum_failures(&self) -> usize {
+        self.checksum_failures
+    }
+    
+    /// Frames ignored because the address filter rejected their target ID
+    pub fn foreign_frames(&self) -> usize {
+        self.foreign_frames
+    }
+    
+    /// Header parsed from the most recently decoded frame
+    pub fn last_frame_header(&self) -> Option<&FrameDescriptor> {
+        self.last_frame_header.as_ref()
+    }
+    
+    /// Why the most recently decoded frame was dropped, if it was
+    pub fn last_frame_error(&self) -> Option<&DecodingError> {
+        self.last_frame_error.as_ref()
+    }
+    
+    /// Copies soft-combined for the most recently decoded frame
+    pub fn combining_depth(&self) -> usize {
+        self.combining_depth
+    }
+    
+    /// Drop the soft copies of every undecoded frame, so the next message
+    /// is not combined with an earlier one
+    pub fn reset_combining(&mut self) {
+        self.combining.clear();
+    }
+    
+    /// Take the most recent message completed by the outer code, if any
+    pub fn take_outer_message(&mut self) -> Option<Result<Vec<u8>, DecodingError>> {
+        self.outer_message.take()
+    }
+    
+    /// Frames of the pending outer-coded message erased so far
+    pub fn outer_erased_frames(&self) -> usize {
+        self.outer_code.as_ref().map_or(0, OuterCodeAssembler::erased_frames)
+    }
+    
     /// Demodulate FSK layer by estimating instantaneous frequency
     /// Detects ±1 Hz shifts in the carrier frequency
     fn demodulate_fsk(&mut self, symbols: &[Complex64]) {
@@ -699,4 +1369,439 @@ impl StreamingSymbolDecoder {
     pub fn get_demodulated_bits(&self) -> &[u8] {
         &self.demodulated_bits
     }
+    
+    /// Channel LLRs for every demodulated bit (parallel to `get_demodulated_bits`)
+    pub fn get_demodulated_llrs(&self) -> &[f64] {
+        &self.demodulated_llrs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AddressMask, BitInterleaving, FrameLayout, LDPCConfig, Scrambling};
+    use crate::encoder::{differential_encode_bits, StreamingFrameEncoder};
+    use crate::ldpc::LDPCSuite;
+    use crate::utils::string_to_bitstream;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::{Distribution, Normal};
+
+    fn add_noise(symbols: &[Complex64], noise_variance: f64, rng: &mut StdRng) -> Vec<Complex64> {
+        let noise = Normal::new(0.0, (noise_variance / 2.0).sqrt()).unwrap();
+        symbols
+            .iter()
+            .map(|s| s + Complex64::new(noise.sample(rng), noise.sample(rng)))
+            .collect()
+    }
+
+    #[test]
+    fn symbol_llrs_agree_with_hard_decisions() {
+        for method in [LlrMethod::Exact, LlrMethod::MaxLog] {
+            for (point, bits) in qpsk_constellation() {
+                let llrs = qpsk_symbol_llrs(point * 0.9, 0.1, method);
+                assert_eq!(llrs_to_bits(&llrs), bits.to_vec(), "{method:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn symbol_llrs_scale_with_noise_variance() {
+        let symbol = Complex64::new(0.5, -0.2);
+        let quiet = qpsk_symbol_llrs(symbol, 0.1, LlrMethod::Exact);
+        let noisy = qpsk_symbol_llrs(symbol, 1.0, LlrMethod::Exact);
+
+        for bit in 0..2 {
+            assert!((quiet[bit] - 10.0 * noisy[bit]).abs() < 1e-9);
+        }
+        // Gray-coded QPSK bits are separable, so max-log is exact here.
+        let max_log = qpsk_symbol_llrs(symbol, 0.3, LlrMethod::MaxLog);
+        let exact = qpsk_symbol_llrs(symbol, 0.3, LlrMethod::Exact);
+        for bit in 0..2 {
+            assert!((max_log[bit] - exact[bit]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn soft_differential_decoding_matches_hard_decoder() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let data: Vec<u8> = (0..64).map(|_| u8::from(rng.gen_bool(0.5))).collect();
+        let encoded = differential_encode_bits(&data);
+        let llrs: Vec<f64> = encoded.iter().map(|&b| if b == 0 { 6.0 } else { -6.0 }).collect();
+
+        for method in [LlrMethod::Exact, LlrMethod::MaxLog] {
+            let soft = differential_decode_llrs(&llrs, 0, method);
+            assert_eq!(llrs_to_bits(&soft), differential_decode_bits(&encoded), "{method:?}");
+            assert_eq!(llrs_to_bits(&soft), data, "{method:?}");
+        }
+    }
+
+    #[test]
+    fn noise_variance_estimate_tracks_channel() {
+        let mut rng = StdRng::seed_from_u64(12);
+        let clean: Vec<Complex64> = (0..2000).map(|i| qpsk_constellation()[i % 4].0).collect();
+        let estimate = estimate_noise_variance(&normalize_symbols(&add_noise(&clean, 0.05, &mut rng)));
+        assert!((estimate - 0.05).abs() < 0.01, "estimate {estimate}");
+    }
+
+    #[test]
+    fn synchronized_frame_decodes_through_soft_path() {
+        let layout = FrameLayout::default();
+        let matrices = LDPCSuite::new(&layout, &LDPCConfig::default()).matrices;
+        let protocol = InternalProtocolConfig::default();
+        let payload = string_to_bitstream("soft");
+
+        let mut encoder = StreamingFrameEncoder::new(&payload, protocol.clone(), matrices.clone()).unwrap();
+        let (tx_symbols, ..) = encoder.get_next_symbols(layout.total_symbols);
+        let rx_symbols = add_noise(&tx_symbols, 0.2, &mut StdRng::seed_from_u64(13));
+
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices).unwrap().with_llr_method(LlrMethod::MaxLog);
+        let decoded = decoder.process_synchronized_symbols(&rx_symbols);
+        assert_eq!(&decoded[..4], b"soft");
+    }
+
+    #[test]
+    fn binary_payload_round_trips_with_trailing_zeros() {
+        let layout = FrameLayout::default();
+        let matrices = LDPCSuite::new(&layout, &LDPCConfig::default()).matrices;
+        let protocol = InternalProtocolConfig::default();
+        let payload = [0xFF, 0x00, 0xA5, 0x00, 0x00];
+
+        let mut encoder = StreamingFrameEncoder::new(
+            &crate::payload::encode(&payload).unwrap(),
+            protocol.clone(),
+            matrices.clone(),
+        ).unwrap();
+        let (tx_symbols, ..) = encoder.get_next_symbols(layout.total_symbols);
+        let rx_symbols = add_noise(&tx_symbols, 0.2, &mut StdRng::seed_from_u64(19));
+
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices).unwrap();
+        let decoded = decoder.process_synchronized_symbols(&rx_symbols);
+        assert_eq!(crate::payload::decode(&decoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn received_frame_header_is_parsed() {
+        let mut protocol = InternalProtocolConfig::default();
+        protocol.command = "set_parameter".to_string();
+        protocol.target_id_hex = "CAFEBABE".to_string();
+        let layout = protocol.frame_layout.clone();
+        let matrices = LDPCSuite::new(&layout, &LDPCConfig::default()).matrices;
+        // Three frames; decode the second
+        let payload = string_to_bitstream(&"header".repeat(6));
+
+        let mut encoder = StreamingFrameEncoder::new(&payload, protocol.clone(), matrices.clone()).unwrap();
+        assert_eq!(encoder.total_frames, 3);
+        encoder.get_next_symbols(layout.total_symbols);
+        let (tx_symbols, ..) = encoder.get_next_symbols(layout.total_symbols);
+        let rx_symbols = add_noise(&tx_symbols, 0.1, &mut StdRng::seed_from_u64(19));
+
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices).unwrap();
+        decoder.process_synchronized_symbols(&rx_symbols);
+        let header = decoder.last_frame_header().expect("frame should be decoded");
+        assert_eq!(header.sync_hex, "A5A5A5A5");
+        assert_eq!(header.sync_errors, 0);
+        assert_eq!(header.target_id_hex, "CAFEBABE");
+        assert_eq!(header.command, Some(CommandType::SetParameter));
+        assert_eq!(header.command_opcode, 0x0003);
+        assert_eq!((header.frame_index, header.total_frames), (1, 3));
+        assert_eq!(header.command_value, 0x0301_0003);
+        assert_eq!(header.payload_preview, "erheaderheaderhe");
+    }
+
+    /// One lightly noisy frame carrying "addr" to `target`
+    fn addressed_frame(protocol: &InternalProtocolConfig, matrices: &LDPCMatrices, target: &str) -> Vec<Complex64> {
+        let protocol = InternalProtocolConfig {
+            target_id_hex: target.to_string(),
+            ..protocol.clone()
+        };
+        let total_symbols = protocol.frame_layout.total_symbols;
+        let mut encoder = StreamingFrameEncoder::new(&string_to_bitstream("addr"), protocol, matrices.clone()).unwrap();
+        let (tx_symbols, ..) = encoder.get_next_symbols(total_symbols);
+        add_noise(&tx_symbols, 0.1, &mut StdRng::seed_from_u64(23))
+    }
+
+    /// Decode a frame for each target and check which ones were delivered
+    fn assert_delivered(decoder: &mut StreamingSymbolDecoder, matrices: &LDPCMatrices, cases: &[(&str, bool)]) {
+        let protocol = decoder.protocol.clone();
+        for &(target, delivered) in cases {
+            let decoded = decoder.process_synchronized_symbols(&addressed_frame(&protocol, matrices, target));
+            let header = decoder.last_frame_header().expect("header should be parsed");
+            assert_eq!(header.target_id_hex, target);
+            assert_eq!(header.foreign, !delivered, "{target}");
+            assert_eq!(decoded.starts_with(b"addr"), delivered, "{target}");
+        }
+    }
+
+    #[test]
+    fn address_filter_drops_foreign_frames() {
+        let layout = FrameLayout::default();
+        let matrices = LDPCSuite::new(&layout, &LDPCConfig::default()).matrices;
+        let filter = AddressFilter {
+            accept_all: false,
+            ids: vec!["DEADBEEF".to_string()],
+            masks: vec![AddressMask {
+                id: "CAFE0000".to_string(),
+                mask: "FFFF0000".to_string(),
+            }],
+            ..AddressFilter::default()
+        };
+        let mut decoder = StreamingSymbolDecoder::new(InternalProtocolConfig::default(), matrices.clone()).unwrap()
+            .with_address_filter(filter);
+        assert_delivered(
+            &mut decoder,
+            &matrices,
+            &[("DEADBEEF", true), ("CAFE1234", true), ("FFFFFFFF", true), ("0D15EA5E", false)],
+        );
+        assert_eq!(decoder.foreign_frames(), 1);
+        assert!(matches!(decoder.last_frame_error(), Some(DecodingError::ForeignFrame { .. })));
+    }
+
+    #[test]
+    fn broadcast_only_filter_drops_addressed_frames() {
+        let layout = FrameLayout::default();
+        let matrices = LDPCSuite::new(&layout, &LDPCConfig::default()).matrices;
+        let mut decoder = StreamingSymbolDecoder::new(InternalProtocolConfig::default(), matrices.clone()).unwrap()
+            .with_address_filter(AddressFilter::broadcast_only());
+        assert_delivered(&mut decoder, &matrices, &[("FFFFFFFF", true), ("DEADBEEF", false)]);
+    }
+
+    #[test]
+    fn address_filter_matches_64_bit_target_ids() {
+        // The deep-space preset's target field is 32 symbols, 64 bits wide
+        let protocol = crate::config::get_protocol_preset("deep-space-probe").unwrap();
+        assert_eq!(protocol.frame_layout.target_id_symbols * 2, 64);
+        let matrices = LDPCSuite::new(&protocol.frame_layout, &LDPCConfig::default()).matrices;
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices.clone()).unwrap()
+            .with_address_filter(AddressFilter::exact(["0123456789ABCDEF", "0D15EA5E"]));
+        assert_delivered(
+            &mut decoder,
+            &matrices,
+            &[
+                ("0123456789ABCDEF", true),
+                ("000000000D15EA5E", true),
+                ("00000000FFFFFFFF", true),
+                ("0123456789ABCDEE", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn interleaved_frame_is_deinterleaved_before_decoding() {
+        let mut protocol = InternalProtocolConfig::default();
+        protocol.frame_layout.interleaving = BitInterleaving::Block { rows: 16 };
+        let matrices = LDPCSuite::new(&protocol.frame_layout, &LDPCConfig::default()).matrices;
+        let payload = string_to_bitstream("mixed");
+
+        let mut encoder = StreamingFrameEncoder::new(&payload, protocol.clone(), matrices.clone()).unwrap();
+        let (tx_symbols, ..) = encoder.get_next_symbols(protocol.frame_layout.total_symbols);
+        let rx_symbols = add_noise(&tx_symbols, 0.1, &mut StdRng::seed_from_u64(15));
+
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices).unwrap();
+        let decoded = decoder.process_synchronized_symbols(&rx_symbols);
+        assert_eq!(&decoded[..5], b"mixed");
+        assert!(decoder.last_decode_outcome().unwrap().success);
+    }
+
+    #[test]
+    fn scrambled_zero_frame_is_descrambled_before_decoding() {
+        let mut protocol = InternalProtocolConfig::default();
+        protocol.frame_layout.scrambling = Scrambling::Lfsr7 { seed: 0x7F };
+        let matrices = LDPCSuite::new(&protocol.frame_layout, &LDPCConfig::default()).matrices;
+        let payload = vec![0u8; 64];
+
+        let mut encoder = StreamingFrameEncoder::new(&payload, protocol.clone(), matrices.clone()).unwrap();
+        let (tx_symbols, ..) = encoder.get_next_symbols(protocol.frame_layout.total_symbols);
+        // The all-zero codeword alone would step the phase by 180° every symbol
+        let codeword_symbols = &tx_symbols[protocol.frame_layout.total_symbols - 80..];
+        let steps: Vec<_> = codeword_symbols.windows(2).map(|pair| pair[1] * pair[0].conj()).collect();
+        let changes = steps.windows(2).filter(|pair| (pair[1] - pair[0]).norm() > 0.5).count();
+        assert!(changes > 30, "phase step changes only {changes} times");
+
+        let rx_symbols = add_noise(&tx_symbols, 0.1, &mut StdRng::seed_from_u64(16));
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices).unwrap();
+        let decoded = decoder.process_synchronized_symbols(&rx_symbols);
+        assert_eq!(&decoded[..8], &[0; 8]);
+        assert!(decoder.last_decode_outcome().unwrap().success);
+    }
+
+    #[test]
+    fn checksum_protected_frame_round_trips() {
+        let mut protocol = InternalProtocolConfig::default();
+        protocol.frame_layout.checksum = FrameChecksum::Crc16Ccitt;
+        let matrices = LDPCSuite::new(&protocol.frame_layout, &LDPCConfig::default()).matrices;
+        let payload = string_to_bitstream("checked");
+
+        let mut encoder = StreamingFrameEncoder::new(&payload, protocol.clone(), matrices.clone()).unwrap();
+        assert_eq!(encoder.data_bits_per_frame(), 112);
+        let (tx_symbols, ..) = encoder.get_next_symbols(protocol.frame_layout.total_symbols);
+        let rx_symbols = add_noise(&tx_symbols, 0.1, &mut StdRng::seed_from_u64(17));
+
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices).unwrap();
+        let decoded = decoder.process_synchronized_symbols(&rx_symbols);
+        // The checksum is stripped from the delivered payload
+        assert_eq!(decoded.len(), 14);
+        assert_eq!(&decoded[..7], b"checked");
+        assert_eq!(decoder.checksum_failures(), 0);
+        assert!(decoder.last_frame_error().is_none());
+    }
+
+    #[test]
+    fn frame_failing_its_checksum_is_dropped() {
+        let mut protocol = InternalProtocolConfig::default();
+        protocol.frame_layout.checksum = FrameChecksum::Crc32;
+        let layout = protocol.frame_layout.clone();
+        let matrices = LDPCSuite::new(&layout, &LDPCConfig::default()).matrices;
+        let payload = string_to_bitstream("garbage?");
+
+        let mut encoder = StreamingFrameEncoder::new(&payload, protocol.clone(), matrices.clone()).unwrap();
+        let (tx_symbols, ..) = encoder.get_next_symbols(layout.total_symbols);
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices).unwrap();
+
+        // The target ID is outside the codeword: FEC cannot see the error, the CRC can
+        let mut corrupted = tx_symbols.clone();
+        corrupted[layout.sync_symbols + 3] *= Complex64::i();
+        assert!(decoder.process_synchronized_symbols(&corrupted).is_empty());
+        assert!(decoder.last_decode_outcome().unwrap().success);
+        assert!(matches!(
+            decoder.last_frame_error(),
+            Some(DecodingError::ChecksumMismatch { .. })
+        ));
+
+        // A scrambled codeword that BP cannot correct is dropped before its
+        // checksum is even looked at
+        let mut rng = StdRng::seed_from_u64(18);
+        let header = layout.sync_symbols + layout.target_id_symbols + layout.command_type_symbols;
+        let mut scrambled = tx_symbols.clone();
+        for symbol in &mut scrambled[header..] {
+            *symbol = qpsk_constellation()[rng.gen_range(0..4)].0;
+        }
+        assert!(decoder.process_synchronized_symbols(&scrambled).is_empty());
+        assert!(matches!(decoder.last_frame_error(), Some(DecodingError::InvalidCodeword)));
+        assert_eq!(decoder.checksum_failures(), 1);
+        assert_eq!(decoder.failed_frames(), 1);
+
+        let decoded = decoder.process_synchronized_symbols(&tx_symbols);
+        assert_eq!(&decoded[..8], b"garbage?");
+        assert!(decoder.last_frame_error().is_none());
+    }
+
+    #[test]
+    fn outer_code_recovers_frame_lost_by_inner_decoder() {
+        let layout = FrameLayout::default();
+        let matrices = LDPCSuite::new(&layout, &LDPCConfig::default()).matrices;
+        let protocol = InternalProtocolConfig::default();
+        let outer_code = OuterCode::ReedSolomon { parity_frames: 2 };
+        let message = b"Reed-Solomon spans all frames of this message";
+        let payload = string_to_bitstream(std::str::from_utf8(message).unwrap());
+
+        let mut encoder = StreamingFrameEncoder::new(&payload, protocol.clone(), matrices.clone()).unwrap();
+        encoder.enable_outer_code(&outer_code).unwrap();
+        assert_eq!(encoder.total_frames, 5);
+        let (mut symbols, ..) = encoder.get_next_symbols(5 * layout.total_symbols);
+        let mut rng = StdRng::seed_from_u64(14);
+        symbols = add_noise(&symbols, 0.1, &mut rng);
+
+        // Scramble the codeword of frame 1 so its inner decode fails
+        let header = layout.sync_symbols + layout.target_id_symbols + layout.command_type_symbols;
+        for symbol in &mut symbols[layout.total_symbols + header..2 * layout.total_symbols] {
+            *symbol = qpsk_constellation()[rng.gen_range(0..4)].0;
+        }
+
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices).unwrap().with_outer_code(&outer_code);
+        for frame in symbols.chunks(layout.total_symbols) {
+            decoder.process_synchronized_symbols(frame);
+        }
+
+        assert_eq!(decoder.failed_frames(), 1);
+        let recovered = decoder.take_outer_message().unwrap().unwrap();
+        assert_eq!(&recovered[..message.len()], message);
+    }
+
+    #[test]
+    fn codec_for_another_layout_is_rejected() {
+        use crate::errors::{ChimeraError, ConfigError};
+
+        let protocol = InternalProtocolConfig::default();
+        let deep_space = crate::config::get_protocol_preset("deep-space-probe").unwrap();
+        assert_ne!(deep_space.frame_layout.codeword_bits(), protocol.frame_layout.codeword_bits());
+        let codec = crate::fec::build_codec(&deep_space.frame_layout, &LDPCConfig::default()).unwrap();
+
+        assert!(matches!(
+            StreamingFrameEncoder::with_codec(&string_to_bitstream("mismatch"), protocol.clone(), codec.clone()),
+            Err(ChimeraError::Config(ConfigError::InvalidFrameLayout { .. }))
+        ));
+        assert!(matches!(
+            StreamingSymbolDecoder::with_codec(protocol, codec),
+            Err(ChimeraError::Config(ConfigError::InvalidFrameLayout { .. }))
+        ));
+    }
+
+    #[test]
+    fn uncorrectable_frame_is_dropped_without_a_checksum() {
+        let protocol = InternalProtocolConfig::default();
+        assert_eq!(protocol.frame_layout.checksum, FrameChecksum::None);
+        let layout = protocol.frame_layout.clone();
+        let matrices = LDPCSuite::new(&layout, &LDPCConfig::default()).matrices;
+
+        let mut encoder = StreamingFrameEncoder::new(&string_to_bitstream("nothing"), protocol.clone(), matrices.clone()).unwrap();
+        let (mut symbols, ..) = encoder.get_next_symbols(layout.total_symbols);
+        let mut rng = StdRng::seed_from_u64(19);
+        let header = layout.sync_symbols + layout.target_id_symbols + layout.command_type_symbols;
+        for symbol in &mut symbols[header..] {
+            *symbol = qpsk_constellation()[rng.gen_range(0..4)].0;
+        }
+
+        let mut decoder = StreamingSymbolDecoder::new(protocol, matrices).unwrap();
+        assert!(decoder.process_synchronized_symbols(&symbols).is_empty());
+        assert!(!decoder.last_decode_outcome().unwrap().success);
+        assert!(matches!(decoder.last_frame_error(), Some(DecodingError::InvalidCodeword)));
+        assert_eq!(decoder.failed_frames(), 1);
+        assert_eq!(decoder.checksum_failures(), 0);
+    }
+
+    #[test]
+    fn chase_combining_recovers_frame_no_single_copy_can() {
+        let layout = FrameLayout::default();
+        let matrices = LDPCSuite::new(&layout, &LDPCConfig::default()).matrices;
+        let protocol = InternalProtocolConfig::default();
+        let payload = string_to_bitstream("combine");
+
+        let mut encoder = StreamingFrameEncoder::new(&payload, protocol.clone(), matrices.clone()).unwrap();
+        let (tx_symbols, ..) = encoder.get_next_symbols(layout.total_symbols);
+        let mut rng = StdRng::seed_from_u64(16);
+
+        // Each copy loses a different third of its codeword to random symbols
+        let header = layout.sync_symbols + layout.target_id_symbols + layout.command_type_symbols;
+        let third = (layout.total_symbols - header) / 3;
+        let copies: Vec<Vec<Complex64>> = (0..3)
+            .map(|copy| {
+                let mut symbols = add_noise(&tx_symbols, 0.1, &mut rng);
+                let start = header + copy * third;
+                for symbol in &mut symbols[start..start + third] {
+                    *symbol = qpsk_constellation()[rng.gen_range(0..4)].0;
+                }
+                symbols
+            })
+            .collect();
+
+        let mut single = StreamingSymbolDecoder::new(protocol.clone(), matrices.clone()).unwrap();
+        for copy in &copies {
+            single.process_synchronized_symbols(copy);
+        }
+        assert_eq!(single.failed_frames(), 3);
+        assert_eq!(single.combining_depth(), 1);
+
+        let mut combining = StreamingSymbolDecoder::new(protocol, matrices).unwrap().with_chase_combining(4);
+        let mut decoded = Vec::new();
+        for copy in &copies {
+            decoded = combining.process_synchronized_symbols(copy);
+        }
+        assert_eq!(combining.combining_depth(), 3);
+        assert!(combining.last_decode_outcome().unwrap().success);
+        assert_eq!(&decoded[..7], b"combine");
+
+        // A decoded frame clears its buffer, so the next copy starts over
+        combining.process_synchronized_symbols(&add_noise(&tx_symbols, 0.1, &mut rng));
+        assert_eq!(combining.combining_depth(), 1);
+    }
 }
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::protocol::CommandType;
+
 pub mod metrics;
 pub mod constellation;
 
@@ -22,9 +24,11 @@ pub struct SymbolDecision {
     pub average_q: f64,
     pub min_distance: f64,
     pub distances: [f64; 4],
+    /// Per-bit channel LLRs for this symbol
     pub soft_metrics: [f64; 2],
 }
 
+/// Header fields of a frame
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct FrameDescriptor {
     pub frame_index: usize,
@@ -33,6 +37,21 @@ pub struct FrameDescriptor {
     pub command_value: u32,
     pub frame_label: String,
     pub payload_preview: String,
+    /// Sync word as received
+    #[serde(default)]
+    pub sync_hex: String,
+    /// Bits of the received sync word differing from the configured one
+    #[serde(default)]
+    pub sync_errors: usize,
+    /// Target ID as received
+    #[serde(default)]
+    pub target_id_hex: String,
+    /// Command decoded from `command_opcode` (`None` if the opcode is unknown)
+    #[serde(default)]
+    pub command: Option<CommandType>,
+    /// Target ID rejected by the receiver's address filter
+    #[serde(default)]
+    pub foreign: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -48,6 +67,18 @@ pub struct DemodulationDiagnostics {
     pub received_symbols_i: Vec<f64>,
     pub received_symbols_q: Vec<f64>,
     pub symbol_decisions: Vec<SymbolDecision>,
+    /// Channel LLRs (two per symbol, positive favours bit 0)
+    #[serde(default)]
+    pub llrs: Vec<f64>,
+    /// Noise variance estimate used to scale the LLRs
+    #[serde(default)]
+    pub noise_variance: f64,
+    /// Copies soft-combined for the frame decoded in this chunk (0 if none)
+    #[serde(default)]
+    pub combining_depth: usize,
+    /// Headers of the frames decoded in this chunk
+    #[serde(default)]
+    pub frames: Vec<FrameDescriptor>,
     pub timing_error: Vec<f64>,
     pub nco_freq_offset: Vec<f64>,
 }
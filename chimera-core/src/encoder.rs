@@ -1,36 +1,15 @@
-/// Encodes a message vector using an LDPC generator matrix.
-///
-/// This function performs the core LDPC encoding operation by multiplying the
-/// message vector with the generator matrix over the Galois Field GF(2). In this
-/// field, addition is equivalent to the XOR operation. The resulting vector is
-/// the codeword, which includes the original message bits and the appended
-/// parity check bits.
-///
-/// The operation is equivalent to `codeword = message * G`, where `G` is the
-/// generator matrix.
-///
-/// # Arguments
-///
-/// * `generator` - A 2D array representing the generator matrix (`G`). Its
-///   dimensions should be `k x n`, where `k` is the message length and `n` is
-///   the codeword length.
-/// * `message` - A slice of `u8` representing the message bits to be encoded.
-///   Its length must be equal to the number of rows in the `generator` matrix.
-///
-/// # Returns
-///
-/// A `Vec<u8>` containing the encoded codeword of length `n`.
-///
-/// # Panics
-///
-/// This function will panic if the length of the `message` does not match the
-/// number of rows in the `generator` matrix.
+//! Frame encoding stage: LDPC encoding, framing and differential QPSK mapping.
 use std::f64::consts::FRAC_1_SQRT_2;
 use std::borrow::Cow;
+use std::sync::Arc;
 
 use num_complex::Complex64;
 
+use crate::checksum;
 use crate::config::InternalProtocolConfig;
+use crate::config::{FrameChecksum, LDPCDecoderConfig, OuterCode};
+use crate::errors::{ConfigError, Result};
+use crate::fec::{check_codeword_fits, FecCodec, Interleaver, LdpcCodec, ReedSolomon, Scrambler};
 use crate::ldpc::LDPCMatrices;
 use crate::utils::{
     hex_to_bitstream, int_to_bitstream, LogCollector,
@@ -50,7 +29,7 @@ fn gray_to_phase(b0: u8, b1: u8) -> u8 {
 
 /// Convert phase index (0-3) to Gray-coded QPSK bits
 /// Inverse of gray_to_phase
-fn phase_to_gray(phase: u8) -> (u8, u8) {
+pub(crate) fn phase_to_gray(phase: u8) -> (u8, u8) {
     match phase & 0x03 {
         0 => (1, 1), // 45°
         1 => (0, 1), // 135°
@@ -107,12 +86,21 @@ pub fn differential_encode_bits(bits: &[u8]) -> Vec<u8> {
 /// Incremental frame encoder for symbol-by-symbol streaming
 pub struct StreamingFrameEncoder {
     pub protocol: InternalProtocolConfig,
-    matrices: LDPCMatrices,
+    codec: Arc<dyn FecCodec>,
+    interleaver: Interleaver,
+    scrambler: Scrambler,
     payload_bits: Vec<u8>,
+    /// Payload bits carried per data frame (whole bytes with an outer code),
+    /// excluding the frame checksum
+    data_bits_per_frame: usize,
+    /// Outer-code parity frames sent after the data frames
+    parity_chunks: Vec<Vec<u8>>,
     current_frame_index: usize,
     pub total_frames: usize,
     current_symbol_in_frame: usize,
     current_frame_bitstream: Vec<u8>,
+    /// Complete passes over the message's frames
+    messages_sent: usize,
     logger: LogCollector,
     // FSK layer state (1 bit/second nested modulation)
     fsk_bit_stream: Vec<u8>,
@@ -125,12 +113,37 @@ impl StreamingFrameEncoder {
         payload_bits: &[u8],
         protocol: InternalProtocolConfig,
         matrices: LDPCMatrices,
-    ) -> Self {
-        let message_bits = matrices.message_bits;
+    ) -> Result<Self> {
+        let codec = LdpcCodec::new(matrices, LDPCDecoderConfig::default());
+        Self::with_codec(payload_bits, protocol, Arc::new(codec))
+    }
+
+    /// Creates an encoder protecting each frame with `codec`, whose codeword
+    /// must fill the layout's payload and ECC sections.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::InvalidFrameLayout`] if the codec's codeword
+    /// does not match the layout's, or its message has no room for payload
+    /// next to the layout's frame checksum.
+    pub fn with_codec(
+        payload_bits: &[u8],
+        protocol: InternalProtocolConfig,
+        codec: Arc<dyn FecCodec>,
+    ) -> Result<Self> {
+        check_codeword_fits(&protocol.frame_layout, codec.as_ref())?;
+        let message_bits = codec.message_bits();
+        let checksum_bits = protocol.frame_layout.checksum.bits();
+        if message_bits <= checksum_bits {
+            return Err(ConfigError::InvalidFrameLayout {
+                reason: format!("{message_bits}-bit frame message cannot carry a {checksum_bits}-bit checksum"),
+            }.into());
+        }
+        let data_bits_per_frame = message_bits - checksum_bits;
         let total_frames = if payload_bits.is_empty() {
             1
         } else {
-            payload_bits.len().div_ceil(message_bits)
+            payload_bits.len().div_ceil(data_bits_per_frame)
         };
         
         let mut logger = LogCollector::new();
@@ -138,20 +151,126 @@ impl StreamingFrameEncoder {
         
         // Generate FSK bit stream - nested 1 bit/second layer
         let fsk_bit_stream = Self::generate_fsk_pattern(payload_bits);
+        let interleaver = Interleaver::new(&protocol.frame_layout.interleaving, codec.codeword_bits());
+        let scrambler = Scrambler::new(&protocol.frame_layout.scrambling, codec.codeword_bits());
         
-        Self {
+        Ok(Self {
             protocol,
-            matrices,
+            codec,
+            interleaver,
+            scrambler,
             payload_bits: payload_bits.to_vec(),
+            data_bits_per_frame,
+            parity_chunks: Vec::new(),
             current_frame_index: 0,
             total_frames,
             current_symbol_in_frame: 0,
             current_frame_bitstream: Vec::new(),
+            messages_sent: 0,
             logger,
             fsk_bit_stream,
             fsk_bit_index: 0,
             symbols_since_fsk_transition: 0,
+        })
+    }
+    
+    /// Protect the message with an outer code spanning its frames
+    ///
+    /// With a Reed-Solomon outer code each data frame carries whole bytes of
+    /// payload and `parity_frames` parity frames follow the data frames, all
+    /// counted in the command field's `total_frames`. The encoder is left
+    /// unchanged if the message needs more frames than the code allows.
+    pub fn enable_outer_code(&mut self, outer_code: &OuterCode) -> Result<()> {
+        let parity_frames = outer_code.parity_frames();
+        if parity_frames == 0 {
+            return Ok(());
+        }
+        
+        let message_bits = self.codec.message_bits() - self.protocol.frame_layout.checksum.bits();
+        let bytes_per_frame = message_bits / 8;
+        if bytes_per_frame == 0 {
+            return Err(ConfigError::InvalidFrameLayout {
+                reason: format!("{message_bits}-bit frame message cannot carry an outer code"),
+            }.into());
+        }
+        let data_bits_per_frame = bytes_per_frame * 8;
+        let data_frames = self.payload_bits.len().div_ceil(data_bits_per_frame).max(1);
+        let rs = ReedSolomon::new(data_frames, parity_frames)?;
+        
+        let mut payload = self.payload_bits.clone();
+        payload.resize(data_frames * data_bits_per_frame, 0);
+        let data: Vec<Vec<u8>> = payload
+            .chunks(data_bits_per_frame)
+            .map(crate::utils::pack_bits)
+            .collect();
+        
+        self.parity_chunks = rs
+            .encode_frames(&data, bytes_per_frame)
+            .iter()
+            .map(|frame| crate::utils::bytes_to_bitstream(frame))
+            .collect();
+        self.data_bits_per_frame = data_bits_per_frame;
+        self.total_frames = rs.total_frames();
+        self.current_frame_index = 0;
+        self.current_symbol_in_frame = 0;
+        self.current_frame_bitstream.clear();
+        self.logger.log(format!(
+            "Reed-Solomon outer code: {data_frames} data + {parity_frames} parity frame(s)."
+        ));
+        Ok(())
+    }
+    
+    /// Outer code protecting the message (`None` unless enabled)
+    pub fn outer_code(&self) -> OuterCode {
+        match self.parity_chunks.len() {
+            0 => OuterCode::None,
+            parity_frames => OuterCode::ReedSolomon { parity_frames },
+        }
+    }
+    
+    /// Payload bits carried per frame, excluding the frame checksum
+    pub fn data_bits_per_frame(&self) -> usize {
+        self.data_bits_per_frame
+    }
+    
+    /// Message bits (before inner FEC) carried by frame `frame_idx`: the
+    /// frame's payload followed by its checksum
+    pub fn frame_message(&self, frame_idx: usize) -> Vec<u8> {
+        let checksum = self.protocol.frame_layout.checksum;
+        let data_bits = self.codec.message_bits() - checksum.bits();
+        let data_frames = self.total_frames - self.parity_chunks.len();
+        
+        let mut message_chunk = if frame_idx < data_frames {
+            let start = (frame_idx * self.data_bits_per_frame).min(self.payload_bits.len());
+            let end = (start + self.data_bits_per_frame).min(self.payload_bits.len());
+            self.payload_bits[start..end].to_vec()
+        } else {
+            self.parity_chunks.get(frame_idx - data_frames).cloned().unwrap_or_default()
+        };
+        message_chunk.resize(data_bits, 0);
+        
+        if checksum != FrameChecksum::None {
+            let (target_bits, command_bits) = self.frame_header(frame_idx);
+            let covered = [target_bits, command_bits, message_chunk.clone()].concat();
+            let crc = checksum::compute(checksum, &covered);
+            message_chunk.extend(int_to_bitstream(u64::from(crc), checksum.bits()));
         }
+        message_chunk
+    }
+    
+    /// Target ID and command field bits of frame `frame_idx`
+    fn frame_header(&self, frame_idx: usize) -> (Vec<u8>, Vec<u8>) {
+        let layout = &self.protocol.frame_layout;
+        let target_bits = hex_to_bitstream(&self.protocol.target_id_hex, layout.target_id_symbols * 2);
+        let command_bits = int_to_bitstream(self.command_value(frame_idx) as u64, layout.command_type_symbols * 2);
+        (target_bits, command_bits)
+    }
+    
+    /// Command field of frame `frame_idx`: opcode plus frame counters
+    fn command_value(&self, frame_idx: usize) -> u32 {
+        self.protocol.get_command_opcode()
+            | ((frame_idx as u32) << self.protocol.current_frame_shift)
+            | ((self.total_frames as u32) << self.protocol.total_frames_shift)
     }
     
     /// Generate FSK bit pattern - could be message checksum, sync pattern, etc.
@@ -268,6 +387,7 @@ impl StreamingFrameEncoder {
                 // Loop back to start when all frames transmitted
                 if self.current_frame_index >= self.total_frames {
                     self.current_frame_index = 0;
+                    self.messages_sent += 1;
                 }
                 self.current_symbol_in_frame = 0;
                 self.current_frame_bitstream.clear();
@@ -285,37 +405,22 @@ impl StreamingFrameEncoder {
             return;
         }
         
-        let layout = &self.protocol.frame_layout;
-        let message_bits = self.matrices.message_bits;
-        
-        let sync_bits = hex_to_bitstream(&self.protocol.sync_sequence_hex, layout.sync_symbols * 2);
-        let target_bits = hex_to_bitstream(&self.protocol.target_id_hex, layout.target_id_symbols * 2);
-        let command_bits_len = layout.command_type_symbols * 2;
-        
+        let sync_bits = self.protocol.sync_bits();
         let frame_idx = self.current_frame_index;
-        let command_value = self.protocol.get_command_opcode()
-            | ((frame_idx as u32) << self.protocol.current_frame_shift)
-            | ((self.total_frames as u32) << self.protocol.total_frames_shift);
-        let command_bits = int_to_bitstream(command_value as u64, command_bits_len);
+        let command_value = self.command_value(frame_idx);
+        let (target_bits, command_bits) = self.frame_header(frame_idx);
         
-        let start = frame_idx * message_bits;
-        let end = usize::min(start + message_bits, self.payload_bits.len());
-        let mut message_chunk = vec![0u8; message_bits];
-        if start < end {
-            message_chunk[..(end - start)].copy_from_slice(&self.payload_bits[start..end]);
-        }
+        let message_chunk = self.frame_message(frame_idx);
         
-        let codeword = encode_with_generator(&self.matrices.generator, &message_chunk);
-        let payload_section = &codeword[..message_bits];
-        let ecc_section = &codeword[message_bits..];
+        // The interleaved, scrambled codeword fills the payload and ECC sections back to back
+        let codeword = self.scrambler.apply(&self.interleaver.interleave(&self.codec.encode(&message_chunk)));
         
         // Build frame bitstream
         let mut frame_bits = Vec::new();
         frame_bits.extend_from_slice(&sync_bits);
         frame_bits.extend_from_slice(&target_bits);
         frame_bits.extend_from_slice(&command_bits);
-        frame_bits.extend_from_slice(payload_section);
-        frame_bits.extend_from_slice(ecc_section);
+        frame_bits.extend_from_slice(&codeword);
         
         // Apply differential encoding to entire frame
         self.current_frame_bitstream = differential_encode_bits(&frame_bits);
@@ -331,6 +436,19 @@ impl StreamingFrameEncoder {
     pub fn is_complete(&self) -> bool {
         self.current_frame_index >= self.total_frames
     }
+
+    /// Symbols left before the last frame of the message ends and the encoder
+    /// wraps to its first frame (a whole message when at the start)
+    pub fn symbols_until_message_end(&self) -> usize {
+        let symbols_per_frame = self.protocol.frame_layout.total_symbols;
+        (self.total_frames - self.current_frame_index) * symbols_per_frame - self.current_symbol_in_frame
+    }
+
+    /// Whether every frame of the message has been sent at least once and the
+    /// next symbol starts it again
+    pub fn at_message_boundary(&self) -> bool {
+        self.messages_sent > 0 && self.current_frame_index == 0 && self.current_symbol_in_frame == 0
+    }
     
     pub fn get_current_frame_bits(&self) -> &[u8] {
         &self.current_frame_bitstream
@@ -341,7 +459,34 @@ impl StreamingFrameEncoder {
     }
 }
 
-fn encode_with_generator(generator: &ndarray::Array2<u8>, message: &[u8]) -> Vec<u8> {
+/// Encodes a message vector using an LDPC generator matrix.
+///
+/// This function performs the core LDPC encoding operation by multiplying the
+/// message vector with the generator matrix over the Galois Field GF(2). In this
+/// field, addition is equivalent to the XOR operation. The resulting vector is
+/// the codeword, which includes the original message bits and the appended
+/// parity check bits.
+///
+/// The operation is equivalent to `codeword = message * G`, where `G` is the
+/// generator matrix.
+///
+/// # Arguments
+///
+/// * `generator` - A 2D array representing the generator matrix (`G`). Its
+///   dimensions should be `k x n`, where `k` is the message length and `n` is
+///   the codeword length.
+/// * `message` - A slice of `u8` representing the message bits to be encoded.
+///   Its length must be equal to the number of rows in the `generator` matrix.
+///
+/// # Returns
+///
+/// A `Vec<u8>` containing the encoded codeword of length `n`.
+///
+/// # Panics
+///
+/// This function will panic if the length of the `message` does not match the
+/// number of rows in the `generator` matrix.
+pub fn encode_with_generator(generator: &ndarray::Array2<u8>, message: &[u8]) -> Vec<u8> {
     assert_eq!(
         generator.nrows(),
         message.len(),
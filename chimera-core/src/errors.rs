@@ -33,6 +33,9 @@ pub enum EncodingError {
     #[error("Message length {message_len} exceeds maximum {max_len}")]
     MessageTooLong { message_len: usize, max_len: usize },
     
+    #[error("Message queue full: {capacity} messages already waiting")]
+    QueueFull { capacity: usize },
+    
     #[error("Generator matrix dimension mismatch: message={message_bits}, matrix_rows={matrix_rows}")]
     GeneratorDimensionMismatch {
         message_bits: usize,
@@ -66,6 +69,15 @@ pub enum DecodingError {
     
     #[error("Belief propagation did not converge after {iterations} iterations")]
     ConvergenceFailed { iterations: usize },
+    
+    #[error("Outer code lost {erasures} frames, can recover at most {correctable}")]
+    TooManyErasures { erasures: usize, correctable: usize },
+    
+    #[error("Frame addressed to {target_id}, not this receiver")]
+    ForeignFrame { target_id: String },
+    
+    #[error("Message truncated: length field needs {declared} bytes, {available} received")]
+    PayloadTruncated { declared: usize, available: usize },
 }
 
 /// LDPC-specific errors
@@ -111,6 +123,15 @@ pub enum ConfigError {
     
     #[error("FSK frequencies out of range: f0={f0} Hz, f1={f1} Hz")]
     InvalidFskFrequencies { f0: f64, f1: f64 },
+    
+    #[error("Invalid address {address:?}: expected up to 16 hex digits")]
+    InvalidAddress { address: String },
+    
+    #[error("Unknown protocol preset: {name}")]
+    UnknownProtocol { name: String },
+    
+    #[error("Invalid protocol preset {source_name}: {reason}")]
+    InvalidPreset { source_name: String, reason: String },
 }
 
 /// DSP processing errors
@@ -0,0 +1,194 @@
+//! Rate-1/2 convolutional code with soft-decision Viterbi decoding.
+//!
+//! The code is the industry-standard constraint length 7 code with octal
+//! generators (133, 171). Each codeword is terminated with `K - 1` zero tail
+//! bits so the trellis starts and ends in the all-zero state; a codeword with
+//! an odd number of bits ends in one unused zero pad bit.
+
+use crate::errors::Result;
+use crate::ldpc::DecodeOutcome;
+
+use super::{codeword_too_short, outcome_from_codeword, FecCodec};
+
+/// Constraint length
+const CONSTRAINT_LENGTH: usize = 7;
+/// Tail bits needed to return the encoder to the zero state
+const TAIL_BITS: usize = CONSTRAINT_LENGTH - 1;
+const NUM_STATES: usize = 1 << TAIL_BITS;
+const GENERATORS: [u32; 2] = [0o133, 0o171];
+
+/// Terminated K=7 rate-1/2 convolutional code sized to a frame codeword
+#[derive(Debug, Clone)]
+pub struct ConvolutionalCodec {
+    message_bits: usize,
+    codeword_bits: usize,
+}
+
+impl ConvolutionalCodec {
+    /// Creates the code filling `codeword_bits` channel bits, carrying
+    /// `codeword_bits / 2 - 6` message bits.
+    pub fn new(codeword_bits: usize) -> Result<Self> {
+        let message_bits = (codeword_bits / 2)
+            .checked_sub(TAIL_BITS)
+            .filter(|&k| k > 0)
+            .ok_or_else(|| codeword_too_short("convolutional", codeword_bits))?;
+
+        Ok(Self {
+            message_bits,
+            codeword_bits,
+        })
+    }
+
+    fn trellis_steps(&self) -> usize {
+        self.message_bits + TAIL_BITS
+    }
+}
+
+/// Output pair and next state for `input` entering the encoder in `state`
+fn transition(state: usize, input: u8) -> ([u8; 2], usize) {
+    let register = ((input as u32) << TAIL_BITS) | state as u32;
+    let outputs = GENERATORS.map(|g| ((register & g).count_ones() & 1) as u8);
+    (outputs, (register >> 1) as usize)
+}
+
+impl FecCodec for ConvolutionalCodec {
+    fn name(&self) -> &'static str {
+        "convolutional"
+    }
+
+    fn message_bits(&self) -> usize {
+        self.message_bits
+    }
+
+    fn codeword_bits(&self) -> usize {
+        self.codeword_bits
+    }
+
+    fn encode(&self, message: &[u8]) -> Vec<u8> {
+        let mut codeword = Vec::with_capacity(self.codeword_bits);
+        let mut state = 0;
+        let tail = std::iter::repeat_n(0, TAIL_BITS);
+
+        for bit in message
+            .iter()
+            .take(self.message_bits)
+            .map(|&b| b & 1)
+            .chain(tail)
+        {
+            let (outputs, next) = transition(state, bit);
+            codeword.extend_from_slice(&outputs);
+            state = next;
+        }
+
+        codeword.resize(self.codeword_bits, 0);
+        codeword
+    }
+
+    fn decode_soft(&self, llrs: &[f64]) -> DecodeOutcome {
+        let steps = self.trellis_steps();
+        let mut metrics = vec![f64::NEG_INFINITY; NUM_STATES];
+        metrics[0] = 0.0;
+        // Surviving (previous state, input bit) per step and state
+        let mut survivors = vec![[(0usize, 0u8); NUM_STATES]; steps];
+
+        for (step, survivor) in survivors.iter_mut().enumerate() {
+            let llr = |j: usize| llrs.get(2 * step + j).copied().unwrap_or(0.0);
+            let inputs: &[u8] = if step < self.message_bits {
+                &[0, 1]
+            } else {
+                &[0]
+            };
+            let mut next_metrics = vec![f64::NEG_INFINITY; NUM_STATES];
+
+            for (state, &metric) in metrics.iter().enumerate() {
+                if metric == f64::NEG_INFINITY {
+                    continue;
+                }
+                for &input in inputs {
+                    let (outputs, next) = transition(state, input);
+                    // Correlation with the LLRs: +L for a 0, -L for a 1
+                    let branch: f64 = outputs
+                        .iter()
+                        .enumerate()
+                        .map(|(j, &bit)| if bit == 0 { llr(j) } else { -llr(j) })
+                        .sum();
+                    if metric + branch > next_metrics[next] {
+                        next_metrics[next] = metric + branch;
+                        survivor[next] = (state, input);
+                    }
+                }
+            }
+            metrics = next_metrics;
+        }
+
+        let mut decoded = vec![0u8; steps];
+        let mut state = 0;
+        for step in (0..steps).rev() {
+            let (prev, input) = survivors[step][state];
+            decoded[step] = input;
+            state = prev;
+        }
+        decoded.truncate(self.message_bits);
+
+        let codeword = self.encode(&decoded);
+        outcome_from_codeword(llrs, decoded, codeword)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_llrs(codeword: &[u8]) -> Vec<f64> {
+        codeword
+            .iter()
+            .map(|&b| if b == 0 { 4.0 } else { -4.0 })
+            .collect()
+    }
+
+    #[test]
+    fn frame_codeword_carries_74_message_bits() {
+        let codec = ConvolutionalCodec::new(160).unwrap();
+        assert_eq!(codec.message_bits(), 74);
+        assert_eq!(codec.encode(&[1; 74]).len(), 160);
+        assert!(ConvolutionalCodec::new(12).is_err());
+    }
+
+    #[test]
+    fn impulse_response_matches_generators() {
+        let codec = ConvolutionalCodec::new(30).unwrap();
+        let mut message = vec![0u8; codec.message_bits()];
+        message[0] = 1;
+        let codeword = codec.encode(&message);
+
+        // Generator taps read MSB (newest bit) first
+        let g0: Vec<u8> = codeword.iter().step_by(2).take(7).copied().collect();
+        let g1: Vec<u8> = codeword
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .take(7)
+            .copied()
+            .collect();
+        assert_eq!(g0, vec![1, 0, 1, 1, 0, 1, 1]);
+        assert_eq!(g1, vec![1, 1, 1, 1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn viterbi_corrects_scattered_errors() {
+        let codec = ConvolutionalCodec::new(160).unwrap();
+        let message: Vec<u8> = (0..74).map(|i| ((i * 7 + 3) % 5 % 2) as u8).collect();
+        let codeword = codec.encode(&message);
+
+        let mut llrs = to_llrs(&codeword);
+        for idx in [5, 40, 90, 131] {
+            llrs[idx] = -llrs[idx];
+        }
+
+        let outcome = codec.decode_soft(&llrs);
+        assert_eq!(outcome.message_bits, message);
+        assert_eq!(outcome.codeword, codeword);
+        assert_eq!(outcome.bits_flipped, 4);
+        assert_eq!(outcome.syndrome_weight_after, 0);
+    }
+}
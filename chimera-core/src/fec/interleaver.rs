@@ -0,0 +1,188 @@
+//! Bit interleaving of the frame codeword.
+//!
+//! The interleaver permutes the FEC codeword before it is written into the
+//! payload and ECC sections, and the receiver applies the inverse permutation
+//! to the channel LLRs before soft decoding. A burst of adjacent channel
+//! errors therefore reaches the decoder as scattered single-bit errors.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::config::BitInterleaving;
+
+/// Fixed permutation of a codeword of `len()` bits
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interleaver {
+    /// `permutation[channel_position] = codeword_position`
+    permutation: Vec<usize>,
+}
+
+impl Interleaver {
+    /// Builds the permutation selected by `interleaving` for `len` bits.
+    ///
+    /// A block interleaver whose matrix is not full is pruned: positions past
+    /// the end of the codeword are skipped when reading out.
+    pub fn new(interleaving: &BitInterleaving, len: usize) -> Self {
+        let permutation = match *interleaving {
+            BitInterleaving::None => (0..len).collect(),
+            BitInterleaving::Block { rows } => {
+                let rows = rows.clamp(1, len.max(1));
+                let columns = len.div_ceil(rows);
+                (0..columns)
+                    .flat_map(|col| (0..rows).map(move |row| row * columns + col))
+                    .filter(|&idx| idx < len)
+                    .collect()
+            }
+            BitInterleaving::PseudoRandom { seed } => {
+                let mut permutation: Vec<usize> = (0..len).collect();
+                permutation.shuffle(&mut StdRng::seed_from_u64(seed));
+                permutation
+            }
+        };
+
+        Self { permutation }
+    }
+
+    pub fn len(&self) -> usize {
+        self.permutation.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.permutation.is_empty()
+    }
+
+    /// Reorders a codeword into channel order.
+    pub fn interleave<T: Copy>(&self, codeword: &[T]) -> Vec<T> {
+        self.permutation.iter().map(|&idx| codeword[idx]).collect()
+    }
+
+    /// Restores codeword order from channel order (bits or LLRs).
+    pub fn deinterleave<T: Copy + Default>(&self, channel: &[T]) -> Vec<T> {
+        let mut codeword = vec![T::default(); self.permutation.len()];
+        for (&idx, &value) in self.permutation.iter().zip(channel) {
+            codeword[idx] = value;
+        }
+        codeword
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FecScheme, FrameLayout, LDPCConfig};
+    use crate::fec::{build_codec, FecCodec};
+
+    fn schemes() -> [BitInterleaving; 3] {
+        [
+            BitInterleaving::None,
+            BitInterleaving::Block { rows: 12 },
+            BitInterleaving::PseudoRandom { seed: 5 },
+        ]
+    }
+
+    #[test]
+    fn deinterleave_inverts_interleave() {
+        for scheme in schemes() {
+            let interleaver = Interleaver::new(&scheme, 160);
+            let data: Vec<usize> = (0..160).collect();
+            let channel = interleaver.interleave(&data);
+
+            let mut sorted = channel.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, data, "{scheme:?} is not a permutation");
+            assert_eq!(interleaver.deinterleave(&channel), data, "{scheme:?}");
+        }
+    }
+
+    #[test]
+    fn block_interleaver_separates_adjacent_channel_bits() {
+        let interleaver = Interleaver::new(&BitInterleaving::Block { rows: 10 }, 160);
+        let channel = interleaver.interleave(&(0..160).collect::<Vec<_>>());
+        assert_eq!(&channel[..3], &[0, 16, 32]);
+        // Within a column, adjacent channel bits are a full row (16 bits) apart
+        for pair in channel.windows(2).filter(|pair| pair[1] > pair[0]) {
+            assert_eq!(pair[1] - pair[0], 16);
+        }
+    }
+
+    /// Frames `codec` decodes correctly for a burst of `burst` wrong channel
+    /// LLRs of `magnitude` at every position in the channel
+    fn burst_survivals(codec: &dyn FecCodec, scheme: &BitInterleaving, burst: usize, magnitude: f64) -> usize {
+        let message: Vec<u8> = (0..codec.message_bits())
+            .map(|i| (i % 3 == 1) as u8)
+            .collect();
+        let codeword = codec.encode(&message);
+        let interleaver = Interleaver::new(scheme, codeword.len());
+        (0..=codeword.len() - burst)
+            .filter(|&start| {
+                let mut channel: Vec<f64> = interleaver
+                    .interleave(&codeword)
+                    .iter()
+                    .map(|&b| if b == 0 { 3.0 } else { -3.0 })
+                    .collect();
+                for llr in &mut channel[start..start + burst] {
+                    *llr = -llr.signum() * magnitude;
+                }
+                codec
+                    .decode_soft(&interleaver.deinterleave(&channel))
+                    .message_bits
+                    == message
+            })
+            .count()
+    }
+
+    #[test]
+    fn interleaving_lets_viterbi_survive_bursts() {
+        let layout = FrameLayout::default();
+        let cfg = LDPCConfig {
+            fec: FecScheme::Convolutional,
+            ..LDPCConfig::default()
+        };
+        let codec = build_codec(&layout, &cfg).unwrap();
+
+        // A 3-symbol cycle slip (confident wrong bits) and a 6-symbol fade (weak wrong bits)
+        for (burst, magnitude) in [(6, 3.0), (12, 1.0)] {
+            let positions = codec.codeword_bits() - burst + 1;
+            let plain = burst_survivals(codec.as_ref(), &BitInterleaving::None, burst, magnitude);
+            assert!(plain < positions / 2, "plain survived {plain}/{positions}");
+
+            for scheme in &schemes()[1..] {
+                let interleaved = burst_survivals(codec.as_ref(), scheme, burst, magnitude);
+                assert!(
+                    interleaved * 20 >= positions * 19,
+                    "{scheme:?} survived {interleaved}/{positions}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn interleaving_lets_ldpc_survive_fades() {
+        // Rate-1/2 PEG code: 64 payload + 64 ECC symbols
+        let layout = FrameLayout {
+            total_symbols: 160,
+            data_payload_symbols: 64,
+            ecc_symbols: 64,
+            ..FrameLayout::default()
+        };
+        let cfg = LDPCConfig {
+            dv: 2,
+            dc: 4,
+            ..LDPCConfig::default()
+        };
+        let codec = build_codec(&layout, &cfg).unwrap();
+
+        // 4- and 8-symbol fades (weak wrong bits)
+        for burst in [8, 16] {
+            let positions = codec.codeword_bits() - burst + 1;
+            let plain = burst_survivals(codec.as_ref(), &BitInterleaving::None, burst, 1.0);
+            assert!(plain + 10 <= positions, "plain survived {plain}/{positions}");
+
+            for scheme in &schemes()[1..] {
+                let interleaved = burst_survivals(codec.as_ref(), scheme, burst, 1.0);
+                assert_eq!(interleaved, positions, "{scheme:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Pluggable forward error correction.
+//!
+//! Every frame carries one FEC codeword in its payload and ECC sections, so a
+//! codec only has to map `message_bits` information bits to exactly
+//! `codeword_bits` channel bits and back. The encoder, decoder, pipeline and
+//! processor work through [`FecCodec`] and never touch a particular code
+//! family directly; [`build_codec`] picks the implementation named by
+//! [`LDPCConfig::fec`].
+
+use std::sync::Arc;
+
+use crate::config::{FecScheme, FrameLayout, LDPCConfig, LDPCDecoderConfig, RateMatching};
+use crate::errors::{ConfigError, Result};
+use crate::ldpc::{
+    decode_ldpc_outcome_packed, BeliefPropagationDecoder, DecodeOutcome, LDPCMatrices, LDPCSuite, PackedCode,
+};
+
+pub mod convolutional;
+pub mod interleaver;
+pub mod polar;
+pub mod rate_matching;
+pub mod reed_solomon;
+pub mod repetition;
+pub mod scrambler;
+
+pub use convolutional::ConvolutionalCodec;
+pub use interleaver::Interleaver;
+pub use polar::PolarCodec;
+pub use rate_matching::RateMatchedCodec;
+pub use reed_solomon::{OuterCodeAssembler, ReedSolomon};
+pub use repetition::RepetitionCodec;
+pub use scrambler::Scrambler;
+
+/// A block code mapping `message_bits` to `codeword_bits` with soft decoding.
+///
+/// LLRs follow the crate-wide `ln(P(0) / P(1))` convention. Codes without a
+/// parity-check matrix report the Hamming distance from the channel decision
+/// to the decoded codeword as `syndrome_weight_before`, so that value is
+/// nonzero exactly when the channel decision was not a codeword.
+pub trait FecCodec: Send + Sync + std::fmt::Debug {
+    /// Short name of the code family (for logs and diagnostics)
+    fn name(&self) -> &'static str;
+
+    /// Information bits per codeword (k)
+    fn message_bits(&self) -> usize;
+
+    /// Channel bits per codeword (n)
+    fn codeword_bits(&self) -> usize;
+
+    /// Code rate k/n
+    fn code_rate(&self) -> f64 {
+        self.message_bits() as f64 / self.codeword_bits() as f64
+    }
+
+    /// Encodes exactly `message_bits` bits into a `codeword_bits`-bit codeword.
+    fn encode(&self, message: &[u8]) -> Vec<u8>;
+
+    /// Decodes `codeword_bits` channel LLRs.
+    fn decode_soft(&self, llrs: &[f64]) -> DecodeOutcome;
+}
+
+/// Builds the codec selected by `cfg.fec` for the codeword size of `layout`.
+///
+/// Under [`RateMatching::Mother`] the code is built for the mother layout and
+/// then shortened and punctured to the layout's payload and ECC sizes.
+///
+/// # Errors
+///
+/// Returns [`ConfigError::InvalidFrameLayout`] if the layout's codeword is too
+/// short for the selected code, the requested rate is not achievable, or the
+/// code's message leaves no room for payload next to the frame checksum.
+pub fn build_codec(layout: &FrameLayout, cfg: &LDPCConfig) -> Result<Arc<dyn FecCodec>> {
+    let mother_layout = layout.mother_layout();
+    let codeword_bits = mother_layout.codeword_bits();
+    let codec: Arc<dyn FecCodec> = match cfg.fec {
+        FecScheme::Ldpc => Arc::new(LdpcCodec::from_suite(
+            LDPCSuite::from_config(&mother_layout, cfg)?,
+            cfg.decoder.clone(),
+        )),
+        FecScheme::Convolutional => Arc::new(ConvolutionalCodec::new(codeword_bits)?),
+        FecScheme::Repetition { factor } => Arc::new(RepetitionCodec::new(codeword_bits, factor)?),
+        FecScheme::Polar { list_size } => Arc::new(PolarCodec::new(
+            mother_layout.message_bits(),
+            codeword_bits,
+            list_size,
+        )?),
+    };
+
+    let codec: Arc<dyn FecCodec> = match layout.rate_matching {
+        RateMatching::None => codec,
+        RateMatching::Mother { .. } => {
+            layout.validate()?;
+            Arc::new(RateMatchedCodec::new(
+                codec,
+                layout.message_bits(),
+                layout.codeword_bits(),
+            )?)
+        }
+    };
+
+    let (message_bits, checksum_bits) = (codec.message_bits(), layout.checksum.bits());
+    if message_bits <= checksum_bits {
+        return Err(ConfigError::InvalidFrameLayout {
+            reason: format!("{message_bits}-bit frame message cannot carry a {checksum_bits}-bit checksum"),
+        }
+        .into());
+    }
+    Ok(codec)
+}
+
+/// The frame LDPC code decoded with belief propagation.
+///
+/// The decoder's Tanner graph is built once here and reused for every frame.
+#[derive(Debug, Clone)]
+pub struct LdpcCodec {
+    matrices: LDPCMatrices,
+    packed: PackedCode,
+    decoder: BeliefPropagationDecoder,
+}
+
+impl LdpcCodec {
+    pub fn new(matrices: LDPCMatrices, decoder: LDPCDecoderConfig) -> Self {
+        let packed = PackedCode::new(&matrices);
+        let decoder = BeliefPropagationDecoder::new(&matrices.parity_check, &decoder);
+        Self {
+            matrices,
+            packed,
+            decoder,
+        }
+    }
+
+    /// Reuses the packed matrices already cached in `suite`.
+    pub fn from_suite(suite: LDPCSuite, decoder: LDPCDecoderConfig) -> Self {
+        let decoder = BeliefPropagationDecoder::new(&suite.matrices.parity_check, &decoder);
+        Self {
+            matrices: suite.matrices,
+            packed: suite.packed,
+            decoder,
+        }
+    }
+
+    /// Generator and parity-check matrices of the code
+    pub fn matrices(&self) -> &LDPCMatrices {
+        &self.matrices
+    }
+}
+
+impl FecCodec for LdpcCodec {
+    fn name(&self) -> &'static str {
+        "LDPC"
+    }
+
+    fn message_bits(&self) -> usize {
+        self.matrices.message_bits
+    }
+
+    fn codeword_bits(&self) -> usize {
+        self.matrices.codeword_bits
+    }
+
+    fn encode(&self, message: &[u8]) -> Vec<u8> {
+        self.packed.encode(message)
+    }
+
+    fn decode_soft(&self, llrs: &[f64]) -> DecodeOutcome {
+        decode_ldpc_outcome_packed(&self.decoder, &self.packed, llrs)
+    }
+}
+
+/// Hard decision on LLRs (negative → 1)
+pub(crate) fn hard_decision(llrs: &[f64]) -> Vec<u8> {
+    llrs.iter().map(|&llr| u8::from(llr < 0.0)).collect()
+}
+
+/// Builds the outcome of a decoder that always returns a codeword.
+pub(crate) fn outcome_from_codeword(
+    llrs: &[f64],
+    message_bits: Vec<u8>,
+    codeword: Vec<u8>,
+) -> DecodeOutcome {
+    let channel_codeword = hard_decision(llrs);
+    let distance = channel_codeword
+        .iter()
+        .zip(&codeword)
+        .filter(|(a, b)| a != b)
+        .count();
+
+    DecodeOutcome {
+        message_bits,
+        channel_codeword,
+        codeword,
+        syndrome_weight_before: distance,
+        syndrome_weight_after: 0,
+        bits_flipped: distance,
+        iterations: 1,
+        success: true,
+    }
+}
+
+pub(crate) fn codeword_too_short(scheme: &str, codeword_bits: usize) -> ConfigError {
+    ConfigError::InvalidFrameLayout {
+        reason: format!("{codeword_bits}-bit codeword is too short for the {scheme} code"),
+    }
+}
+
+/// Checks that `codec`'s codeword fills `layout`'s payload and ECC sections
+pub(crate) fn check_codeword_fits(layout: &FrameLayout, codec: &dyn FecCodec) -> Result<()> {
+    let (codec_bits, layout_bits) = (codec.codeword_bits(), layout.codeword_bits());
+    if codec_bits != layout_bits {
+        return Err(ConfigError::InvalidFrameLayout {
+            reason: format!("{codec_bits}-bit codeword does not fill the layout's {layout_bits}-bit codeword"),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn schemes() -> Vec<FecScheme> {
+        vec![
+            FecScheme::Ldpc,
+            FecScheme::Convolutional,
+            FecScheme::Repetition { factor: 3 },
+            FecScheme::Polar { list_size: 1 },
+            FecScheme::Polar { list_size: 8 },
+        ]
+    }
+
+    #[test]
+    fn every_codec_fills_the_frame_codeword() {
+        let layout = FrameLayout::default();
+        for fec in schemes() {
+            let cfg = LDPCConfig {
+                fec,
+                ..LDPCConfig::default()
+            };
+            let codec = build_codec(&layout, &cfg).unwrap();
+
+            assert_eq!(
+                codec.codeword_bits(),
+                layout.codeword_bits(),
+                "{}",
+                codec.name()
+            );
+            assert!(codec.code_rate() > 0.0 && codec.code_rate() <= 1.0);
+        }
+    }
+
+    #[test]
+    fn message_must_leave_room_for_the_checksum() {
+        // Repeating the 160-bit codeword five times leaves a 32-bit message
+        let cfg = LDPCConfig {
+            fec: FecScheme::Repetition { factor: 5 },
+            ..LDPCConfig::default()
+        };
+        let codec = build_codec(&FrameLayout::default(), &cfg).unwrap();
+        assert_eq!(codec.message_bits(), 32);
+
+        let layout = FrameLayout {
+            checksum: crate::config::FrameChecksum::Crc32,
+            ..FrameLayout::default()
+        };
+        assert!(build_codec(&layout, &cfg).is_err());
+
+        let protocol = crate::config::InternalProtocolConfig {
+            frame_layout: layout,
+            ..Default::default()
+        };
+        assert!(crate::encoder::StreamingFrameEncoder::with_codec(&[1, 0, 1], protocol, codec).is_err());
+    }
+
+    #[test]
+    fn every_codec_corrects_a_weak_error() {
+        let layout = FrameLayout::default();
+        let mut rng = StdRng::seed_from_u64(21);
+
+        for fec in schemes() {
+            let cfg = LDPCConfig {
+                fec,
+                ..LDPCConfig::default()
+            };
+            let codec = build_codec(&layout, &cfg).unwrap();
+
+            let message: Vec<u8> = (0..codec.message_bits())
+                .map(|_| u8::from(rng.gen_bool(0.5)))
+                .collect();
+            let codeword = codec.encode(&message);
+            let mut llrs: Vec<f64> = codeword
+                .iter()
+                .map(|&b| if b == 0 { 3.0 } else { -3.0 })
+                .collect();
+            llrs[7] = -llrs[7] * 0.2;
+
+            let outcome = codec.decode_soft(&llrs);
+            assert!(outcome.success, "{}", codec.name());
+            assert_eq!(outcome.message_bits, message, "{}", codec.name());
+            assert_eq!(outcome.bits_flipped, 1, "{}", codec.name());
+        }
+    }
+}
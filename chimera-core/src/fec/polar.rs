@@ -0,0 +1,397 @@
+//! Polar code with CRC-aided successive-cancellation list decoding.
+//!
+//! The mother code has length `N = 2^n >= codeword_bits` and generator
+//! `F^{⊗n}` with `F = [[1, 0], [1, 1]]` (no bit reversal). It is shortened to
+//! the frame codeword by freezing every input index at or above
+//! `codeword_bits`: those code bits then depend only on frozen inputs, are
+//! always zero and are not transmitted. The decoder treats them as known
+//! zeros.
+//!
+//! The message is followed by an 8-bit CRC, and message plus CRC occupy the
+//! most reliable remaining inputs ranked by polarization weight. The list
+//! decoder keeps the `list_size` most likely paths and returns the best one
+//! whose CRC checks; a list size of 1 is plain successive cancellation.
+
+use crate::errors::{ConfigError, Result};
+use crate::ldpc::DecodeOutcome;
+
+use super::{codeword_too_short, outcome_from_codeword, FecCodec};
+
+/// CRC appended to the message for path selection
+const CRC_BITS: usize = 8;
+/// CRC-8 generator x^8 + x^2 + x + 1 (without the leading term)
+const CRC_POLY: u8 = 0x07;
+/// LLR of a shortened (known zero) code bit
+const SHORTENED_LLR: f64 = 1e6;
+
+/// CRC-8 remainder of `bits`, MSB first
+fn crc8(bits: &[u8]) -> u8 {
+    bits.iter().fold(0u8, |crc, &bit| {
+        let feedback = (crc >> 7) ^ (bit & 1);
+        (crc << 1) ^ if feedback == 1 { CRC_POLY } else { 0 }
+    })
+}
+
+/// Reliability of input index `i` as its polarization weight
+/// `Σ b_j 2^{j/4}` over the set bits `b_j` of `i`
+fn polarization_weight(index: usize) -> f64 {
+    (0..usize::BITS)
+        .filter(|&bit| index >> bit & 1 == 1)
+        .map(|bit| 2f64.powf(f64::from(bit) / 4.0))
+        .sum()
+}
+
+/// Computes `x = u · F^{⊗n}` in place.
+fn polar_transform(bits: &mut [u8]) {
+    let mut half = 1;
+    while half < bits.len() {
+        for block in bits.chunks_mut(2 * half) {
+            let (left, right) = block.split_at_mut(half);
+            for (l, &r) in left.iter_mut().zip(right.iter()) {
+                *l ^= r;
+            }
+        }
+        half *= 2;
+    }
+}
+
+/// Min-sum check-node combination of two LLRs
+fn f_min_sum(a: f64, b: f64) -> f64 {
+    a.signum() * b.signum() * a.abs().min(b.abs())
+}
+
+/// Path-metric penalty for deciding `bit` against `llr`: `ln(1 + e^{-(1-2bit)·llr})`
+fn penalty(llr: f64, bit: u8) -> f64 {
+    let x = if bit == 0 { -llr } else { llr };
+    x.max(0.0) + (-x.abs()).exp().ln_1p()
+}
+
+/// One decoding path of the list
+#[derive(Debug, Clone)]
+struct Path {
+    /// `alpha[level]` holds the LLRs of the current node of size `2^level`
+    alpha: Vec<Vec<f64>>,
+    /// `beta[level]` holds the partial sums of the current node of size `2^level`
+    beta: Vec<Vec<u8>>,
+    /// Decided input bits
+    inputs: Vec<u8>,
+    /// Accumulated decision penalty (lower is more likely)
+    metric: f64,
+}
+
+/// Shortened polar code sized to a frame codeword
+#[derive(Debug, Clone)]
+pub struct PolarCodec {
+    message_bits: usize,
+    codeword_bits: usize,
+    /// log2 of the mother code length
+    levels: usize,
+    /// Input indices carrying message + CRC bits, ascending
+    information_set: Vec<usize>,
+    /// `frozen[i]` for every input index of the mother code
+    frozen: Vec<bool>,
+    list_size: usize,
+}
+
+impl PolarCodec {
+    /// Creates the code carrying `message_bits` bits plus an 8-bit CRC in
+    /// `codeword_bits` channel bits, decoded with a list of `list_size` paths.
+    pub fn new(message_bits: usize, codeword_bits: usize, list_size: usize) -> Result<Self> {
+        if list_size == 0 {
+            return Err(ConfigError::InvalidFrameLayout {
+                reason: "polar list size must be at least 1".into(),
+            }
+            .into());
+        }
+        let info_bits = message_bits + CRC_BITS;
+        if message_bits == 0 || info_bits > codeword_bits {
+            return Err(codeword_too_short("polar", codeword_bits).into());
+        }
+
+        let mother = codeword_bits.next_power_of_two();
+        let mut ranked: Vec<usize> = (0..codeword_bits).collect();
+        ranked.sort_by(|&a, &b| polarization_weight(b).total_cmp(&polarization_weight(a)));
+        let mut information_set = ranked[..info_bits].to_vec();
+        information_set.sort_unstable();
+
+        let mut frozen = vec![true; mother];
+        for &idx in &information_set {
+            frozen[idx] = false;
+        }
+
+        Ok(Self {
+            message_bits,
+            codeword_bits,
+            levels: mother.trailing_zeros() as usize,
+            information_set,
+            frozen,
+            list_size,
+        })
+    }
+
+    pub fn list_size(&self) -> usize {
+        self.list_size
+    }
+
+    /// Input indices carrying message and CRC bits
+    pub fn information_set(&self) -> &[usize] {
+        &self.information_set
+    }
+
+    fn mother_length(&self) -> usize {
+        self.frozen.len()
+    }
+
+    /// Splits decided inputs into message bits and whether the CRC checks.
+    fn unpack_inputs(&self, inputs: &[u8]) -> (Vec<u8>, bool) {
+        let info: Vec<u8> = self.information_set.iter().map(|&i| inputs[i]).collect();
+        let crc_ok = crc8(&info) == 0;
+        (info[..self.message_bits].to_vec(), crc_ok)
+    }
+
+    fn new_path(&self, channel: Vec<f64>) -> Path {
+        let mut alpha: Vec<Vec<f64>> = (0..self.levels).map(|l| vec![0.0; 1 << l]).collect();
+        alpha.push(channel);
+        Path {
+            alpha,
+            beta: (0..=self.levels).map(|l| vec![0; 1 << l]).collect(),
+            inputs: vec![0; self.mother_length()],
+            metric: 0.0,
+        }
+    }
+
+    /// Decodes the node of size `2^level` whose first input is `first` on
+    /// every path, leaving its partial sums in `beta[level]`.
+    fn decode_node(&self, paths: &mut Vec<Path>, level: usize, first: usize) {
+        if level == 0 {
+            self.decide_leaf(paths, first);
+            return;
+        }
+
+        let half = 1 << (level - 1);
+        for path in paths.iter_mut() {
+            let (lower, upper) = path.alpha.split_at_mut(level);
+            let (a, b) = upper[0].split_at(half);
+            for ((out, &a), &b) in lower[level - 1].iter_mut().zip(a).zip(b) {
+                *out = f_min_sum(a, b);
+            }
+        }
+        self.decode_node(paths, level - 1, first);
+
+        for path in paths.iter_mut() {
+            let (lower, upper) = path.beta.split_at_mut(level);
+            upper[0][..half].copy_from_slice(&lower[level - 1]);
+
+            let (lower, upper) = path.alpha.split_at_mut(level);
+            let (a, b) = upper[0].split_at(half);
+            for (((out, &a), &b), &v) in lower[level - 1]
+                .iter_mut()
+                .zip(a)
+                .zip(b)
+                .zip(&path.beta[level][..half])
+            {
+                *out = if v == 0 { b + a } else { b - a };
+            }
+        }
+        self.decode_node(paths, level - 1, first + half);
+
+        for path in paths.iter_mut() {
+            let (lower, upper) = path.beta.split_at_mut(level);
+            let (left, right) = upper[0].split_at_mut(half);
+            for ((l, r), &v) in left.iter_mut().zip(right.iter_mut()).zip(&lower[level - 1]) {
+                *l ^= v;
+                *r = v;
+            }
+        }
+    }
+
+    /// Decides input `index` on every path, forking on information bits and
+    /// keeping the `list_size` most likely continuations.
+    fn decide_leaf(&self, paths: &mut Vec<Path>, index: usize) {
+        if self.frozen[index] {
+            for path in paths.iter_mut() {
+                path.metric += penalty(path.alpha[0][0], 0);
+                path.beta[0][0] = 0;
+                path.inputs[index] = 0;
+            }
+            return;
+        }
+
+        // (path, bit, metric) for both continuations of every path
+        let mut candidates: Vec<(usize, u8, f64)> = paths
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, path)| {
+                let llr = path.alpha[0][0];
+                [0u8, 1].map(|bit| (idx, bit, path.metric + penalty(llr, bit)))
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.2.total_cmp(&b.2));
+        candidates.truncate(self.list_size);
+
+        let mut survivors = Vec::with_capacity(candidates.len());
+        let mut old: Vec<Option<Path>> = std::mem::take(paths).into_iter().map(Some).collect();
+        let mut remaining = vec![0usize; old.len()];
+        for &(idx, _, _) in &candidates {
+            remaining[idx] += 1;
+        }
+        for (idx, bit, metric) in candidates {
+            remaining[idx] -= 1;
+            // The last continuation of a path takes it over; earlier ones copy it
+            let mut path = if remaining[idx] == 0 {
+                old[idx].take().expect("path continued twice")
+            } else {
+                old[idx].clone().expect("path already moved")
+            };
+            path.metric = metric;
+            path.beta[0][0] = bit;
+            path.inputs[index] = bit;
+            survivors.push(path);
+        }
+        *paths = survivors;
+    }
+
+    /// Runs the list decoder and returns the decided inputs of the chosen
+    /// path and whether its CRC checks.
+    fn decode_inputs(&self, llrs: &[f64]) -> (Vec<u8>, bool) {
+        let mut channel = vec![SHORTENED_LLR; self.mother_length()];
+        for (slot, &llr) in channel.iter_mut().zip(&llrs[..self.codeword_bits.min(llrs.len())]) {
+            *slot = llr;
+        }
+
+        let mut paths = vec![self.new_path(channel)];
+        self.decode_node(&mut paths, self.levels, 0);
+        paths.sort_by(|a, b| a.metric.total_cmp(&b.metric));
+
+        let chosen = paths
+            .iter()
+            .position(|path| self.unpack_inputs(&path.inputs).1)
+            .unwrap_or(0);
+        let inputs = paths.swap_remove(chosen).inputs;
+        let crc_ok = self.unpack_inputs(&inputs).1;
+        (inputs, crc_ok)
+    }
+
+    fn encode_inputs(&self, mut inputs: Vec<u8>) -> Vec<u8> {
+        polar_transform(&mut inputs);
+        inputs.truncate(self.codeword_bits);
+        inputs
+    }
+}
+
+impl FecCodec for PolarCodec {
+    fn name(&self) -> &'static str {
+        "polar"
+    }
+
+    fn message_bits(&self) -> usize {
+        self.message_bits
+    }
+
+    fn codeword_bits(&self) -> usize {
+        self.codeword_bits
+    }
+
+    fn encode(&self, message: &[u8]) -> Vec<u8> {
+        let mut info: Vec<u8> = message[..self.message_bits.min(message.len())]
+            .iter()
+            .map(|&b| b & 1)
+            .collect();
+        info.resize(self.message_bits, 0);
+        let crc = crc8(&info);
+        info.extend((0..CRC_BITS).rev().map(|bit| (crc >> bit) & 1));
+
+        let mut inputs = vec![0u8; self.mother_length()];
+        for (&idx, &bit) in self.information_set.iter().zip(&info) {
+            inputs[idx] = bit;
+        }
+        self.encode_inputs(inputs)
+    }
+
+    fn decode_soft(&self, llrs: &[f64]) -> DecodeOutcome {
+        let (inputs, crc_ok) = self.decode_inputs(llrs);
+        let (message, _) = self.unpack_inputs(&inputs);
+        let codeword = self.encode_inputs(inputs);
+
+        let mut outcome = outcome_from_codeword(llrs, message, codeword);
+        outcome.success = crc_ok;
+        outcome.syndrome_weight_after = usize::from(!crc_ok);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::{Distribution, Normal};
+
+    fn random_bits(rng: &mut StdRng, len: usize) -> Vec<u8> {
+        (0..len).map(|_| u8::from(rng.gen_bool(0.5))).collect()
+    }
+
+    #[test]
+    fn construction_freezes_the_shortened_inputs() {
+        let codec = PolarCodec::new(128, 160, 4).unwrap();
+        assert_eq!(codec.mother_length(), 256);
+        assert_eq!(codec.information_set().len(), 128 + CRC_BITS);
+        assert!(codec.information_set().iter().all(|&i| i < 160));
+        // The most reliable unshortened input is always used
+        assert!(codec.information_set().contains(&159));
+
+        assert!(PolarCodec::new(128, 160, 0).is_err());
+        assert!(PolarCodec::new(155, 160, 1).is_err());
+    }
+
+    #[test]
+    fn clean_codewords_decode_with_sc_and_scl() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for list_size in [1, 8] {
+            let codec = PolarCodec::new(128, 160, list_size).unwrap();
+            for _ in 0..5 {
+                let message = random_bits(&mut rng, 128);
+                let codeword = codec.encode(&message);
+                assert_eq!(codeword.len(), 160);
+
+                let llrs: Vec<f64> = codeword
+                    .iter()
+                    .map(|&b| if b == 0 { 4.0 } else { -4.0 })
+                    .collect();
+                let outcome = codec.decode_soft(&llrs);
+                assert!(outcome.success);
+                assert_eq!(outcome.message_bits, message);
+                assert_eq!(outcome.codeword, codeword);
+            }
+        }
+    }
+
+    #[test]
+    fn list_decoding_beats_successive_cancellation() {
+        let sc = PolarCodec::new(128, 160, 1).unwrap();
+        let scl = PolarCodec::new(128, 160, 8).unwrap();
+        let sigma = 0.5;
+        let noise = Normal::new(0.0, sigma).unwrap();
+        let mut rng = StdRng::seed_from_u64(12);
+
+        let (mut sc_errors, mut scl_errors) = (0, 0);
+        for _ in 0..100 {
+            let message = random_bits(&mut rng, 128);
+            let llrs: Vec<f64> = sc
+                .encode(&message)
+                .iter()
+                .map(|&b| {
+                    let symbol = if b == 0 { 1.0 } else { -1.0 };
+                    2.0 * (symbol + noise.sample(&mut rng)) / (sigma * sigma)
+                })
+                .collect();
+            sc_errors += usize::from(sc.decode_soft(&llrs).message_bits != message);
+            scl_errors += usize::from(scl.decode_soft(&llrs).message_bits != message);
+        }
+
+        assert!(sc_errors > 0, "channel too clean to compare decoders");
+        assert!(
+            scl_errors * 2 < sc_errors,
+            "SCL-8 {scl_errors} frame errors vs SC {sc_errors}"
+        );
+    }
+}
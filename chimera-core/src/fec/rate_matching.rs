@@ -0,0 +1,311 @@
+//! Shortening and puncturing of a mother code to fit a frame layout.
+//!
+//! The mother code's generator is recovered by encoding unit messages (every
+//! codec is linear). The last `k_m - k` message bits are shortened: they are
+//! always zero, so code bits that depend on no other message bit are known
+//! zeros and are not sent. Of the remaining code bits, the surplus over the
+//! layout's codeword is punctured, spread evenly over the non-systematic
+//! positions. The decoder feeds the mother code confident zeros for
+//! shortened bits and zero LLRs (erasures) for punctured ones.
+
+use std::sync::Arc;
+
+use crate::errors::{ConfigError, Result};
+use crate::ldpc::DecodeOutcome;
+
+use super::{hard_decision, FecCodec};
+
+/// LLR fed to the mother decoder for a known zero bit
+const KNOWN_ZERO_LLR: f64 = 1e3;
+
+/// Role of a mother code bit after rate matching
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitRole {
+    /// Sent on the channel
+    Transmitted,
+    /// Depends only on shortened message bits; always zero, not sent
+    Shortened,
+    /// Not sent; decoded as an erasure
+    Punctured,
+}
+
+/// Mother code adapted to `message_bits` / `codeword_bits`
+#[derive(Debug, Clone)]
+pub struct RateMatchedCodec {
+    mother: Arc<dyn FecCodec>,
+    message_bits: usize,
+    /// Mother code positions sent on the channel, in channel order
+    transmitted: Vec<usize>,
+    /// Role of every mother code position
+    roles: Vec<BitRole>,
+}
+
+impl RateMatchedCodec {
+    /// Shortens and punctures `mother` to carry `message_bits` bits in
+    /// `codeword_bits` channel bits.
+    ///
+    /// Fails if the mother code has fewer message bits, or if shortening
+    /// leaves fewer code bits than requested.
+    pub fn new(mother: Arc<dyn FecCodec>, message_bits: usize, codeword_bits: usize) -> Result<Self> {
+        let (mother_k, mother_n) = (mother.message_bits(), mother.codeword_bits());
+        let unachievable = |reason: String| ConfigError::InvalidFrameLayout {
+            reason: format!(
+                "rate {message_bits}/{codeword_bits} not achievable from the \
+                 {mother_k}/{mother_n} {} code: {reason}",
+                mother.name()
+            ),
+        };
+        if message_bits == 0 || message_bits > mother_k {
+            return Err(unachievable("message cannot be shortened to that size".into()).into());
+        }
+
+        // Row i of the generator is the codeword of the i-th unit message
+        let generator: Vec<Vec<u8>> = (0..mother_k)
+            .map(|row| {
+                let unit: Vec<u8> = (0..mother_k).map(|i| u8::from(i == row)).collect();
+                mother.encode(&unit)
+            })
+            .collect();
+        let column = |pos: usize| generator.iter().map(move |row| row[pos] & 1);
+
+        let mut roles = vec![BitRole::Transmitted; mother_n];
+        let mut systematic = vec![false; mother_n];
+        for (pos, role) in roles.iter_mut().enumerate() {
+            if column(pos).take(message_bits).all(|bit| bit == 0) {
+                *role = BitRole::Shortened;
+            } else if column(pos).map(usize::from).sum::<usize>() == 1 {
+                systematic[pos] = true;
+            }
+        }
+
+        let available = roles.iter().filter(|&&r| r == BitRole::Transmitted).count();
+        let surplus = available.checked_sub(codeword_bits).ok_or_else(|| {
+            unachievable(format!("shortening leaves only {available} code bits"))
+        })?;
+        let parity: Vec<usize> = (0..mother_n)
+            .filter(|&pos| roles[pos] == BitRole::Transmitted && !systematic[pos])
+            .collect();
+        if surplus > parity.len() {
+            return Err(unachievable(format!(
+                "only {} parity bits can be punctured, {surplus} needed",
+                parity.len()
+            ))
+            .into());
+        }
+        for idx in 0..surplus {
+            roles[parity[(2 * idx + 1) * parity.len() / (2 * surplus)]] = BitRole::Punctured;
+        }
+
+        let transmitted = (0..mother_n)
+            .filter(|&pos| roles[pos] == BitRole::Transmitted)
+            .collect();
+
+        Ok(Self {
+            mother,
+            message_bits,
+            transmitted,
+            roles,
+        })
+    }
+
+    /// The code being shortened and punctured
+    pub fn mother(&self) -> &Arc<dyn FecCodec> {
+        &self.mother
+    }
+
+    /// Role of every mother code bit
+    pub fn roles(&self) -> &[BitRole] {
+        &self.roles
+    }
+
+    /// Expands channel LLRs to mother code LLRs: known zeros for shortened
+    /// bits and erasures for punctured ones.
+    pub fn mother_llrs(&self, llrs: &[f64]) -> Vec<f64> {
+        let mut mother: Vec<f64> = self
+            .roles
+            .iter()
+            .map(|role| match role {
+                BitRole::Shortened => KNOWN_ZERO_LLR,
+                BitRole::Transmitted | BitRole::Punctured => 0.0,
+            })
+            .collect();
+        for (&pos, &llr) in self.transmitted.iter().zip(llrs) {
+            mother[pos] = llr;
+        }
+        mother
+    }
+
+    fn select_transmitted(&self, mother_codeword: &[u8]) -> Vec<u8> {
+        self.transmitted
+            .iter()
+            .map(|&pos| mother_codeword[pos])
+            .collect()
+    }
+}
+
+impl FecCodec for RateMatchedCodec {
+    fn name(&self) -> &'static str {
+        self.mother.name()
+    }
+
+    fn message_bits(&self) -> usize {
+        self.message_bits
+    }
+
+    fn codeword_bits(&self) -> usize {
+        self.transmitted.len()
+    }
+
+    fn encode(&self, message: &[u8]) -> Vec<u8> {
+        let mut padded = message[..self.message_bits.min(message.len())].to_vec();
+        padded.resize(self.mother.message_bits(), 0);
+        self.select_transmitted(&self.mother.encode(&padded))
+    }
+
+    fn decode_soft(&self, llrs: &[f64]) -> DecodeOutcome {
+        let inner = self.mother.decode_soft(&self.mother_llrs(llrs));
+        let channel_codeword = hard_decision(&llrs[..self.codeword_bits().min(llrs.len())]);
+        let codeword = self.select_transmitted(&inner.codeword);
+        let bits_flipped = channel_codeword
+            .iter()
+            .zip(&codeword)
+            .filter(|(a, b)| a != b)
+            .count();
+
+        DecodeOutcome {
+            message_bits: inner.message_bits[..self.message_bits].to_vec(),
+            channel_codeword,
+            codeword,
+            bits_flipped,
+            ..inner
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FecScheme, FrameLayout, LDPCConfig, RateMatching};
+    use crate::fec::build_codec;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::{Distribution, Normal};
+
+    /// The default 64/16-symbol LDPC code matched to the burst telemetry split
+    fn burst_layout() -> FrameLayout {
+        FrameLayout {
+            total_symbols: 96,
+            sync_symbols: 24,
+            target_id_symbols: 16,
+            command_type_symbols: 8,
+            data_payload_symbols: 40,
+            ecc_symbols: 8,
+            rate_matching: RateMatching::Mother {
+                mother_payload_symbols: 64,
+                mother_ecc_symbols: 16,
+            },
+            ..FrameLayout::default()
+        }
+    }
+
+    fn noisy_llrs(codeword: &[u8], sigma: f64, rng: &mut StdRng) -> Vec<f64> {
+        let noise = Normal::new(0.0, sigma).unwrap();
+        codeword
+            .iter()
+            .map(|&b| {
+                let symbol = if b == 0 { 1.0 } else { -1.0 };
+                2.0 * (symbol + noise.sample(rng)) / (sigma * sigma)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ldpc_mother_code_fits_a_smaller_layout() {
+        let layout = burst_layout();
+        layout.validate().unwrap();
+        let codec = build_codec(&layout, &LDPCConfig::default()).unwrap();
+        assert_eq!(codec.message_bits(), 80);
+        assert_eq!(codec.codeword_bits(), 96);
+
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut decoded = 0;
+        for _ in 0..20 {
+            let message: Vec<u8> = (0..80).map(|_| u8::from(rng.gen_bool(0.5))).collect();
+            let codeword = codec.encode(&message);
+            assert_eq!(codeword.len(), 96);
+
+            let outcome = codec.decode_soft(&noisy_llrs(&codeword, 0.4, &mut rng));
+            decoded += usize::from(outcome.success && outcome.message_bits == message);
+        }
+        assert!(decoded >= 18, "decoded {decoded}/20 frames");
+    }
+
+    #[test]
+    fn punctured_bits_are_erasures_and_shortened_bits_known() {
+        let mother = build_codec(&FrameLayout::default(), &LDPCConfig::default()).unwrap();
+        let codec = RateMatchedCodec::new(mother, 80, 96).unwrap();
+        let count = |role| codec.roles().iter().filter(|&&r| r == role).count();
+        // The 48 shortened systematic bits (and any parity bit depending only
+        // on them) are dropped, then parity bits are punctured down to 96
+        assert!(count(BitRole::Shortened) >= 48);
+        assert_eq!(count(BitRole::Shortened) + count(BitRole::Punctured), 64);
+
+        let llrs = codec.mother_llrs(&[-2.0; 96]);
+        for (role, llr) in codec.roles().iter().zip(&llrs) {
+            let expected = match role {
+                BitRole::Transmitted => -2.0,
+                BitRole::Shortened => KNOWN_ZERO_LLR,
+                BitRole::Punctured => 0.0,
+            };
+            assert_eq!(*llr, expected);
+        }
+    }
+
+    #[test]
+    fn punctured_convolutional_code_raises_the_rate() {
+        // Rate-1/2 code for 74 bits punctured from 160 to 120 channel bits
+        let layout = FrameLayout {
+            total_symbols: 108,
+            data_payload_symbols: 37,
+            ecc_symbols: 23,
+            rate_matching: RateMatching::Mother {
+                mother_payload_symbols: 37,
+                mother_ecc_symbols: 43,
+            },
+            ..FrameLayout::default()
+        };
+        layout.validate().unwrap();
+        let cfg = LDPCConfig {
+            fec: FecScheme::Convolutional,
+            ..LDPCConfig::default()
+        };
+        let codec = build_codec(&layout, &cfg).unwrap();
+        assert_eq!(codec.codeword_bits(), 120);
+
+        let message: Vec<u8> = (0..74).map(|i| u8::from(i % 5 < 2)).collect();
+        let mut llrs: Vec<f64> = codec
+            .encode(&message)
+            .iter()
+            .map(|&b| if b == 0 { 3.0 } else { -3.0 })
+            .collect();
+        llrs[30] = -llrs[30];
+        llrs[90] = -llrs[90];
+        assert_eq!(codec.decode_soft(&llrs).message_bits, message);
+    }
+
+    #[test]
+    fn validate_rejects_unachievable_rates() {
+        let larger_payload = FrameLayout {
+            total_symbols: 136,
+            data_payload_symbols: 80,
+            ..burst_layout()
+        };
+        assert!(larger_payload.validate().is_err());
+
+        let more_ecc = FrameLayout {
+            total_symbols: 108,
+            ecc_symbols: 20,
+            ..burst_layout()
+        };
+        assert!(more_ecc.validate().is_err());
+    }
+}
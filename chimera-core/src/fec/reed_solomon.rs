@@ -0,0 +1,474 @@
+//! Reed–Solomon outer code over GF(256) spanning the frames of a message.
+//!
+//! A message of `K` data frames is followed by `P` parity frames. Byte `j` of
+//! every frame forms one systematic RS(`K + P`, `K`) codeword, so the outer
+//! code sees each frame as a single symbol position. Frames that fail the
+//! inner decode (or never arrive) are known-bad positions and are decoded as
+//! erasures: up to `P` lost frames per message are rebuilt exactly.
+//!
+//! The field uses the primitive polynomial `x^8 + x^4 + x^3 + x^2 + 1`
+//! (0x11D) and the generator `g(x) = (x - α^0)(x - α^1)...(x - α^(P-1))`.
+
+use crate::errors::{ConfigError, DecodingError, Result};
+
+/// Maximum RS codeword length (frames per message) over GF(256)
+pub const MAX_FRAMES: usize = 255;
+
+const PRIMITIVE_POLY: u16 = 0x11D;
+
+const fn build_tables() -> ([u8; 512], [u8; 256]) {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+    let mut x: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x as u8;
+        log[x as usize] = i as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= PRIMITIVE_POLY;
+        }
+        i += 1;
+    }
+    while i < 512 {
+        exp[i] = exp[i - 255];
+        i += 1;
+    }
+    (exp, log)
+}
+
+const TABLES: ([u8; 512], [u8; 256]) = build_tables();
+const EXP: [u8; 512] = TABLES.0;
+const LOG: [u8; 256] = TABLES.1;
+
+fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        EXP[LOG[a as usize] as usize + LOG[b as usize] as usize]
+    }
+}
+
+fn gf_div(a: u8, b: u8) -> u8 {
+    assert!(b != 0, "division by zero in GF(256)");
+    if a == 0 {
+        0
+    } else {
+        EXP[LOG[a as usize] as usize + 255 - LOG[b as usize] as usize]
+    }
+}
+
+fn gf_pow(a: u8, exponent: usize) -> u8 {
+    if exponent == 0 {
+        1
+    } else if a == 0 {
+        0
+    } else {
+        EXP[(LOG[a as usize] as usize * exponent) % 255]
+    }
+}
+
+fn gf_pow_alpha(power: usize) -> u8 {
+    EXP[power % 255]
+}
+
+/// Evaluates a polynomial (highest degree first) at `x`
+fn poly_eval(poly: &[u8], x: u8) -> u8 {
+    poly.iter().fold(0, |acc, &coef| gf_mul(acc, x) ^ coef)
+}
+
+/// Multiplies two polynomials (highest degree first)
+fn poly_mul(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut product = vec![0u8; a.len() + b.len() - 1];
+    for (i, &ai) in a.iter().enumerate() {
+        for (j, &bj) in b.iter().enumerate() {
+            product[i + j] ^= gf_mul(ai, bj);
+        }
+    }
+    product
+}
+
+/// Systematic RS(`data_frames + parity_frames`, `data_frames`) code
+#[derive(Debug, Clone)]
+pub struct ReedSolomon {
+    data_frames: usize,
+    parity_frames: usize,
+    /// Generator polynomial, highest degree first (monic)
+    generator: Vec<u8>,
+}
+
+impl ReedSolomon {
+    /// Creates the code for `data_frames` data and `parity_frames` parity frames.
+    ///
+    /// Returns [`ConfigError::InvalidFrameLayout`] if there are no data frames or
+    /// the total exceeds [`MAX_FRAMES`].
+    pub fn new(data_frames: usize, parity_frames: usize) -> Result<Self> {
+        if data_frames == 0 || data_frames + parity_frames > MAX_FRAMES {
+            return Err(ConfigError::InvalidFrameLayout {
+                reason: format!(
+                    "Reed-Solomon outer code needs 1..={MAX_FRAMES} frames, got {data_frames} data + {parity_frames} parity"
+                ),
+            }
+            .into());
+        }
+
+        let generator =
+            (0..parity_frames).fold(vec![1u8], |poly, i| poly_mul(&poly, &[1, gf_pow_alpha(i)]));
+
+        Ok(Self {
+            data_frames,
+            parity_frames,
+            generator,
+        })
+    }
+
+    pub fn data_frames(&self) -> usize {
+        self.data_frames
+    }
+
+    pub fn parity_frames(&self) -> usize {
+        self.parity_frames
+    }
+
+    pub fn total_frames(&self) -> usize {
+        self.data_frames + self.parity_frames
+    }
+
+    /// Computes the parity symbols of one codeword from its `data_frames` data symbols.
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        // Remainder of data(x) * x^P divided by g(x)
+        let mut remainder = vec![0u8; self.parity_frames];
+        for &symbol in data.iter().take(self.data_frames) {
+            let feedback = symbol ^ remainder.first().copied().unwrap_or(0);
+            remainder.rotate_left(1);
+            if let Some(last) = remainder.last_mut() {
+                *last = 0;
+            }
+            for (r, &g) in remainder.iter_mut().zip(&self.generator[1..]) {
+                *r ^= gf_mul(feedback, g);
+            }
+        }
+        remainder
+    }
+
+    /// Corrects the erased positions of `codeword` (data followed by parity) in place.
+    ///
+    /// Returns [`DecodingError::TooManyErasures`] if more than `parity_frames`
+    /// positions are erased, and [`DecodingError::InvalidCodeword`] if the
+    /// corrected word still fails the syndrome check (an unflagged error).
+    pub fn decode_erasures(
+        &self,
+        codeword: &mut [u8],
+        erasures: &[usize],
+    ) -> std::result::Result<(), DecodingError> {
+        let n = self.total_frames();
+        if erasures.len() > self.parity_frames {
+            return Err(DecodingError::TooManyErasures {
+                erasures: erasures.len(),
+                correctable: self.parity_frames,
+            });
+        }
+
+        // Syndromes S_i = c(α^i), i.e. S(x) = Σ S_i x^i lowest degree first
+        let syndromes: Vec<u8> = (0..self.parity_frames)
+            .map(|i| poly_eval(&codeword[..n], gf_pow_alpha(i)))
+            .collect();
+        if syndromes.iter().all(|&s| s == 0) {
+            return Ok(());
+        }
+
+        // Position of symbol k in c(x) (highest degree first) is x^(n-1-k)
+        let locators: Vec<u8> = erasures.iter().map(|&k| gf_pow_alpha(n - 1 - k)).collect();
+
+        // Λ(x) = Π (1 + X_j x), lowest degree first
+        let mut lambda = vec![1u8];
+        for &x in &locators {
+            let mut next = vec![0u8; lambda.len() + 1];
+            for (i, &coef) in lambda.iter().enumerate() {
+                next[i] ^= coef;
+                next[i + 1] ^= gf_mul(coef, x);
+            }
+            lambda = next;
+        }
+
+        // Ω(x) = S(x) Λ(x) mod x^P, lowest degree first
+        let mut omega = vec![0u8; self.parity_frames];
+        for (i, &s) in syndromes.iter().enumerate() {
+            for (j, &l) in lambda.iter().enumerate() {
+                if i + j < self.parity_frames {
+                    omega[i + j] ^= gf_mul(s, l);
+                }
+            }
+        }
+
+        // Forney: e_j = X_j Ω(X_j^-1) / Λ'(X_j^-1)
+        let eval_low_first = |poly: &[u8], x: u8| {
+            poly.iter()
+                .rev()
+                .fold(0, |acc, &coef| gf_mul(acc, x) ^ coef)
+        };
+        for (&pos, &x) in erasures.iter().zip(&locators) {
+            let x_inv = gf_div(1, x);
+            // Formal derivative in characteristic 2 keeps only odd powers
+            let derivative: u8 = lambda
+                .iter()
+                .enumerate()
+                .skip(1)
+                .step_by(2)
+                .fold(0, |acc, (i, &coef)| {
+                    acc ^ gf_mul(coef, gf_pow(x_inv, i - 1))
+                });
+            if derivative == 0 {
+                return Err(DecodingError::InvalidCodeword);
+            }
+            let magnitude = gf_div(gf_mul(x, eval_low_first(&omega, x_inv)), derivative);
+            codeword[pos] ^= magnitude;
+        }
+
+        let clean =
+            (0..self.parity_frames).all(|i| poly_eval(&codeword[..n], gf_pow_alpha(i)) == 0);
+        if clean {
+            Ok(())
+        } else {
+            Err(DecodingError::InvalidCodeword)
+        }
+    }
+
+    /// Computes the parity frames for `data` (each frame `bytes_per_frame` long).
+    pub fn encode_frames(&self, data: &[Vec<u8>], bytes_per_frame: usize) -> Vec<Vec<u8>> {
+        let mut parity = vec![vec![0u8; bytes_per_frame]; self.parity_frames];
+        for byte in 0..bytes_per_frame {
+            let column: Vec<u8> = data
+                .iter()
+                .map(|frame| frame.get(byte).copied().unwrap_or(0))
+                .collect();
+            for (frame, symbol) in parity.iter_mut().zip(self.encode(&column)) {
+                frame[byte] = symbol;
+            }
+        }
+        parity
+    }
+
+    /// Rebuilds missing frames (`None`) in place and returns the data frames
+    /// concatenated.
+    pub fn reconstruct_frames(
+        &self,
+        frames: &mut [Option<Vec<u8>>],
+        bytes_per_frame: usize,
+    ) -> std::result::Result<Vec<u8>, DecodingError> {
+        let erasures: Vec<usize> = (0..self.total_frames())
+            .filter(|&idx| frames.get(idx).is_none_or(Option::is_none))
+            .collect();
+        if erasures.len() > self.parity_frames {
+            return Err(DecodingError::TooManyErasures {
+                erasures: erasures.len(),
+                correctable: self.parity_frames,
+            });
+        }
+
+        let mut rebuilt = vec![vec![0u8; bytes_per_frame]; self.total_frames()];
+        for byte in 0..bytes_per_frame {
+            let mut column: Vec<u8> = (0..self.total_frames())
+                .map(|idx| {
+                    frames
+                        .get(idx)
+                        .and_then(Option::as_ref)
+                        .and_then(|frame| frame.get(byte).copied())
+                        .unwrap_or(0)
+                })
+                .collect();
+            self.decode_erasures(&mut column, &erasures)?;
+            for (frame, symbol) in rebuilt.iter_mut().zip(column) {
+                frame[byte] = symbol;
+            }
+        }
+
+        for (slot, frame) in frames.iter_mut().zip(&rebuilt) {
+            slot.get_or_insert_with(|| frame.clone());
+        }
+        Ok(rebuilt[..self.data_frames].concat())
+    }
+}
+
+/// Collects the frames of one outer-coded message from the receiver.
+///
+/// Frames are placed by the `current_frame`/`total_frames` counters of the
+/// command field; a frame passed as `None` (inner decode failed) or never
+/// seen becomes an erasure.
+#[derive(Debug, Clone)]
+pub struct OuterCodeAssembler {
+    parity_frames: usize,
+    bytes_per_frame: usize,
+    total_frames: usize,
+    frames: Vec<Option<Vec<u8>>>,
+    last_index: Option<usize>,
+    erased: usize,
+}
+
+impl OuterCodeAssembler {
+    pub fn new(parity_frames: usize, bytes_per_frame: usize) -> Self {
+        Self {
+            parity_frames,
+            bytes_per_frame,
+            total_frames: 0,
+            frames: Vec::new(),
+            last_index: None,
+            erased: 0,
+        }
+    }
+
+    /// Frames of the pending message that failed the inner decode so far
+    pub fn erased_frames(&self) -> usize {
+        self.erased
+    }
+
+    /// Adds frame `index` of a `total`-frame message.
+    ///
+    /// Returns the recovered message once its last frame has been seen. A
+    /// frame from a new message (smaller index or different total) first
+    /// completes the pending one, which is then returned instead.
+    pub fn insert(
+        &mut self,
+        index: usize,
+        total: usize,
+        frame: Option<Vec<u8>>,
+    ) -> Option<std::result::Result<Vec<u8>, DecodingError>> {
+        let starts_new =
+            total != self.total_frames || self.last_index.is_some_and(|last| index <= last);
+        let pending = if starts_new && self.last_index.is_some() {
+            self.finish()
+        } else {
+            None
+        };
+        if starts_new {
+            self.total_frames = total;
+            self.frames = vec![None; total];
+        }
+
+        if index < self.total_frames {
+            self.erased += usize::from(frame.is_none());
+            self.frames[index] = frame;
+            self.last_index = Some(index);
+        }
+
+        if pending.is_some() {
+            return pending;
+        }
+        if index + 1 >= self.total_frames {
+            return self.finish();
+        }
+        None
+    }
+
+    /// Decodes the pending message, treating unseen frames as erasures.
+    pub fn finish(&mut self) -> Option<std::result::Result<Vec<u8>, DecodingError>> {
+        self.last_index?;
+        self.last_index = None;
+
+        let data_frames = self.total_frames.saturating_sub(self.parity_frames);
+        let result = match ReedSolomon::new(data_frames, self.parity_frames) {
+            Ok(rs) => rs.reconstruct_frames(&mut self.frames, self.bytes_per_frame),
+            Err(_) => Err(DecodingError::TooManyErasures {
+                erasures: self.total_frames,
+                correctable: self.parity_frames,
+            }),
+        };
+        self.frames.clear();
+        self.total_frames = 0;
+        self.erased = 0;
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_frames(count: usize, bytes: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|f| (0..bytes).map(|b| (f * 31 + b * 7 + 1) as u8).collect())
+            .collect()
+    }
+
+    #[test]
+    fn field_tables_are_consistent() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_div(1, a)), 1);
+        }
+        assert_eq!(gf_mul(0x02, 0x80), 0x1D);
+    }
+
+    #[test]
+    fn encoded_codeword_has_zero_syndromes() {
+        let rs = ReedSolomon::new(10, 4).unwrap();
+        let data: Vec<u8> = (0..10).map(|i| i * 17 + 3).collect();
+        let mut codeword = data.clone();
+        codeword.extend(rs.encode(&data));
+
+        for i in 0..4 {
+            assert_eq!(poly_eval(&codeword, gf_pow_alpha(i)), 0);
+        }
+        assert!(rs.decode_erasures(&mut codeword, &[]).is_ok());
+    }
+
+    #[test]
+    fn recovers_up_to_parity_lost_frames() {
+        let rs = ReedSolomon::new(6, 3).unwrap();
+        let data = data_frames(6, 16);
+        let parity = rs.encode_frames(&data, 16);
+
+        let mut frames: Vec<Option<Vec<u8>>> =
+            data.iter().chain(&parity).cloned().map(Some).collect();
+        frames[0] = None;
+        frames[4] = None;
+        frames[7] = None;
+
+        let message = rs.reconstruct_frames(&mut frames, 16).unwrap();
+        assert_eq!(message, data.concat());
+        assert_eq!(frames[7].as_ref(), Some(&parity[1]));
+    }
+
+    #[test]
+    fn too_many_lost_frames_is_reported() {
+        let rs = ReedSolomon::new(4, 2).unwrap();
+        let data = data_frames(4, 8);
+        let mut frames: Vec<Option<Vec<u8>>> = data
+            .iter()
+            .chain(&rs.encode_frames(&data, 8))
+            .cloned()
+            .map(Some)
+            .collect();
+        frames[1] = None;
+        frames[2] = None;
+        frames[5] = None;
+
+        assert!(matches!(
+            rs.reconstruct_frames(&mut frames, 8),
+            Err(DecodingError::TooManyErasures {
+                erasures: 3,
+                correctable: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn assembler_places_frames_by_counter() {
+        let rs = ReedSolomon::new(3, 2).unwrap();
+        let data = data_frames(3, 4);
+        let frames: Vec<Vec<u8>> = data
+            .iter()
+            .chain(&rs.encode_frames(&data, 4))
+            .cloned()
+            .collect();
+
+        let mut assembler = OuterCodeAssembler::new(2, 4);
+        // Frame 1 fails the inner decode, frame 3 never arrives
+        assert!(assembler.insert(0, 5, Some(frames[0].clone())).is_none());
+        assert!(assembler.insert(1, 5, None).is_none());
+        assert!(assembler.insert(2, 5, Some(frames[2].clone())).is_none());
+        let message = assembler.insert(4, 5, Some(frames[4].clone()));
+
+        assert_eq!(message.unwrap().unwrap(), data.concat());
+        assert_eq!(assembler.erased_frames(), 0);
+    }
+}
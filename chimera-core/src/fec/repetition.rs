@@ -0,0 +1,127 @@
+//! Repetition code with soft combining.
+//!
+//! The message is transmitted `factor` times back to back (`m m m ...`) rather
+//! than bit by bit, so a burst of channel errors hits different message bits
+//! in each copy. Decoding sums the LLRs of every copy of a bit. Codeword bits
+//! left over after `factor` copies are zero padding and are ignored.
+
+use crate::errors::{ConfigError, Result};
+use crate::ldpc::DecodeOutcome;
+
+use super::{codeword_too_short, outcome_from_codeword, FecCodec};
+
+/// Rate-1/`factor` repetition code sized to a frame codeword
+#[derive(Debug, Clone)]
+pub struct RepetitionCodec {
+    factor: usize,
+    message_bits: usize,
+    codeword_bits: usize,
+}
+
+impl RepetitionCodec {
+    /// Creates the code filling `codeword_bits` channel bits with `factor`
+    /// copies of `codeword_bits / factor` message bits.
+    pub fn new(codeword_bits: usize, factor: usize) -> Result<Self> {
+        if factor == 0 {
+            return Err(ConfigError::InvalidFrameLayout {
+                reason: "repetition factor must be at least 1".into(),
+            }
+            .into());
+        }
+        let message_bits = codeword_bits / factor;
+        if message_bits == 0 {
+            return Err(codeword_too_short("repetition", codeword_bits).into());
+        }
+
+        Ok(Self {
+            factor,
+            message_bits,
+            codeword_bits,
+        })
+    }
+
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+}
+
+impl FecCodec for RepetitionCodec {
+    fn name(&self) -> &'static str {
+        "repetition"
+    }
+
+    fn message_bits(&self) -> usize {
+        self.message_bits
+    }
+
+    fn codeword_bits(&self) -> usize {
+        self.codeword_bits
+    }
+
+    fn encode(&self, message: &[u8]) -> Vec<u8> {
+        let mut block = message[..self.message_bits.min(message.len())].to_vec();
+        block.resize(self.message_bits, 0);
+
+        let mut codeword: Vec<u8> = block
+            .iter()
+            .map(|&b| b & 1)
+            .cycle()
+            .take(self.message_bits * self.factor)
+            .collect();
+        codeword.resize(self.codeword_bits, 0);
+        codeword
+    }
+
+    fn decode_soft(&self, llrs: &[f64]) -> DecodeOutcome {
+        let mut combined = vec![0.0; self.message_bits];
+        for copy in
+            llrs[..(self.message_bits * self.factor).min(llrs.len())].chunks(self.message_bits)
+        {
+            for (sum, &llr) in combined.iter_mut().zip(copy) {
+                *sum += llr;
+            }
+        }
+
+        let message: Vec<u8> = combined.iter().map(|&llr| u8::from(llr < 0.0)).collect();
+        let codeword = self.encode(&message);
+        outcome_from_codeword(llrs, message, codeword)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_fill_codeword_with_padding() {
+        let codec = RepetitionCodec::new(10, 3).unwrap();
+        assert_eq!(codec.message_bits(), 3);
+        assert_eq!(codec.encode(&[1, 0, 1]), vec![1, 0, 1, 1, 0, 1, 1, 0, 1, 0]);
+        assert!(RepetitionCodec::new(10, 0).is_err());
+        assert!(RepetitionCodec::new(2, 3).is_err());
+    }
+
+    #[test]
+    fn majority_of_soft_copies_wins() {
+        let codec = RepetitionCodec::new(160, 3).unwrap();
+        let message: Vec<u8> = (0..53).map(|i| (i % 3 == 0) as u8).collect();
+        let codeword = codec.encode(&message);
+        let mut llrs: Vec<f64> = codeword
+            .iter()
+            .map(|&b| if b == 0 { 2.0 } else { -2.0 })
+            .collect();
+
+        // A burst wipes out the first copy of bits 0..10
+        for llr in &mut llrs[..10] {
+            *llr = -*llr;
+        }
+        // One confident wrong copy outweighs two weak correct ones
+        llrs[20] = -llrs[20] * 3.0;
+        llrs[20 + 53] *= 0.1;
+        llrs[20 + 106] *= 0.1;
+
+        let outcome = codec.decode_soft(&llrs);
+        assert_eq!(outcome.message_bits[..20], message[..20]);
+        assert_ne!(outcome.message_bits[20], message[20]);
+    }
+}
@@ -0,0 +1,122 @@
+//! Additive scrambling (data whitening) of the frame codeword.
+//!
+//! The transmitter XORs an LFSR sequence onto the interleaved codeword before
+//! it is written into the payload and ECC sections; the receiver flips the
+//! signs of the channel LLRs at the same positions before deinterleaving. The
+//! sequence depends only on the position within the frame.
+
+use crate::config::Scrambling;
+
+/// Whitening sequence for a codeword of `len()` bits
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scrambler {
+    /// Bits XORed onto the codeword; empty when scrambling is off
+    sequence: Vec<u8>,
+}
+
+impl Scrambler {
+    /// Builds the sequence selected by `scrambling` for `len` bits.
+    pub fn new(scrambling: &Scrambling, len: usize) -> Self {
+        let sequence = match *scrambling {
+            Scrambling::None => Vec::new(),
+            Scrambling::Lfsr7 { seed } | Scrambling::Lfsr15 { seed } => {
+                let (degree, tap) = scrambling.polynomial().unwrap_or_default();
+                lfsr_sequence(degree, tap, seed, len)
+            }
+        };
+
+        Self { sequence }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.sequence.is_empty()
+    }
+
+    /// Scrambles or descrambles codeword bits.
+    pub fn apply(&self, bits: &[u8]) -> Vec<u8> {
+        if !self.is_active() {
+            return bits.to_vec();
+        }
+        bits.iter().zip(&self.sequence).map(|(&bit, &chip)| bit ^ chip).collect()
+    }
+
+    /// Descrambles channel LLRs (positive favours 0) by flipping the sign of
+    /// every scrambled position.
+    pub fn apply_llrs(&self, llrs: &[f64]) -> Vec<f64> {
+        if !self.is_active() {
+            return llrs.to_vec();
+        }
+        llrs.iter()
+            .zip(&self.sequence)
+            .map(|(&llr, &chip)| if chip == 1 { -llr } else { llr })
+            .collect()
+    }
+}
+
+/// `len` output bits of the Fibonacci LFSR x^degree + x^tap + 1 started from `seed`
+fn lfsr_sequence(degree: u32, tap: u32, seed: u16, len: usize) -> Vec<u8> {
+    let mask = (1u32 << degree) - 1;
+    let mut state = u32::from(seed) & mask;
+    (0..len)
+        .map(|_| {
+            let bit = ((state >> (degree - 1)) ^ (state >> (tap - 1))) & 1;
+            state = ((state << 1) | bit) & mask;
+            bit as u8
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lfsr_sequences_have_maximal_period() {
+        for (scrambling, period) in [
+            (Scrambling::Lfsr7 { seed: 0x7F }, 127),
+            (Scrambling::Lfsr15 { seed: 0x1234 }, 32767),
+        ] {
+            let sequence = Scrambler::new(&scrambling, 2 * period).sequence;
+            assert_eq!(sequence[..period], sequence[period..]);
+            let shortest = (1..period).find(|&lag| sequence[..period] == sequence[lag..lag + period]);
+            assert_eq!(shortest, None, "{scrambling:?} repeats early");
+            // A maximal-length sequence is balanced up to one extra 1
+            assert_eq!(sequence[..period].iter().filter(|&&bit| bit == 1).count(), period.div_ceil(2));
+        }
+    }
+
+    #[test]
+    fn scrambling_round_trips_bits_and_llrs() {
+        let scrambler = Scrambler::new(&Scrambling::Lfsr7 { seed: 0x5B }, 96);
+        let bits: Vec<u8> = (0..96).map(|i| (i % 5 == 0) as u8).collect();
+        let scrambled = scrambler.apply(&bits);
+        assert_ne!(scrambled, bits);
+        assert_eq!(scrambler.apply(&scrambled), bits);
+
+        // Channel LLRs of the scrambled bits descramble to LLRs of the originals
+        let llrs: Vec<f64> = scrambled.iter().map(|&bit| if bit == 0 { 4.0 } else { -4.0 }).collect();
+        let descrambled = scrambler.apply_llrs(&llrs);
+        let hard: Vec<u8> = descrambled.iter().map(|&llr| (llr < 0.0) as u8).collect();
+        assert_eq!(hard, bits);
+    }
+
+    #[test]
+    fn all_zero_codeword_is_whitened() {
+        let scrambler = Scrambler::new(&Scrambling::Lfsr15 { seed: 0x4A80 }, 160);
+        let whitened = scrambler.apply(&[0; 160]);
+        let longest_run = whitened
+            .chunk_by(|a, b| a == b)
+            .map(<[u8]>::len)
+            .max()
+            .unwrap_or(0);
+        assert!(longest_run <= 15, "run of {longest_run} identical bits");
+    }
+
+    #[test]
+    fn no_scrambling_passes_bits_through() {
+        let scrambler = Scrambler::new(&Scrambling::None, 8);
+        assert!(!scrambler.is_active());
+        assert_eq!(scrambler.apply(&[0, 1, 1, 0]), [0, 1, 1, 0]);
+        assert_eq!(scrambler.apply_llrs(&[1.5, -2.0]), [1.5, -2.0]);
+    }
+}
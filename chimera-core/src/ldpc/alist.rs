@@ -0,0 +1,310 @@
+//! Text formats for exchanging parity-check matrices with other tools.
+//!
+//! Two formats are supported:
+//!
+//! * **alist** (MacKay): `n m`, the maximum column and row weights, the `n`
+//!   column weights, the `m` row weights, then one line per column listing
+//!   its 1-based row indices and one line per row listing its 1-based column
+//!   indices. Lists may be zero-padded to the maximum weight.
+//! * **sparse text**: a `rows cols` header followed by one `row col` pair
+//!   (0-based) per nonzero entry. Blank lines and `#` comments are ignored.
+
+use std::path::Path;
+
+use ndarray::Array2;
+
+use crate::errors::LdpcError;
+
+fn malformed(reason: impl Into<String>) -> LdpcError {
+    LdpcError::MatrixConstructionFailed {
+        reason: reason.into(),
+    }
+}
+
+/// Tokenizes `text` into unsigned integers, ignoring `#` comments.
+fn numbers(text: &str) -> Result<Vec<usize>, LdpcError> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or(""))
+        .flat_map(str::split_whitespace)
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| malformed(format!("invalid integer '{token}'")))
+        })
+        .collect()
+}
+
+/// Parses a parity-check matrix in alist format.
+///
+/// The column lists define the matrix; the row lists, when present, must
+/// describe the same entries.
+pub fn parse_alist(text: &str) -> Result<Array2<u8>, LdpcError> {
+    let values = numbers(text)?;
+    let mut next = {
+        let mut iter = values.into_iter();
+        move |what: &str| {
+            iter.next()
+                .ok_or_else(|| malformed(format!("alist ends before {what}")))
+        }
+    };
+
+    let cols = next("the column count")?;
+    let rows = next("the row count")?;
+    let max_col_weight = next("the maximum column weight")?;
+    let max_row_weight = next("the maximum row weight")?;
+    let col_weights = (0..cols)
+        .map(|_| next("the column weights"))
+        .collect::<Result<Vec<_>, _>>()?;
+    let row_weights = (0..rows)
+        .map(|_| next("the row weights"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut matrix = Array2::<u8>::zeros((rows, cols));
+    for (col, &weight) in col_weights.iter().enumerate() {
+        for slot in 0..max_col_weight {
+            let row = next("the column lists")?;
+            if slot >= weight {
+                continue;
+            }
+            if row == 0 || row > rows {
+                return Err(malformed(format!(
+                    "column {} lists row {row} of {rows}",
+                    col + 1
+                )));
+            }
+            matrix[(row - 1, col)] = 1;
+        }
+    }
+
+    for (row, &weight) in row_weights.iter().enumerate() {
+        for slot in 0..max_row_weight {
+            let Ok(col) = next("the row lists") else {
+                // Row lists are redundant; files that omit them are accepted
+                return if row == 0 && slot == 0 {
+                    Ok(matrix)
+                } else {
+                    Err(malformed("alist row lists are truncated"))
+                };
+            };
+            if slot >= weight {
+                continue;
+            }
+            if col == 0 || col > cols || matrix[(row, col - 1)] == 0 {
+                return Err(malformed(format!(
+                    "row {} lists column {col}, which its column list does not contain",
+                    row + 1
+                )));
+            }
+        }
+    }
+
+    Ok(matrix)
+}
+
+/// Serializes a parity-check matrix in alist format (lists zero-padded).
+pub fn to_alist(matrix: &Array2<u8>) -> String {
+    let (rows, cols) = matrix.dim();
+    let col_lists: Vec<Vec<usize>> = (0..cols)
+        .map(|col| {
+            (0..rows)
+                .filter(|&row| matrix[(row, col)] & 1 == 1)
+                .map(|row| row + 1)
+                .collect()
+        })
+        .collect();
+    let row_lists: Vec<Vec<usize>> = (0..rows)
+        .map(|row| {
+            (0..cols)
+                .filter(|&col| matrix[(row, col)] & 1 == 1)
+                .map(|col| col + 1)
+                .collect()
+        })
+        .collect();
+    let max_col_weight = col_lists.iter().map(Vec::len).max().unwrap_or(0);
+    let max_row_weight = row_lists.iter().map(Vec::len).max().unwrap_or(0);
+
+    let join = |values: &mut dyn Iterator<Item = usize>| {
+        values.map(|v| v.to_string()).collect::<Vec<_>>().join(" ")
+    };
+    let padded = |list: &Vec<usize>, width: usize| {
+        join(
+            &mut list
+                .iter()
+                .copied()
+                .chain(std::iter::repeat_n(0, width - list.len())),
+        )
+    };
+
+    let mut lines = vec![
+        format!("{cols} {rows}"),
+        format!("{max_col_weight} {max_row_weight}"),
+        join(&mut col_lists.iter().map(Vec::len)),
+        join(&mut row_lists.iter().map(Vec::len)),
+    ];
+    lines.extend(col_lists.iter().map(|list| padded(list, max_col_weight)));
+    lines.extend(row_lists.iter().map(|list| padded(list, max_row_weight)));
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+/// Parses a parity-check matrix from sparse `row col` text.
+pub fn parse_sparse_text(text: &str) -> Result<Array2<u8>, LdpcError> {
+    let values = numbers(text)?;
+    let [rows, cols, entries @ ..] = values.as_slice() else {
+        return Err(malformed("sparse matrix text needs a 'rows cols' header"));
+    };
+    let (pairs, []) = entries.as_chunks::<2>() else {
+        return Err(malformed("sparse matrix entry without a column index"));
+    };
+
+    let mut matrix = Array2::<u8>::zeros((*rows, *cols));
+    for &[row, col] in pairs {
+        if row >= *rows || col >= *cols {
+            return Err(malformed(format!(
+                "entry ({row}, {col}) outside {rows}x{cols} matrix"
+            )));
+        }
+        matrix[(row, col)] = 1;
+    }
+    Ok(matrix)
+}
+
+/// Reads a parity-check matrix from disk: alist for `.alist` files, sparse
+/// text otherwise.
+pub fn load_parity_check(path: &Path) -> Result<Array2<u8>, LdpcError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| malformed(format!("{}: {err}", path.display())))?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("alist") => parse_alist(&text),
+        _ => parse_sparse_text(&text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FrameLayout, LDPCConfig};
+    use crate::ldpc::LDPCSuite;
+
+    // Hamming (7,4) code
+    const HAMMING_ALIST: &str = "7 3\n3 4\n1 1 2 1 2 2 3\n4 4 4\n\
+        1 0 0\n2 0 0\n1 3 0\n3 0 0\n2 3 0\n1 2 0\n1 2 3\n\
+        1 3 6 7\n2 5 6 7\n3 4 5 7\n";
+
+    #[test]
+    fn parses_hamming_alist() {
+        let h = parse_alist(HAMMING_ALIST).unwrap();
+        assert_eq!(h.dim(), (3, 7));
+        assert_eq!(h.row(0).to_vec(), vec![1, 0, 1, 0, 0, 1, 1]);
+        assert_eq!(h.row(2).to_vec(), vec![0, 0, 1, 1, 1, 0, 1]);
+    }
+
+    #[test]
+    fn alist_round_trips_the_frame_code() {
+        let suite = LDPCSuite::new(&FrameLayout::default(), &LDPCConfig::default());
+        let text = to_alist(&suite.matrices.parity_check);
+        assert_eq!(parse_alist(&text).unwrap(), suite.matrices.parity_check);
+    }
+
+    #[test]
+    fn inconsistent_row_lists_are_rejected() {
+        let corrupted = HAMMING_ALIST.replace("1 3 6 7", "1 2 6 7");
+        assert!(matches!(
+            parse_alist(&corrupted),
+            Err(LdpcError::MatrixConstructionFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_sparse_text() {
+        let text = "# 2x3 example\n2 3\n0 0\n0 2\n1 1 # trailing comment\n";
+        let h = parse_sparse_text(text).unwrap();
+        assert_eq!(
+            h,
+            Array2::from_shape_vec((2, 3), vec![1, 0, 1, 0, 1, 0]).unwrap()
+        );
+        assert!(parse_sparse_text("2 3\n2 0\n").is_err());
+    }
+
+    #[test]
+    fn loaded_code_replaces_the_built_in_construction() {
+        let layout = FrameLayout::default();
+        let other = LDPCSuite::new(
+            &layout,
+            &LDPCConfig {
+                dv: 3,
+                dc: 15,
+                seed: Some(7),
+                ..LDPCConfig::default()
+            },
+        );
+        let path = std::env::temp_dir().join(format!("chimera-{}.alist", std::process::id()));
+        std::fs::write(&path, other.matrices.to_alist()).unwrap();
+
+        let cfg = LDPCConfig {
+            parity_check_file: Some(path.clone()),
+            ..LDPCConfig::default()
+        };
+        let loaded = LDPCSuite::from_config(&layout, &cfg);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap().matrices, other.matrices);
+    }
+
+    #[test]
+    fn mismatched_dimensions_are_rejected() {
+        let h = parse_alist(HAMMING_ALIST).unwrap();
+        assert!(matches!(
+            LDPCSuite::from_parity_check(&FrameLayout::default(), h),
+            Err(LdpcError::IncompatibleMatrixDimensions { rows: 3, cols: 7 })
+        ));
+    }
+
+    #[test]
+    fn singular_tail_is_reordered_and_dependent_rows_dropped() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/singular_tail.alist");
+        let h = load_parity_check(&path).unwrap();
+        // 8-bit codeword: 4 payload bits and 4 ECC bits
+        let layout = FrameLayout {
+            total_symbols: 52,
+            data_payload_symbols: 2,
+            ecc_symbols: 2,
+            ..FrameLayout::default()
+        };
+        let suite = LDPCSuite::from_config(
+            &layout,
+            &LDPCConfig {
+                parity_check_file: Some(path),
+                ..LDPCConfig::default()
+            },
+        )
+        .unwrap();
+
+        assert_ne!(suite.column_order, (0..8).collect::<Vec<_>>());
+        assert_eq!(suite.matrices.parity_check.nrows(), 4);
+        let reordered = h.select(ndarray::Axis(1), &suite.column_order);
+        for message in 0..16u8 {
+            let bits: Vec<u8> = (0..4).map(|i| (message >> i) & 1).collect();
+            let codeword = crate::encoder::encode_with_generator(&suite.matrices.generator, &bits);
+            assert_eq!(&codeword[..4], &bits[..]);
+            // Every check of the file, including the redundant one, holds
+            assert_eq!(crate::ldpc::syndrome_weight(&reordered, &codeword), 0);
+        }
+    }
+
+    #[test]
+    fn singular_parity_block_is_rejected() {
+        let layout = FrameLayout::default();
+        let mut h = LDPCSuite::new(&layout, &LDPCConfig::default())
+            .matrices
+            .parity_check;
+        // Duplicate a check so the parity block loses rank
+        let first = h.row(0).to_owned();
+        h.row_mut(1).assign(&first);
+        assert!(matches!(
+            LDPCSuite::from_parity_check(&layout, h),
+            Err(LdpcError::SingularMatrix)
+        ));
+    }
+}
@@ -0,0 +1,371 @@
+//! Asymptotic analysis of LDPC ensembles over the BPSK AWGN channel.
+//!
+//! * **Density evolution** under the Gaussian approximation (Chung,
+//!   Richardson & Urbanke, 2001) tracks the mean of the check-to-variable
+//!   messages. The BP threshold is the largest noise level at which that mean
+//!   grows without bound.
+//! * **EXIT charts** (ten Brink) track mutual information instead, using the
+//!   `J` function approximation of Brännström et al. Decoding converges when
+//!   the variable-node curve stays above the inverted check-node curve (the
+//!   tunnel is open).
+//!
+//! Degree distributions are in the edge perspective: `λ_i` (`ρ_j`) is the
+//! fraction of edges attached to variable (check) nodes of degree `i` (`j`).
+//! Channel noise is the standard deviation `σ` of unit-energy BPSK symbols.
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::LdpcError;
+
+/// Edge-perspective degree distribution pair of an LDPC ensemble
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DegreeDistribution {
+    /// `(degree, λ)` pairs for variable nodes
+    pub variable: Vec<(usize, f64)>,
+    /// `(degree, ρ)` pairs for check nodes
+    pub check: Vec<(usize, f64)>,
+}
+
+impl DegreeDistribution {
+    /// Ensemble with every variable node of degree `dv` and every check node
+    /// of degree `dc`.
+    pub fn regular(dv: usize, dc: usize) -> Self {
+        Self {
+            variable: vec![(dv, 1.0)],
+            check: vec![(dc, 1.0)],
+        }
+    }
+
+    /// Edge-perspective distribution of a concrete parity-check matrix.
+    ///
+    /// Degree-1 variable nodes (the end of a staircase parity part) are left
+    /// out: their messages never improve, so they would cap the message mean
+    /// and hide the threshold behind the small error floor they add.
+    pub fn from_parity_check(parity_check: &Array2<u8>) -> Result<Self, LdpcError> {
+        let distribution = |degrees: Vec<usize>| -> Vec<(usize, f64)> {
+            let mut sockets = std::collections::BTreeMap::new();
+            for degree in degrees {
+                *sockets.entry(degree).or_insert(0usize) += degree;
+            }
+            let edges: usize = sockets.values().sum();
+            sockets
+                .into_iter()
+                .map(|(degree, count)| (degree, count as f64 / edges as f64))
+                .collect()
+        };
+        let weight = |line: ndarray::ArrayView1<u8>| line.iter().filter(|&&b| b & 1 == 1).count();
+
+        let variable = distribution(
+            parity_check
+                .columns()
+                .into_iter()
+                .map(weight)
+                .filter(|&d| d > 1)
+                .collect(),
+        );
+        let check = distribution(
+            parity_check
+                .rows()
+                .into_iter()
+                .map(weight)
+                .filter(|&d| d > 0)
+                .collect(),
+        );
+        if variable.is_empty() || check.is_empty() {
+            return Err(LdpcError::MatrixConstructionFailed {
+                reason: "parity-check matrix has no variable nodes of degree 2 or more".into(),
+            });
+        }
+
+        Ok(Self { variable, check })
+    }
+
+    /// Design rate `1 - ∫ρ / ∫λ`
+    pub fn design_rate(&self) -> f64 {
+        let integral = |pairs: &[(usize, f64)]| {
+            pairs
+                .iter()
+                .map(|&(degree, fraction)| fraction / degree as f64)
+                .sum::<f64>()
+        };
+        1.0 - integral(&self.check) / integral(&self.variable)
+    }
+
+    fn validate(&self) -> Result<(), LdpcError> {
+        let valid = |pairs: &[(usize, f64)]| {
+            !pairs.is_empty()
+                && pairs.iter().all(|&(degree, fraction)| degree > 0 && fraction >= 0.0)
+                && (pairs.iter().map(|&(_, fraction)| fraction).sum::<f64>() - 1.0).abs() < 1e-6
+        };
+        if valid(&self.variable) && valid(&self.check) {
+            Ok(())
+        } else {
+            Err(LdpcError::MatrixConstructionFailed {
+                reason: "degree fractions must be non-negative and sum to 1".into(),
+            })
+        }
+    }
+}
+
+/// Chung's `φ(x) = 1 - E[tanh(u/2)]` for `u ~ N(x, 2x)`
+fn phi(x: f64) -> f64 {
+    if x <= 0.0 {
+        1.0
+    } else if x < 10.0 {
+        (-0.4527 * x.powf(0.86) + 0.0218).exp()
+    } else {
+        (std::f64::consts::PI / x).sqrt() * (-x / 4.0).exp() * (1.0 - 10.0 / (7.0 * x))
+    }
+}
+
+/// Mean above which messages are treated as error-free
+const DE_CONVERGED_MEAN: f64 = 300.0;
+
+/// Inverse of [`phi`] by bisection (φ is decreasing)
+fn phi_inverse(y: f64) -> f64 {
+    if y >= 1.0 {
+        return 0.0;
+    }
+    let (mut lo, mut hi) = (0.0, 2.0 * DE_CONVERGED_MEAN);
+    if y <= phi(hi) {
+        return hi;
+    }
+    for _ in 0..80 {
+        let mid = 0.5 * (lo + hi);
+        if phi(mid) > y {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Runs Gaussian-approximation density evolution at noise `sigma` and
+/// returns the number of iterations until the check-to-variable mean
+/// diverges, or `None` if it stalls or `max_iterations` is reached.
+pub fn density_evolution(
+    distribution: &DegreeDistribution,
+    sigma: f64,
+    max_iterations: usize,
+) -> Option<usize> {
+    let channel_mean = 2.0 / (sigma * sigma);
+    let mut check_mean = 0.0;
+
+    for iteration in 1..=max_iterations {
+        let variable_phi: f64 = distribution
+            .variable
+            .iter()
+            .map(|&(degree, lambda)| lambda * phi(channel_mean + (degree - 1) as f64 * check_mean))
+            .sum();
+        let next: f64 = distribution
+            .check
+            .iter()
+            .map(|&(degree, rho)| {
+                rho * phi_inverse(1.0 - (1.0 - variable_phi).powi(degree as i32 - 1))
+            })
+            .sum();
+
+        if next >= DE_CONVERGED_MEAN {
+            return Some(iteration);
+        }
+        if next - check_mean < 1e-12 {
+            return None;
+        }
+        check_mean = next;
+    }
+    None
+}
+
+/// Largest noise `σ` at which Gaussian-approximation density evolution
+/// converges, found by bisection to within `1e-4`.
+pub fn ga_threshold(distribution: &DegreeDistribution) -> Result<f64, LdpcError> {
+    distribution.validate()?;
+
+    const MAX_ITERATIONS: usize = 20_000;
+    let (mut lo, mut hi) = (0.05, 5.0);
+    if density_evolution(distribution, lo, MAX_ITERATIONS).is_none() {
+        return Ok(0.0);
+    }
+    while hi - lo > 1e-4 {
+        let mid = 0.5 * (lo + hi);
+        if density_evolution(distribution, mid, MAX_ITERATIONS).is_some() {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(lo)
+}
+
+/// Eb/N0 in dB of BPSK noise `sigma` at code rate `rate`
+pub fn sigma_to_ebn0_db(sigma: f64, rate: f64) -> f64 {
+    10.0 * (1.0 / (2.0 * rate * sigma * sigma)).log10()
+}
+
+const J_H1: f64 = 0.3073;
+const J_H2: f64 = 0.8935;
+const J_H3: f64 = 1.1064;
+
+/// Mutual information between a bit and its LLR `~ N(±s²/2, s²)`
+pub fn j_function(s: f64) -> f64 {
+    if s <= 0.0 {
+        return 0.0;
+    }
+    (1.0 - 2f64.powf(-J_H1 * s.powf(2.0 * J_H2))).powf(J_H3)
+}
+
+/// Inverse of [`j_function`]
+pub fn j_inverse(mutual_information: f64) -> f64 {
+    let info = mutual_information.clamp(0.0, 1.0 - 1e-12);
+    if info <= 0.0 {
+        return 0.0;
+    }
+    (-(1.0 - info.powf(1.0 / J_H3)).log2() / J_H1).powf(1.0 / (2.0 * J_H2))
+}
+
+/// One a-priori information level of an EXIT chart
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ExitPoint {
+    /// A-priori mutual information `I_A`
+    pub a_priori: f64,
+    /// Extrinsic information of the variable nodes, `I_E,V(I_A)`
+    pub variable: f64,
+    /// Extrinsic information of the check nodes, `I_E,C(I_A)`
+    pub check: f64,
+}
+
+/// EXIT curves of an ensemble at one noise level
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExitChart {
+    pub sigma: f64,
+    pub points: Vec<ExitPoint>,
+}
+
+impl ExitChart {
+    /// Whether the variable-node curve lies above the inverted check-node
+    /// curve everywhere, so iterative decoding can reach `I = 1`.
+    pub fn tunnel_open(&self, distribution: &DegreeDistribution) -> bool {
+        self.points
+            .iter()
+            .filter(|point| point.a_priori < 1.0 - 1e-9)
+            .all(|point| {
+                // Variable output fed back as check input must beat I_A
+                check_extrinsic(distribution, point.variable) > point.a_priori
+            })
+    }
+
+    /// Curves as CSV with an `a_priori,variable,check` header
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("a_priori,variable,check\n");
+        for point in &self.points {
+            csv.push_str(&format!(
+                "{:.6},{:.6},{:.6}\n",
+                point.a_priori, point.variable, point.check
+            ));
+        }
+        csv
+    }
+}
+
+fn variable_extrinsic(distribution: &DegreeDistribution, sigma: f64, a_priori: f64) -> f64 {
+    let channel = 2.0 / sigma;
+    let incoming = j_inverse(a_priori);
+    distribution
+        .variable
+        .iter()
+        .map(|&(degree, lambda)| {
+            let s = ((degree - 1) as f64 * incoming * incoming + channel * channel).sqrt();
+            lambda * j_function(s)
+        })
+        .sum()
+}
+
+fn check_extrinsic(distribution: &DegreeDistribution, a_priori: f64) -> f64 {
+    let incoming = j_inverse(1.0 - a_priori);
+    1.0 - distribution
+        .check
+        .iter()
+        .map(|&(degree, rho)| rho * j_function(((degree - 1) as f64).sqrt() * incoming))
+        .sum::<f64>()
+}
+
+/// Evaluates both EXIT curves at `points` evenly spaced a-priori levels in
+/// `[0, 1]` for channel noise `sigma`.
+pub fn exit_chart(
+    distribution: &DegreeDistribution,
+    sigma: f64,
+    points: usize,
+) -> Result<ExitChart, LdpcError> {
+    distribution.validate()?;
+    let steps = points.max(2) - 1;
+
+    Ok(ExitChart {
+        sigma,
+        points: (0..=steps)
+            .map(|step| {
+                let a_priori = step as f64 / steps as f64;
+                ExitPoint {
+                    a_priori,
+                    variable: variable_extrinsic(distribution, sigma, a_priori),
+                    check: check_extrinsic(distribution, a_priori),
+                }
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FrameLayout, LDPCConfig};
+    use crate::ldpc::LDPCSuite;
+
+    #[test]
+    fn regular_3_6_threshold_matches_the_literature() {
+        let ensemble = DegreeDistribution::regular(3, 6);
+        assert!((ensemble.design_rate() - 0.5).abs() < 1e-12);
+
+        // Gaussian approximation: 0.8747 (exact density evolution: 0.8809)
+        let threshold = ga_threshold(&ensemble).unwrap();
+        assert!((threshold - 0.8747).abs() < 0.005, "threshold {threshold}");
+        assert!((sigma_to_ebn0_db(threshold, 0.5) - 1.16).abs() < 0.05);
+    }
+
+    #[test]
+    fn exit_tunnel_closes_near_the_threshold() {
+        let ensemble = DegreeDistribution::regular(3, 6);
+        let below = exit_chart(&ensemble, 0.82, 101).unwrap();
+        let above = exit_chart(&ensemble, 0.93, 101).unwrap();
+        assert!(below.tunnel_open(&ensemble));
+        assert!(!above.tunnel_open(&ensemble));
+
+        assert_eq!(below.points.len(), 101);
+        assert!(below.to_csv().starts_with("a_priori,variable,check\n0.000000,"));
+        for pair in below.points.windows(2) {
+            assert!(pair[1].variable >= pair[0].variable);
+            assert!(pair[1].check >= pair[0].check);
+        }
+    }
+
+    #[test]
+    fn j_inverse_inverts_j() {
+        for s in [0.1, 0.5, 1.0, 2.0, 5.0] {
+            assert!((j_inverse(j_function(s)) - s).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn distribution_of_the_frame_code() {
+        let suite = LDPCSuite::new(&FrameLayout::default(), &LDPCConfig::default());
+        let ensemble = DegreeDistribution::from_parity_check(&suite.matrices.parity_check).unwrap();
+        let rate = suite.matrices.message_bits as f64 / suite.matrices.codeword_bits as f64;
+        assert!((ensemble.design_rate() - rate).abs() < 0.01);
+        assert!(ensemble.variable.iter().all(|&(degree, _)| degree >= 2));
+
+        // Mostly (2, 10): bounded by the (2, 10) stability limit of 0.48
+        let threshold = ga_threshold(&ensemble).unwrap();
+        assert!(threshold > 0.35 && threshold < 0.48, "threshold {threshold}");
+    }
+}
@@ -0,0 +1,452 @@
+//! Iterative message-passing (belief propagation) decoding.
+//!
+//! Messages flow along the edges of the Tanner graph defined by the
+//! parity-check matrix. With the flooding schedule every check node is updated
+//! from the current variable messages, then every variable node from the new
+//! check messages; with the layered schedule each check is updated in turn
+//! and immediately refreshes the posteriors of its variables, so later checks
+//! in the same iteration already see the new information. After each
+//! iteration the posterior LLRs are sliced and the syndrome checked, so
+//! decoding can stop as soon as a valid codeword appears.
+//!
+//! LLRs use the convention `ln(P(bit = 0) / P(bit = 1))`: positive values
+//! favour 0, negative values favour 1.
+
+use ndarray::Array2;
+
+use crate::config::{BeliefPropagationAlgorithm, DecodingSchedule, LDPCDecoderConfig};
+use crate::errors::LdpcError;
+
+/// Largest magnitude fed through `atanh` in the sum-product check update.
+const TANH_CLAMP: f64 = 1.0 - 1e-12;
+
+/// Result of a belief-propagation decode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeliefPropagationOutcome {
+    /// Hard decision on the posterior LLRs (one bit per codeword position)
+    pub codeword: Vec<u8>,
+    /// Posterior LLRs after the last iteration
+    pub posterior_llrs: Vec<f64>,
+    /// Number of iterations performed (0 if the channel decision was already valid)
+    pub iterations: usize,
+    /// Whether `codeword` satisfies every parity check
+    pub converged: bool,
+}
+
+/// Belief-propagation decoder bound to one parity-check matrix.
+///
+/// The Tanner graph is flattened into edge lists once at construction, so a
+/// decoder can be reused for every frame of a stream.
+#[derive(Debug, Clone)]
+pub struct BeliefPropagationDecoder {
+    config: LDPCDecoderConfig,
+    codeword_bits: usize,
+    /// Variable index of each edge, edges grouped by check node
+    edge_vars: Vec<usize>,
+    /// `check_offsets[c]..check_offsets[c + 1]` are the edges of check `c`
+    check_offsets: Vec<usize>,
+    /// Edge indices incident to each variable node
+    var_edges: Vec<Vec<usize>>,
+}
+
+impl BeliefPropagationDecoder {
+    /// Builds the Tanner graph of `parity_check` and stores the decoder settings.
+    pub fn new(parity_check: &Array2<u8>, config: &LDPCDecoderConfig) -> Self {
+        let codeword_bits = parity_check.ncols();
+        let mut edge_vars = Vec::new();
+        let mut check_offsets = Vec::with_capacity(parity_check.nrows() + 1);
+        let mut var_edges = vec![Vec::new(); codeword_bits];
+
+        check_offsets.push(0);
+        for row in parity_check.outer_iter() {
+            for (var, &bit) in row.iter().enumerate() {
+                if bit & 1 == 1 {
+                    var_edges[var].push(edge_vars.len());
+                    edge_vars.push(var);
+                }
+            }
+            check_offsets.push(edge_vars.len());
+        }
+
+        Self {
+            config: config.clone(),
+            codeword_bits,
+            edge_vars,
+            check_offsets,
+            var_edges,
+        }
+    }
+
+    /// Decoder settings in use.
+    pub fn config(&self) -> &LDPCDecoderConfig {
+        &self.config
+    }
+
+    /// Runs belief propagation on the channel LLRs and returns the best
+    /// estimate, whether or not it converged.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `channel_llrs.len()` differs from the code length.
+    pub fn decode(&self, channel_llrs: &[f64]) -> BeliefPropagationOutcome {
+        debug_assert_eq!(channel_llrs.len(), self.codeword_bits);
+
+        let mut posterior = channel_llrs.to_vec();
+        let mut codeword = hard_decision(&posterior);
+        let mut converged = self.syndrome_is_zero(&codeword);
+
+        if converged && self.config.early_stop {
+            return BeliefPropagationOutcome {
+                codeword,
+                posterior_llrs: posterior,
+                iterations: 0,
+                converged,
+            };
+        }
+
+        let mut var_to_check: Vec<f64> = self.edge_vars.iter().map(|&v| channel_llrs[v]).collect();
+        let mut check_to_var = vec![0.0; self.edge_vars.len()];
+        let mut iterations = 0;
+
+        while iterations < self.config.max_iterations {
+            iterations += 1;
+
+            match self.config.schedule {
+                DecodingSchedule::Flooding => {
+                    for check in 0..self.check_offsets.len() - 1 {
+                        let edges = self.check_offsets[check]..self.check_offsets[check + 1];
+                        self.update_check(&var_to_check[edges.clone()], &mut check_to_var[edges]);
+                    }
+
+                    for (var, edges) in self.var_edges.iter().enumerate() {
+                        let total =
+                            channel_llrs[var] + edges.iter().map(|&e| check_to_var[e]).sum::<f64>();
+                        posterior[var] = total;
+                        for &edge in edges {
+                            var_to_check[edge] = total - check_to_var[edge];
+                        }
+                    }
+                }
+                DecodingSchedule::Layered => {
+                    for check in 0..self.check_offsets.len() - 1 {
+                        let edges = self.check_offsets[check]..self.check_offsets[check + 1];
+                        for edge in edges.clone() {
+                            var_to_check[edge] = posterior[self.edge_vars[edge]] - check_to_var[edge];
+                        }
+                        self.update_check(&var_to_check[edges.clone()], &mut check_to_var[edges.clone()]);
+                        for edge in edges {
+                            posterior[self.edge_vars[edge]] = var_to_check[edge] + check_to_var[edge];
+                        }
+                    }
+                }
+            }
+
+            codeword = hard_decision(&posterior);
+            converged = self.syndrome_is_zero(&codeword);
+            if converged && self.config.early_stop {
+                break;
+            }
+        }
+
+        BeliefPropagationOutcome {
+            codeword,
+            posterior_llrs: posterior,
+            iterations,
+            converged,
+        }
+    }
+
+    /// Like [`decode`](Self::decode), but returns
+    /// [`LdpcError::MaxIterationsExceeded`] when the iteration budget runs out
+    /// without reaching a valid codeword.
+    pub fn decode_checked(
+        &self,
+        channel_llrs: &[f64],
+    ) -> Result<BeliefPropagationOutcome, LdpcError> {
+        let outcome = self.decode(channel_llrs);
+        if outcome.converged {
+            Ok(outcome)
+        } else {
+            Err(LdpcError::MaxIterationsExceeded {
+                max_iter: self.config.max_iterations,
+            })
+        }
+    }
+
+    /// Computes the extrinsic check-to-variable messages for one check node.
+    fn update_check(&self, incoming: &[f64], outgoing: &mut [f64]) {
+        match self.config.algorithm {
+            BeliefPropagationAlgorithm::SumProduct => sum_product_update(incoming, outgoing),
+            BeliefPropagationAlgorithm::NormalizedMinSum { alpha } => {
+                min_sum_update(incoming, outgoing, |magnitude| alpha * magnitude)
+            }
+            BeliefPropagationAlgorithm::OffsetMinSum { beta } => {
+                min_sum_update(incoming, outgoing, |magnitude| (magnitude - beta).max(0.0))
+            }
+        }
+    }
+
+    /// Returns true if `codeword` satisfies every parity check.
+    pub fn syndrome_is_zero(&self, codeword: &[u8]) -> bool {
+        self.check_offsets.windows(2).all(|range| {
+            self.edge_vars[range[0]..range[1]]
+                .iter()
+                .fold(0u8, |parity, &var| parity ^ (codeword[var] & 1))
+                == 0
+        })
+    }
+}
+
+fn hard_decision(llrs: &[f64]) -> Vec<u8> {
+    llrs.iter().map(|&llr| u8::from(llr < 0.0)).collect()
+}
+
+/// Exact tanh-rule update using prefix/suffix products to exclude each edge.
+fn sum_product_update(incoming: &[f64], outgoing: &mut [f64]) {
+    let tanhs: Vec<f64> = incoming.iter().map(|&llr| (llr / 2.0).tanh()).collect();
+
+    let mut prefix = 1.0;
+    for (out, &t) in outgoing.iter_mut().zip(&tanhs) {
+        *out = prefix;
+        prefix *= t;
+    }
+
+    let mut suffix = 1.0;
+    for (out, &t) in outgoing.iter_mut().zip(&tanhs).rev() {
+        let product = (*out * suffix).clamp(-TANH_CLAMP, TANH_CLAMP);
+        *out = 2.0 * product.atanh();
+        suffix *= t;
+    }
+}
+
+/// Min-sum update: sign product times the smallest other magnitude, with the
+/// magnitude corrected by `correct` (normalisation or offset).
+fn min_sum_update(incoming: &[f64], outgoing: &mut [f64], correct: impl Fn(f64) -> f64) {
+    let mut min1 = f64::INFINITY;
+    let mut min2 = f64::INFINITY;
+    let mut min_idx = 0;
+    let mut negative = false;
+
+    for (idx, &llr) in incoming.iter().enumerate() {
+        let magnitude = llr.abs();
+        negative ^= llr < 0.0;
+        if magnitude < min1 {
+            min2 = min1;
+            min1 = magnitude;
+            min_idx = idx;
+        } else if magnitude < min2 {
+            min2 = magnitude;
+        }
+    }
+
+    for (idx, (out, &llr)) in outgoing.iter_mut().zip(incoming).enumerate() {
+        let magnitude = if idx == min_idx { min2 } else { min1 };
+        let magnitude = if magnitude.is_finite() {
+            correct(magnitude)
+        } else {
+            0.0
+        };
+        let sign_negative = negative ^ (llr < 0.0);
+        *out = if sign_negative { -magnitude } else { magnitude };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FrameLayout, LDPCConfig};
+    use crate::ldpc::LDPCSuite;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::{Distribution, Normal};
+
+    const ALGORITHMS: [BeliefPropagationAlgorithm; 3] = [
+        BeliefPropagationAlgorithm::SumProduct,
+        BeliefPropagationAlgorithm::NormalizedMinSum { alpha: 0.75 },
+        BeliefPropagationAlgorithm::OffsetMinSum { beta: 0.5 },
+    ];
+
+    fn random_codeword(suite: &LDPCSuite, rng: &mut StdRng) -> Vec<u8> {
+        let generator = &suite.matrices.generator;
+        let mut codeword = vec![0u8; generator.ncols()];
+        for row in generator.outer_iter() {
+            if rng.gen_bool(0.5) {
+                for (bit, &g) in codeword.iter_mut().zip(row.iter()) {
+                    *bit ^= g;
+                }
+            }
+        }
+        codeword
+    }
+
+    fn bpsk_llrs(codeword: &[u8], magnitude: f64) -> Vec<f64> {
+        codeword
+            .iter()
+            .map(|&bit| if bit == 0 { magnitude } else { -magnitude })
+            .collect()
+    }
+
+    fn decoder(algorithm: BeliefPropagationAlgorithm) -> (LDPCSuite, BeliefPropagationDecoder) {
+        let suite = LDPCSuite::new(&FrameLayout::default(), &LDPCConfig::default());
+        let config = LDPCDecoderConfig {
+            algorithm,
+            ..LDPCDecoderConfig::default()
+        };
+        let decoder = BeliefPropagationDecoder::new(&suite.matrices.parity_check, &config);
+        (suite, decoder)
+    }
+
+    #[test]
+    fn clean_codeword_stops_before_first_iteration() {
+        let (suite, decoder) = decoder(BeliefPropagationAlgorithm::SumProduct);
+        let mut rng = StdRng::seed_from_u64(1);
+        let codeword = random_codeword(&suite, &mut rng);
+
+        let outcome = decoder.decode(&bpsk_llrs(&codeword, 4.0));
+        assert!(outcome.converged);
+        assert_eq!(outcome.iterations, 0);
+        assert_eq!(outcome.codeword, codeword);
+    }
+
+    #[test]
+    fn every_algorithm_corrects_flipped_bits() {
+        for algorithm in ALGORITHMS {
+            let (suite, decoder) = decoder(algorithm);
+            let mut rng = StdRng::seed_from_u64(2);
+            let codeword = random_codeword(&suite, &mut rng);
+
+            let mut llrs = bpsk_llrs(&codeword, 3.0);
+            for idx in [5, 77, 150] {
+                llrs[idx] = -llrs[idx] / 3.0;
+            }
+
+            let outcome = decoder.decode(&llrs);
+            assert!(outcome.converged, "{algorithm:?} did not converge");
+            assert!(outcome.iterations > 0);
+            assert_eq!(outcome.codeword, codeword, "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn decoding_reduces_errors_on_awgn() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let sigma = 0.55;
+        let noise = Normal::new(0.0, sigma).unwrap();
+
+        for algorithm in ALGORITHMS {
+            let (suite, decoder) = decoder(algorithm);
+            let mut channel_errors = 0;
+            let mut decoded_errors = 0;
+
+            for _ in 0..50 {
+                let codeword = random_codeword(&suite, &mut rng);
+                let llrs: Vec<f64> = codeword
+                    .iter()
+                    .map(|&bit| {
+                        let symbol = if bit == 0 { 1.0 } else { -1.0 };
+                        2.0 * (symbol + noise.sample(&mut rng)) / (sigma * sigma)
+                    })
+                    .collect();
+
+                channel_errors += hard_decision(&llrs)
+                    .iter()
+                    .zip(&codeword)
+                    .filter(|(a, b)| a != b)
+                    .count();
+                decoded_errors += decoder
+                    .decode(&llrs)
+                    .codeword
+                    .iter()
+                    .zip(&codeword)
+                    .filter(|(a, b)| a != b)
+                    .count();
+            }
+
+            assert!(channel_errors > 0);
+            assert!(
+                decoded_errors * 4 < channel_errors,
+                "{algorithm:?}: {decoded_errors} errors after decoding, {channel_errors} before"
+            );
+        }
+    }
+
+    #[test]
+    fn layered_schedule_converges_in_fewer_iterations() {
+        let suite = LDPCSuite::new(&FrameLayout::default(), &LDPCConfig::default());
+        let decoder = |schedule| {
+            let config = LDPCDecoderConfig {
+                schedule,
+                ..LDPCDecoderConfig::default()
+            };
+            BeliefPropagationDecoder::new(&suite.matrices.parity_check, &config)
+        };
+        let flooding = decoder(DecodingSchedule::Flooding);
+        let layered = decoder(DecodingSchedule::Layered);
+
+        let mut rng = StdRng::seed_from_u64(6);
+        let sigma = 0.55;
+        let noise = Normal::new(0.0, sigma).unwrap();
+        let (mut flooding_iterations, mut layered_iterations) = (0, 0);
+        for _ in 0..30 {
+            let codeword = random_codeword(&suite, &mut rng);
+            let llrs: Vec<f64> = codeword
+                .iter()
+                .map(|&bit| {
+                    let symbol = if bit == 0 { 1.0 } else { -1.0 };
+                    2.0 * (symbol + noise.sample(&mut rng)) / (sigma * sigma)
+                })
+                .collect();
+
+            // Compare speed on frames both schedules decode
+            let (by_layer, by_flood) = (layered.decode(&llrs), flooding.decode(&llrs));
+            if by_layer.converged && by_flood.converged {
+                layered_iterations += by_layer.iterations;
+                flooding_iterations += by_flood.iterations;
+            }
+        }
+
+        assert!(
+            layered_iterations * 4 < flooding_iterations * 3,
+            "layered {layered_iterations}, flooding {flooding_iterations} iterations"
+        );
+    }
+
+    #[test]
+    fn garbage_input_reports_max_iterations() {
+        let suite = LDPCSuite::new(&FrameLayout::default(), &LDPCConfig::default());
+        let config = LDPCDecoderConfig {
+            max_iterations: 5,
+            ..LDPCDecoderConfig::default()
+        };
+        let decoder = BeliefPropagationDecoder::new(&suite.matrices.parity_check, &config);
+
+        let mut rng = StdRng::seed_from_u64(4);
+        let llrs: Vec<f64> = (0..suite.matrices.codeword_bits)
+            .map(|_| rng.gen_range(-0.5..0.5))
+            .collect();
+
+        let outcome = decoder.decode(&llrs);
+        assert!(!outcome.converged);
+        assert_eq!(outcome.iterations, 5);
+        assert!(matches!(
+            decoder.decode_checked(&llrs),
+            Err(LdpcError::MaxIterationsExceeded { max_iter: 5 })
+        ));
+    }
+
+    #[test]
+    fn early_stop_can_be_disabled() {
+        let suite = LDPCSuite::new(&FrameLayout::default(), &LDPCConfig::default());
+        let config = LDPCDecoderConfig {
+            max_iterations: 8,
+            early_stop: false,
+            ..LDPCDecoderConfig::default()
+        };
+        let decoder = BeliefPropagationDecoder::new(&suite.matrices.parity_check, &config);
+        let codeword = random_codeword(&suite, &mut StdRng::seed_from_u64(5));
+
+        let outcome = decoder.decode(&bpsk_llrs(&codeword, 4.0));
+        assert!(outcome.converged);
+        assert_eq!(outcome.iterations, 8);
+        assert_eq!(outcome.codeword, codeword);
+    }
+}
@@ -0,0 +1,406 @@
+//! Parity-check matrix construction for the frame LDPC code.
+//!
+//! The code is built with progressive edge growth (PEG) over a dual-diagonal
+//! ("staircase") parity part:
+//!
+//! ```text
+//! H = [ H_info | H_parity ]      H_parity = 1 . . .
+//!                                           1 1 . .
+//!                                           . 1 1 .
+//!                                           . . 1 1
+//! ```
+//!
+//! `H_parity` is lower-triangular with a unit diagonal, so it is always
+//! invertible over GF(2). That guarantees full rank (the message length of the
+//! code is exactly the payload size of the frame layout) and lets us derive a
+//! systematic generator `G = [I | P]` directly from `H`.
+//!
+//! Each information column receives `dv` edges placed by PEG: every new edge
+//! goes to the least-loaded check node that is farthest away in the current
+//! Tanner graph, which maximises the local girth and avoids the 4-cycles that
+//! cripple belief propagation on short blocks.
+
+use ndarray::Array2;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::config::LDPCConfig;
+
+/// Builds a parity-check matrix with `codeword_bits` columns and
+/// `parity_bits` rows from the variable/check degrees in `cfg`.
+///
+/// `cfg.dv` is the column weight of every information bit (clamped to
+/// `1..=parity_bits`); parity bits have weight 2 (weight 1 for the last one)
+/// from the staircase. `cfg.dc` is the target check-node degree: PEG fills
+/// checks below `dc` first and only exceeds it when the layout's rate cannot
+/// be reached with a `(dv, dc)` ensemble, in which case the check degrees stay
+/// as balanced as possible.
+///
+/// The construction is deterministic for a given `cfg.seed`; `None` draws the
+/// tie-breaking seed from OS entropy.
+pub fn build_parity_check(
+    codeword_bits: usize,
+    parity_bits: usize,
+    cfg: &LDPCConfig,
+) -> Array2<u8> {
+    let message_bits = codeword_bits - parity_bits;
+    let mut graph = TannerGraph::new(codeword_bits, parity_bits);
+
+    // Staircase parity part.
+    for row in 0..parity_bits {
+        graph.connect(message_bits + row, row);
+        if row > 0 {
+            graph.connect(message_bits + row - 1, row);
+        }
+    }
+
+    if parity_bits > 0 {
+        let mut rng = match cfg.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let dv = cfg.dv.clamp(1, parity_bits);
+        let dc = cfg.dc.max(1);
+
+        for var in 0..message_bits {
+            for edge in 0..dv {
+                let candidates = if edge == 0 {
+                    (0..parity_bits).collect()
+                } else {
+                    graph.farthest_checks(var)
+                };
+                let check = graph.pick_check(var, &candidates, dc, &mut rng);
+                graph.connect(var, check);
+            }
+        }
+    }
+
+    graph.to_dense()
+}
+
+/// Systematic form of a parity-check matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystematicForm {
+    /// Linearly independent rows of `H`, in their original order, with the
+    /// columns reordered by `column_order`
+    pub parity_check: Array2<u8>,
+    /// Generator `G = [I_k | P]` of `parity_check`
+    pub generator: Array2<u8>,
+    /// `column_order[i]` is the column of `H` moved to position `i`; the
+    /// identity when the last `rank` columns of `H` are already invertible
+    pub column_order: Vec<usize>,
+}
+
+impl SystematicForm {
+    /// Dimension `k = n - rank(H)` of the code
+    pub fn message_bits(&self) -> usize {
+        self.generator.nrows()
+    }
+}
+
+/// Derives a systematic generator from any parity-check matrix `H`.
+///
+/// Gauss-Jordan elimination over GF(2) picks pivot columns from right to
+/// left, skipping columns that depend on those already picked, so `H` need
+/// not end in an invertible block. The pivot columns become the parity part:
+/// the columns of `H` are reordered to `[A | B]` with `B` invertible (recorded
+/// in [`SystematicForm::column_order`]), `B` is reduced to the identity,
+/// transforming `H` into `[A' | I]`, and the generator is `[I | A'^T]`, which
+/// satisfies `G * H^T = 0`. Rows that reduce to zero are linearly dependent
+/// on the others and are dropped.
+pub fn systematic_form(parity_check: &Array2<u8>) -> SystematicForm {
+    let (rows, cols) = parity_check.dim();
+
+    let mut reduced: Vec<Vec<u8>> = parity_check
+        .outer_iter()
+        .map(|row| row.iter().map(|&bit| bit & 1).collect())
+        .collect();
+    let mut origin: Vec<usize> = (0..rows).collect();
+    let mut pivot_cols = Vec::with_capacity(rows);
+
+    for col in (0..cols).rev() {
+        let pivot = pivot_cols.len();
+        if pivot == rows {
+            break;
+        }
+        let Some(found) = (pivot..rows).find(|&r| reduced[r][col] == 1) else {
+            continue;
+        };
+        reduced.swap(pivot, found);
+        origin.swap(pivot, found);
+
+        let pivot_row = reduced[pivot].clone();
+        for (idx, row) in reduced.iter_mut().enumerate() {
+            if idx != pivot && row[col] == 1 {
+                for (bit, &p) in row.iter_mut().zip(pivot_row.iter()) {
+                    *bit ^= p;
+                }
+            }
+        }
+        pivot_cols.push(col);
+    }
+
+    let rank = pivot_cols.len();
+    let message_bits = cols - rank;
+    let mut is_pivot = vec![false; cols];
+    for &col in &pivot_cols {
+        is_pivot[col] = true;
+    }
+    // Pivot rows ordered by the position of their pivot column
+    let mut parity_rows: Vec<(usize, usize)> = pivot_cols.iter().copied().zip(0..).collect();
+    parity_rows.sort_unstable();
+    let column_order: Vec<usize> = (0..cols)
+        .filter(|&col| !is_pivot[col])
+        .chain(parity_rows.iter().map(|&(col, _)| col))
+        .collect();
+
+    let mut generator = Array2::<u8>::zeros((message_bits, cols));
+    for (msg, &col) in column_order[..message_bits].iter().enumerate() {
+        generator[(msg, msg)] = 1;
+        for (parity, &(_, row)) in parity_rows.iter().enumerate() {
+            generator[(msg, message_bits + parity)] = reduced[row][col];
+        }
+    }
+
+    // Only pivot rows are ever added to other rows, so the original rows that
+    // became pivots span every check
+    let mut independent = origin[..rank].to_vec();
+    independent.sort_unstable();
+    let parity_check = Array2::from_shape_fn((rank, cols), |(row, col)| {
+        parity_check[(independent[row], column_order[col])] & 1
+    });
+
+    SystematicForm {
+        parity_check,
+        generator,
+        column_order,
+    }
+}
+
+/// Adjacency-list view of a Tanner graph under construction.
+struct TannerGraph {
+    var_checks: Vec<Vec<usize>>,
+    check_vars: Vec<Vec<usize>>,
+}
+
+impl TannerGraph {
+    fn new(vars: usize, checks: usize) -> Self {
+        Self {
+            var_checks: vec![Vec::new(); vars],
+            check_vars: vec![Vec::new(); checks],
+        }
+    }
+
+    fn connect(&mut self, var: usize, check: usize) {
+        self.var_checks[var].push(check);
+        self.check_vars[check].push(var);
+    }
+
+    /// Expands the Tanner graph breadth-first from `var` and returns the check
+    /// nodes at the largest distance from it: either those never reached, or
+    /// if every check is reachable, those reached at the last expansion step.
+    fn farthest_checks(&self, var: usize) -> Vec<usize> {
+        let num_checks = self.check_vars.len();
+        let mut reached = vec![false; num_checks];
+        let mut visited = vec![false; self.var_checks.len()];
+        visited[var] = true;
+
+        let mut frontier: Vec<usize> = self.var_checks[var].clone();
+        for &check in &frontier {
+            reached[check] = true;
+        }
+        let mut reached_count = frontier.len();
+
+        loop {
+            let mut next = Vec::new();
+            for &check in &frontier {
+                for &neighbour in &self.check_vars[check] {
+                    if visited[neighbour] {
+                        continue;
+                    }
+                    visited[neighbour] = true;
+                    for &far_check in &self.var_checks[neighbour] {
+                        if !reached[far_check] {
+                            reached[far_check] = true;
+                            next.push(far_check);
+                        }
+                    }
+                }
+            }
+
+            if next.is_empty() {
+                return (0..num_checks).filter(|&c| !reached[c]).collect();
+            }
+            if reached_count + next.len() == num_checks {
+                return next;
+            }
+
+            reached_count += next.len();
+            frontier = next;
+        }
+    }
+
+    /// Picks the lowest-degree candidate not already attached to `var`,
+    /// preferring checks still below the `dc` target and breaking ties randomly.
+    ///
+    /// If every farthest candidate is already at `dc`, the search widens to
+    /// all checks so the degree target wins over girth.
+    fn pick_check(&self, var: usize, candidates: &[usize], dc: usize, rng: &mut StdRng) -> usize {
+        let attachable = |c: &usize| !self.var_checks[var].contains(c);
+        let below_target = |c: &usize| self.check_vars[*c].len() < dc;
+
+        let free: Vec<usize> = candidates.iter().copied().filter(attachable).collect();
+        let all: Vec<usize> = (0..self.check_vars.len()).filter(attachable).collect();
+
+        let pool = [
+            free.iter()
+                .copied()
+                .filter(below_target)
+                .collect::<Vec<_>>(),
+            all.iter().copied().filter(below_target).collect(),
+            free,
+        ]
+        .into_iter()
+        .find(|pool| !pool.is_empty())
+        .unwrap_or(all);
+
+        let min_degree = pool
+            .iter()
+            .map(|&c| self.check_vars[c].len())
+            .min()
+            .unwrap_or(0);
+        let lightest: Vec<usize> = pool
+            .into_iter()
+            .filter(|&c| self.check_vars[c].len() == min_degree)
+            .collect();
+
+        *lightest
+            .choose(rng)
+            .expect("PEG always has at least one candidate check")
+    }
+
+    fn to_dense(&self) -> Array2<u8> {
+        let mut dense = Array2::<u8>::zeros((self.check_vars.len(), self.var_checks.len()));
+        for (check, vars) in self.check_vars.iter().enumerate() {
+            for &var in vars {
+                dense[(check, var)] = 1;
+            }
+        }
+        dense
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column_weight(h: &Array2<u8>, col: usize) -> usize {
+        h.column(col).iter().map(|&b| b as usize).sum()
+    }
+
+    #[test]
+    fn information_columns_have_dv_edges() {
+        let cfg = LDPCConfig {
+            dv: 3,
+            dc: 15,
+            seed: Some(7),
+            ..LDPCConfig::default()
+        };
+        let h = build_parity_check(160, 32, &cfg);
+
+        for col in 0..128 {
+            assert_eq!(column_weight(&h, col), 3, "column {col}");
+        }
+        assert_eq!(column_weight(&h, 159), 1);
+    }
+
+    #[test]
+    fn default_ensemble_is_check_regular() {
+        // 128 information bits * dv=2 + 63 staircase edges spread over 32 checks.
+        let h = build_parity_check(160, 32, &LDPCConfig::default());
+        for row in h.outer_iter() {
+            let degree: usize = row.iter().map(|&b| b as usize).sum();
+            assert!((9..=10).contains(&degree), "check degree {degree}");
+        }
+    }
+
+    #[test]
+    fn construction_is_deterministic_per_seed() {
+        let cfg = LDPCConfig::default();
+        assert_eq!(
+            build_parity_check(160, 32, &cfg),
+            build_parity_check(160, 32, &cfg)
+        );
+
+        let other = LDPCConfig {
+            seed: Some(7),
+            ..cfg.clone()
+        };
+        assert_ne!(
+            build_parity_check(160, 32, &LDPCConfig::default()),
+            build_parity_check(160, 32, &other)
+        );
+    }
+
+    #[test]
+    fn peg_avoids_four_cycles() {
+        let h = build_parity_check(160, 32, &LDPCConfig::default());
+        let (rows, cols) = h.dim();
+        for a in 0..cols {
+            for b in (a + 1)..cols {
+                let shared = (0..rows)
+                    .filter(|&r| h[(r, a)] == 1 && h[(r, b)] == 1)
+                    .count();
+                assert!(shared <= 1, "columns {a} and {b} share {shared} checks");
+            }
+        }
+    }
+
+    /// `G * H^T = 0` over GF(2)
+    fn is_orthogonal(g: &Array2<u8>, h: &Array2<u8>) -> bool {
+        let product = g.mapv(u32::from).dot(&h.t().mapv(u32::from));
+        product.iter().all(|v| v % 2 == 0)
+    }
+
+    #[test]
+    fn systematic_generator_is_orthogonal_to_parity_check() {
+        let h = build_parity_check(160, 32, &LDPCConfig::default());
+        let form = systematic_form(&h);
+
+        // The staircase parity part is invertible, so nothing is reordered
+        assert_eq!(form.column_order, (0..160).collect::<Vec<_>>());
+        assert_eq!(form.parity_check, h);
+        assert_eq!(form.generator.dim(), (128, 160));
+        for r in 0..128 {
+            for c in 0..128 {
+                assert_eq!(form.generator[(r, c)], u8::from(r == c));
+            }
+        }
+        assert!(is_orthogonal(&form.generator, &h));
+    }
+
+    #[test]
+    fn singular_parity_block_is_reordered() {
+        // The last two columns are equal, so column 1 joins the parity part
+        let h = Array2::from_shape_vec((2, 4), vec![1, 0, 1, 1, 0, 1, 1, 1]).unwrap();
+        let form = systematic_form(&h);
+
+        assert_eq!(form.column_order, vec![0, 2, 1, 3]);
+        assert_eq!(form.message_bits(), 2);
+        assert!(is_orthogonal(&form.generator, &form.parity_check));
+        let original = h.select(ndarray::Axis(1), &form.column_order);
+        assert_eq!(form.parity_check, original);
+    }
+
+    #[test]
+    fn dependent_rows_are_dropped() {
+        // Row 2 is the sum of rows 0 and 1
+        let h = Array2::from_shape_vec((3, 4), vec![1, 0, 1, 0, 0, 1, 0, 1, 1, 1, 1, 1]).unwrap();
+        let form = systematic_form(&h);
+
+        assert_eq!(form.message_bits(), 2);
+        assert_eq!(form.parity_check, h.slice(ndarray::s![..2, ..]));
+        assert!(is_orthogonal(&form.generator, &h));
+    }
+}
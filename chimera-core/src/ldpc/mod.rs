@@ -0,0 +1,631 @@
+use ldpc::codes::{CssCode, LinearCode};
+use ndarray::Array2;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinMat;
+
+use crate::config::{FrameLayout, LDPCConfig, LDPCDecoderConfig, LdpcConstruction};
+use crate::errors::{DecodingError, LdpcError};
+
+pub mod alist;
+pub mod analysis;
+pub mod belief_propagation;
+pub mod construction;
+pub mod packed;
+pub mod quasi_cyclic;
+
+pub use analysis::{exit_chart, ga_threshold, DegreeDistribution, ExitChart, ExitPoint};
+pub use belief_propagation::{BeliefPropagationDecoder, BeliefPropagationOutcome};
+pub use packed::{PackedCode, PackedMatrix};
+
+/// Represents the core matrices and parameters of a classical linear block code.
+///
+/// This structure holds the generator and parity-check matrices, along with the
+/// fundamental dimensions of the code: the number of message bits (k), codeword
+/// bits (n), and parity-check bits (n-k).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LDPCMatrices {
+    /// The parity-check matrix (H) of the code.
+    ///
+    /// A binary vector `c` is a valid codeword if `H * c^T = 0`.
+    pub parity_check: Array2<u8>,
+    /// The generator matrix (G) of the code.
+    ///
+    /// A message vector `m` is encoded into a codeword `c` by `c = m * G`.
+    pub generator: Array2<u8>,
+    /// The number of message bits (k), also known as the dimension of the code.
+    pub message_bits: usize,
+    /// The number of codeword bits (n), also known as the block length.
+    pub codeword_bits: usize,
+    /// The number of parity-check bits (n-k).
+    pub parity_bits: usize,
+}
+
+/// A collection of LDPC-related code structures for quantum error correction.
+///
+/// This suite encapsulates the classical X and Z codes, the resulting quantum
+/// CSS code, and the dense matrix representations required for classical encoding
+/// and decoding operations.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LDPCSuite {
+    /// The classical linear code for correcting Z-type errors (bit-flips).
+    pub x_code: LinearCode,
+    /// The classical linear code for correcting X-type errors (phase-flips).
+    pub z_code: LinearCode,
+    /// The Calderbank-Shor-Steane (CSS) quantum code constructed from the X and Z codes.
+    pub quantum_css: CssCode,
+    /// Dense matrix representations derived from the `x_code` for simulation purposes.
+    pub matrices: LDPCMatrices,
+    /// Bit-packed copies of `matrices` for word-parallel encoding and syndromes.
+    pub packed: PackedCode,
+    /// `column_order[i]` is the column of the source parity-check matrix at
+    /// codeword position `i` (the identity unless its last columns were
+    /// singular; see [`construction::systematic_form`]).
+    pub column_order: Vec<usize>,
+}
+
+impl LDPCSuite {
+    /// Creates a new `LDPCSuite` based on a given frame layout and configuration.
+    ///
+    /// The parity-check matrix is a progressive-edge-growth LDPC code with
+    /// information-bit degree `cfg.dv` and target check degree `cfg.dc`, built
+    /// over a dual-diagonal parity part so that it always has full rank (see
+    /// [`construction`]). The generator is the systematic form derived from
+    /// that matrix, so the first `message_bits` bits of every codeword are the
+    /// message itself and the remaining `ecc_bits` carry real redundancy.
+    ///
+    /// The Z code of the CSS pair takes the generator as its checks, which
+    /// makes it orthogonal to the X code by construction.
+    ///
+    /// `cfg.construction` and `cfg.parity_check_file` are not consulted: a
+    /// quasi-cyclic or loaded code may not fit `layout`, so those go through
+    /// [`Self::from_config`], which returns the error instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `layout` - The `FrameLayout` defining the number of message, ECC, and total bits.
+    /// * `cfg` - The `LDPCConfig` providing `dv`, `dc` and the construction seed.
+    ///
+    /// # Returns
+    ///
+    /// A new `LDPCSuite` instance.
+    pub fn new(layout: &FrameLayout, cfg: &LDPCConfig) -> Self {
+        let parity_check =
+            construction::build_parity_check(layout.codeword_bits(), layout.ecc_bits(), cfg);
+        Self::from_parity_check(layout, parity_check)
+            .expect("staircase parity block is always invertible")
+    }
+
+    /// Builds the suite around an externally designed parity-check matrix.
+    ///
+    /// `parity_check` must have `layout.codeword_bits()` columns and rank
+    /// `layout.ecc_bits()`. Linearly dependent rows are dropped, and when its
+    /// last `ecc_bits` columns are singular the columns are reordered so that
+    /// a systematic generator exists (recorded in `column_order`). A matrix
+    /// with too few columns or too many independent rows returns
+    /// `IncompatibleMatrixDimensions`, one of too low rank `SingularMatrix`.
+    pub fn from_parity_check(
+        layout: &FrameLayout,
+        parity_check: Array2<u8>,
+    ) -> Result<Self, LdpcError> {
+        let (rows, cols) = parity_check.dim();
+        if cols != layout.codeword_bits() || rows < layout.ecc_bits() {
+            return Err(LdpcError::IncompatibleMatrixDimensions { rows, cols });
+        }
+        let construction::SystematicForm {
+            parity_check,
+            generator,
+            column_order,
+        } = construction::systematic_form(&parity_check);
+        match generator.nrows().cmp(&layout.message_bits()) {
+            std::cmp::Ordering::Less => {
+                return Err(LdpcError::IncompatibleMatrixDimensions { rows, cols })
+            }
+            std::cmp::Ordering::Greater => return Err(LdpcError::SingularMatrix),
+            std::cmp::Ordering::Equal => {}
+        }
+
+        let x_code = LinearCode::from_both_matrices(
+            array_to_sparse(&generator),
+            array_to_sparse(&parity_check),
+        );
+        let z_code = LinearCode::from_parity_check_matrix(array_to_sparse(&generator));
+        let quantum_css = CssCode::new(&x_code, &z_code);
+        let matrices = build_ldpc_matrices(&x_code);
+        let packed = PackedCode::new(&matrices);
+
+        Ok(Self {
+            x_code,
+            z_code,
+            quantum_css,
+            matrices,
+            packed,
+            column_order,
+        })
+    }
+
+    /// Builds the suite selected by `cfg`: the matrix in
+    /// `cfg.parity_check_file` when set, `cfg.construction` otherwise.
+    pub fn from_config(layout: &FrameLayout, cfg: &LDPCConfig) -> Result<Self, LdpcError> {
+        let parity_check = match (&cfg.parity_check_file, &cfg.construction) {
+            (Some(path), _) => alist::load_parity_check(path)?,
+            (None, LdpcConstruction::ProgressiveEdgeGrowth) => return Ok(Self::new(layout, cfg)),
+            (None, LdpcConstruction::QuasiCyclic { base_graph }) => {
+                quasi_cyclic::build_parity_check(layout, base_graph)?
+            }
+        };
+        Self::from_parity_check(layout, parity_check)
+    }
+}
+
+impl LDPCMatrices {
+    /// Exports the parity-check matrix in alist format.
+    pub fn to_alist(&self) -> String {
+        alist::to_alist(&self.parity_check)
+    }
+}
+
+fn build_ldpc_matrices(code: &LinearCode) -> LDPCMatrices {
+    LDPCMatrices {
+        parity_check: sparse_to_array(code.parity_check_matrix()),
+        generator: sparse_to_array(code.generator_matrix()),
+        message_bits: code.dimension(),
+        codeword_bits: code.len(),
+        parity_bits: code.num_checks(),
+    }
+}
+
+fn array_to_sparse(matrix: &Array2<u8>) -> SparseBinMat {
+    SparseBinMat::new(
+        matrix.ncols(),
+        matrix
+            .outer_iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, &bit)| bit & 1 == 1)
+                    .map(|(col, _)| col)
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+fn sparse_to_array(matrix: &SparseBinMat) -> Array2<u8> {
+    let rows = matrix.number_of_rows();
+    let cols = matrix.number_of_columns();
+    let mut dense = Array2::<u8>::zeros((rows, cols));
+
+    for (row_idx, row) in matrix.rows().enumerate() {
+        for col_idx in row.non_trivial_positions() {
+            dense[(row_idx, col_idx)] = 1;
+        }
+    }
+
+    dense
+}
+
+#[inline]
+fn words_for_bits(bits: usize) -> usize {
+    bits.div_ceil(64)
+}
+
+#[inline]
+fn get_bit(words: &[u64], idx: usize) -> u8 {
+    let word = idx / 64;
+    let bit = idx % 64;
+    ((words[word] >> bit) & 1) as u8
+}
+
+#[inline]
+fn set_bit(words: &mut [u64], idx: usize, value: u8) {
+    let word = idx / 64;
+    let bit = idx % 64;
+    let mask = 1u64 << bit;
+    if value & 1 == 1 {
+        words[word] |= mask;
+    } else {
+        words[word] &= !mask;
+    }
+}
+
+#[inline]
+fn xor_suffix(row: &mut [u64], pivot: &[u64], start_bit: usize, total_bits: usize) {
+    if start_bit >= total_bits {
+        return;
+    }
+
+    let start_word = start_bit / 64;
+    let bit_offset = start_bit % 64;
+    let total_words = words_for_bits(total_bits);
+    let last_bits = total_bits % 64;
+
+    for word_idx in start_word..total_words {
+        let mut mask = u64::MAX;
+        if word_idx == start_word {
+            mask &= u64::MAX << bit_offset;
+        }
+        if last_bits != 0 && word_idx == total_words - 1 {
+            mask &= (1u64 << last_bits) - 1;
+        }
+
+        row[word_idx] ^= pivot[word_idx] & mask;
+    }
+}
+
+/// Performs hard-decision decoding of an LDPC codeword.
+///
+/// This function attempts to recover the original message bits from a (potentially noisy)
+/// codeword by solving the linear system `G * m^T = c^T` over the finite field GF(2),
+/// where `G` is the generator matrix, `m` is the message vector, and `c` is the codeword.
+///
+/// The implementation uses parallelized Gaussian elimination to transform an augmented
+/// matrix `[G^T | c]` into reduced row echelon form. This method is effective for
+/// recovering the message when the codeword has few or no errors. It is not a
+/// belief propagation or message-passing decoder and is therefore not robust against
+/// high noise levels; use [`decode_ldpc_soft`] when channel LLRs are available.
+///
+/// The `_snr_db` parameter is currently unused but is reserved for future integration
+/// with soft-decision decoding algorithms.
+///
+/// # Arguments
+///
+/// * `matrices` - A reference to the `LDPCMatrices` containing the generator matrix.
+/// * `noisy_codeword` - A slice of `u8` representing the received codeword bits. Its
+///   length must equal `matrices.codeword_bits`.
+/// * `_snr_db` - The signal-to-noise ratio in decibels (currently unused).
+///
+/// # Returns
+///
+/// A `Vec<u8>` containing the recovered message bits. If the system is underdetermined,
+/// free variables are resolved to 0.
+///
+/// # Panics
+///
+/// Panics in debug builds if `noisy_codeword.len()` does not match `matrices.codeword_bits`.
+///
+/// # Performance Notes
+///
+/// - Uses word-level (64-bit) operations for GF(2) arithmetic
+/// - Parallelizes row operations using rayon when beneficial
+/// - SIMD-friendly memory layout with potential for further optimization
+pub fn decode_ldpc(matrices: &LDPCMatrices, noisy_codeword: &[u8], _snr_db: f64) -> Vec<u8> {
+    let message_bits = matrices.message_bits;
+    let codeword_bits = matrices.codeword_bits;
+    let total_bits = message_bits + 1;
+    let word_len = words_for_bits(total_bits);
+
+    debug_assert_eq!(noisy_codeword.len(), codeword_bits);
+
+    // Build the augmented matrix for the linear system G^T * m = c, where G is
+    // the generator matrix and c is the received codeword. Each row encodes a
+    // single codeword bit equation over GF(2).
+    // 
+    // Memory layout is optimized for cache efficiency and potential SIMD operations
+    let mut augmented: Vec<Vec<u64>> = (0..codeword_bits)
+        .map(|col| {
+            let mut row = vec![0u64; word_len];
+            for row_idx in 0..message_bits {
+                if matrices.generator[(row_idx, col)] & 1 == 1 {
+                    set_bit(&mut row, row_idx, 1);
+                }
+            }
+            if noisy_codeword[col] & 1 == 1 {
+                set_bit(&mut row, message_bits, 1);
+            }
+            row
+        })
+        .collect();
+
+    // Gaussian elimination over GF(2) to obtain reduced row echelon form. We
+    // keep track of which row becomes the pivot for each message bit column.
+    let mut pivot_row = 0usize;
+    let mut column_pivots: Vec<Option<usize>> = vec![None; message_bits];
+
+    for (col, pivot) in column_pivots.iter_mut().enumerate().take(message_bits) {
+        if pivot_row >= augmented.len() {
+            break;
+        }
+
+        // Find a row with a leading 1 in this column.
+        if let Some(pivot_idx) =
+            (pivot_row..augmented.len()).find(|&r| get_bit(&augmented[r], col) == 1)
+        {
+            augmented.swap(pivot_row, pivot_idx);
+
+            // Eliminate this column from every other row to reach reduced form.
+            // The pivot row is now at `pivot_row`. We can borrow it immutably
+            // while we modify other rows.
+            let (pivot_slice, other_rows) = augmented.split_at_mut(pivot_row);
+            let (pivot_row_ref, other_rows_after) = other_rows.split_at_mut(1);
+
+            // XOR the pivot row with all other rows that have a 1 in the pivot column.
+            let pivot_row_data = &pivot_row_ref[0];
+
+            let eliminate = |rows: &mut [Vec<u64>]| {
+                // Use sequential iteration for small matrices to avoid parallel overhead.
+                const PARALLEL_THRESHOLD: usize = 1000;
+                if rows.len() < PARALLEL_THRESHOLD {
+                    rows.iter_mut()
+                        .filter(|row| get_bit(row, col) == 1)
+                        .for_each(|row| xor_suffix(row, pivot_row_data, col, total_bits));
+                } else {
+                    // Parallel processing for large matrices - SIMD-friendly
+                    rows.par_iter_mut()
+                        .filter(|row| get_bit(row, col) == 1)
+                        .for_each(|row| xor_suffix(row, pivot_row_data, col, total_bits));
+                }
+            };
+
+            eliminate(pivot_slice);
+            eliminate(other_rows_after);
+
+            *pivot = Some(pivot_row);
+            pivot_row += 1;
+        }
+    }
+
+    let mut message = vec![0u8; message_bits];
+    for (col, pivot) in column_pivots.into_iter().enumerate() {
+        if let Some(row_idx) = pivot {
+            message[col] = get_bit(&augmented[row_idx], message_bits);
+        } else {
+            // Column without a pivot corresponds to a free variable; choose 0.
+            message[col] = 0;
+        }
+    }
+
+    message
+}
+
+/// Structured result of decoding one LDPC codeword.
+///
+/// Counts are measured on the codeword itself: the syndrome weight (number of
+/// unsatisfied parity checks) of the channel hard decision and of the decoder
+/// output, and how many codeword bits the decoder changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecodeOutcome {
+    /// Recovered message bits (best estimate even if decoding failed)
+    pub message_bits: Vec<u8>,
+    /// Hard decision on the channel LLRs, before error correction
+    pub channel_codeword: Vec<u8>,
+    /// Codeword produced by the decoder
+    pub codeword: Vec<u8>,
+    /// Unsatisfied parity checks in `channel_codeword`
+    pub syndrome_weight_before: usize,
+    /// Unsatisfied parity checks in `codeword`
+    pub syndrome_weight_after: usize,
+    /// Codeword bits that differ between `channel_codeword` and `codeword`
+    pub bits_flipped: usize,
+    /// Belief-propagation iterations used
+    pub iterations: usize,
+    /// Whether the decoded codeword satisfies every parity check
+    pub success: bool,
+}
+
+impl DecodeOutcome {
+    /// Returns the message bits, or [`DecodingError::InvalidCodeword`] if the
+    /// decoded codeword fails the syndrome check.
+    pub fn into_result(self) -> Result<Vec<u8>, DecodingError> {
+        if self.success {
+            Ok(self.message_bits)
+        } else {
+            Err(DecodingError::InvalidCodeword)
+        }
+    }
+}
+
+/// Counts the parity checks of `parity_check` not satisfied by `codeword`.
+pub fn syndrome_weight(parity_check: &Array2<u8>, codeword: &[u8]) -> usize {
+    parity_check
+        .outer_iter()
+        .filter(|row| {
+            row.iter()
+                .zip(codeword)
+                .fold(0u8, |parity, (&h, &c)| parity ^ (h & c & 1))
+                == 1
+        })
+        .count()
+}
+
+/// Decodes channel LLRs with belief propagation and reports the full outcome.
+///
+/// The iterative decoder configured by `cfg` corrects the codeword, after which
+/// the message is solved from it exactly as in [`decode_ldpc`]. If decoding
+/// does not converge the message is still solved from the decoder's final hard
+/// decision and `success` is false.
+///
+/// # Arguments
+///
+/// * `matrices` - The code's parity-check and generator matrices.
+/// * `channel_llrs` - One LLR per codeword bit, positive favouring 0.
+/// * `cfg` - Algorithm, iteration budget and early-stopping settings.
+pub fn decode_ldpc_outcome(
+    matrices: &LDPCMatrices,
+    channel_llrs: &[f64],
+    cfg: &LDPCDecoderConfig,
+) -> DecodeOutcome {
+    let bp = BeliefPropagationDecoder::new(&matrices.parity_check, cfg).decode(channel_llrs);
+    outcome_from_bp(
+        channel_llrs,
+        bp,
+        |codeword| decode_ldpc(matrices, codeword, 0.0),
+        |codeword| syndrome_weight(&matrices.parity_check, codeword),
+    )
+}
+
+/// [`decode_ldpc_outcome`] with a prebuilt `decoder` for the code of `packed`,
+/// using the packed matrices for the syndromes and message recovery instead
+/// of per-frame dense elimination.
+pub fn decode_ldpc_outcome_packed(
+    decoder: &BeliefPropagationDecoder,
+    packed: &PackedCode,
+    channel_llrs: &[f64],
+) -> DecodeOutcome {
+    let bp = decoder.decode(channel_llrs);
+    outcome_from_bp(
+        channel_llrs,
+        bp,
+        |codeword| packed.recover_message(codeword),
+        |codeword| packed.syndrome_weight(codeword),
+    )
+}
+
+fn outcome_from_bp(
+    channel_llrs: &[f64],
+    bp: BeliefPropagationOutcome,
+    recover_message: impl Fn(&[u8]) -> Vec<u8>,
+    syndrome_weight: impl Fn(&[u8]) -> usize,
+) -> DecodeOutcome {
+    let channel_codeword: Vec<u8> = channel_llrs.iter().map(|&llr| u8::from(llr < 0.0)).collect();
+
+    DecodeOutcome {
+        message_bits: recover_message(&bp.codeword),
+        syndrome_weight_before: syndrome_weight(&channel_codeword),
+        syndrome_weight_after: syndrome_weight(&bp.codeword),
+        bits_flipped: channel_codeword
+            .iter()
+            .zip(&bp.codeword)
+            .filter(|(a, b)| a != b)
+            .count(),
+        iterations: bp.iterations,
+        success: bp.converged,
+        channel_codeword,
+        codeword: bp.codeword,
+    }
+}
+
+/// Decodes channel LLRs with belief propagation and recovers the message bits.
+///
+/// Convenience wrapper around [`decode_ldpc_outcome`] for callers that only
+/// need the message.
+///
+/// # Errors
+///
+/// Returns [`DecodingError::ConvergenceFailed`] if no valid codeword is found
+/// within `cfg.max_iterations`.
+pub fn decode_ldpc_soft(
+    matrices: &LDPCMatrices,
+    channel_llrs: &[f64],
+    cfg: &LDPCDecoderConfig,
+) -> Result<Vec<u8>, DecodingError> {
+    let outcome = decode_ldpc_outcome(matrices, channel_llrs, cfg);
+    if !outcome.success {
+        return Err(DecodingError::ConvergenceFailed {
+            iterations: outcome.iterations,
+        });
+    }
+
+    Ok(outcome.message_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn matrices_have_expected_shapes() {
+        let layout = FrameLayout::default();
+        let cfg = LDPCConfig::default();
+        let suite = LDPCSuite::new(&layout, &cfg);
+
+        assert_eq!(suite.matrices.generator.nrows(), layout.message_bits());
+        assert_eq!(suite.matrices.generator.ncols(), layout.codeword_bits());
+        assert_eq!(suite.matrices.parity_check.nrows(), layout.ecc_bits());
+        assert_eq!(suite.matrices.parity_check.ncols(), layout.codeword_bits());
+        assert_eq!(suite.quantum_css.len(), layout.codeword_bits());
+    }
+
+    #[test]
+    fn decode_ldpc_recovers_encoded_message() {
+        let layout = FrameLayout::default();
+        let cfg = LDPCConfig::default();
+        let matrices = LDPCSuite::new(&layout, &cfg).matrices;
+
+        let mut rng = StdRng::seed_from_u64(1337);
+        let message: Vec<u8> = (0..layout.message_bits())
+            .map(|_| if rng.gen_bool(0.5) { 1 } else { 0 })
+            .collect();
+
+        let mut codeword = vec![0u8; layout.codeword_bits()];
+        for (row_idx, &bit) in message.iter().enumerate() {
+            if bit == 0 {
+                continue;
+            }
+            for (col_idx, codeword_bit) in
+                codeword.iter_mut().enumerate().take(layout.codeword_bits())
+            {
+                *codeword_bit ^= matrices.generator[(row_idx, col_idx)] & 1;
+            }
+        }
+
+        let decoded = decode_ldpc(&matrices, &codeword, 0.0);
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn decode_ldpc_soft_corrects_channel_errors() {
+        let layout = FrameLayout::default();
+        let cfg = LDPCConfig::default();
+        let matrices = LDPCSuite::new(&layout, &cfg).matrices;
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let message: Vec<u8> = (0..layout.message_bits())
+            .map(|_| u8::from(rng.gen_bool(0.5)))
+            .collect();
+        let mut llrs = vec![0.0; layout.codeword_bits()];
+        for (col, llr) in llrs.iter_mut().enumerate() {
+            let bit = message
+                .iter()
+                .enumerate()
+                .fold(0u8, |acc, (row, &m)| acc ^ (m & matrices.generator[(row, col)]));
+            *llr = if bit == 0 { 2.5 } else { -2.5 };
+        }
+        llrs[3] = -llrs[3];
+        llrs[140] = -llrs[140] * 0.2;
+
+        let decoded = decode_ldpc_soft(&matrices, &llrs, &cfg.decoder).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn decode_outcome_reports_syndrome_and_corrections() {
+        let layout = FrameLayout::default();
+        let cfg = LDPCConfig::default();
+        let matrices = LDPCSuite::new(&layout, &cfg).matrices;
+
+        let message = vec![1u8; layout.message_bits()];
+        let codeword = crate::encoder::encode_with_generator(&matrices.generator, &message);
+        let mut llrs: Vec<f64> = codeword.iter().map(|&b| if b == 0 { 3.0 } else { -3.0 }).collect();
+        llrs[10] = -llrs[10] * 0.3;
+        llrs[99] = -llrs[99] * 0.3;
+
+        let outcome = decode_ldpc_outcome(&matrices, &llrs, &cfg.decoder);
+        assert!(outcome.success);
+        assert!(outcome.syndrome_weight_before > 0);
+        assert_eq!(outcome.syndrome_weight_after, 0);
+        assert_eq!(outcome.bits_flipped, 2);
+        assert_eq!(outcome.codeword, codeword);
+        assert_eq!(outcome.clone().into_result().unwrap(), message);
+    }
+
+    #[test]
+    fn failed_decode_raises_invalid_codeword() {
+        let layout = FrameLayout::default();
+        let cfg = LDPCConfig::default();
+        let matrices = LDPCSuite::new(&layout, &cfg).matrices;
+
+        let mut rng = StdRng::seed_from_u64(9);
+        let llrs: Vec<f64> = (0..layout.codeword_bits()).map(|_| rng.gen_range(-0.3..0.3)).collect();
+        let decoder_cfg = LDPCDecoderConfig {
+            max_iterations: 3,
+            ..LDPCDecoderConfig::default()
+        };
+
+        let outcome = decode_ldpc_outcome(&matrices, &llrs, &decoder_cfg);
+        assert!(!outcome.success);
+        assert!(outcome.syndrome_weight_after > 0);
+        assert!(matches!(outcome.into_result(), Err(DecodingError::InvalidCodeword)));
+    }
+}
@@ -0,0 +1,307 @@
+//! Bit-packed GF(2) matrices for word-parallel encoding and syndrome checks.
+//!
+//! Rows are stored as `u64` words (bit `i` of a row lives in word `i / 64`,
+//! bit `i % 64`), so encoding is an XOR of whole generator rows and a parity
+//! check is an AND plus a popcount.
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use super::LDPCMatrices;
+
+/// Packs bits (one per byte) into `u64` words.
+pub fn pack_words(bits: &[u8]) -> Vec<u64> {
+    bits.chunks(64)
+        .map(|chunk| {
+            chunk
+                .chunks(8)
+                .enumerate()
+                .fold(0u64, |word, (byte_idx, group)| {
+                    let mut bytes = [0u8; 8];
+                    bytes[..group.len()].copy_from_slice(group);
+                    // Gather the LSB of each of the 8 bytes into one byte
+                    let lsbs = u64::from_le_bytes(bytes) & 0x0101_0101_0101_0101;
+                    let byte = lsbs.wrapping_mul(0x0102_0408_1020_4080) >> 56;
+                    word | (byte << (8 * byte_idx))
+                })
+        })
+        .collect()
+}
+
+/// Bits of every byte value, LSB first
+const BYTE_BITS: [[u8; 8]; 256] = {
+    let mut table = [[0u8; 8]; 256];
+    let mut value = 0;
+    while value < 256 {
+        let mut bit = 0;
+        while bit < 8 {
+            table[value][bit] = ((value >> bit) & 1) as u8;
+            bit += 1;
+        }
+        value += 1;
+    }
+    table
+};
+
+/// Expands the first `len` bits of `words` back to one bit per byte.
+pub fn unpack_words(words: &[u64], len: usize) -> Vec<u8> {
+    let mut bits = Vec::with_capacity(words.len() * 64);
+    for word in words {
+        for byte in word.to_le_bytes() {
+            bits.extend_from_slice(&BYTE_BITS[byte as usize]);
+        }
+    }
+    bits.truncate(len);
+    bits
+}
+
+/// Dense GF(2) matrix with rows packed into `u64` words
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackedMatrix {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl PackedMatrix {
+    pub fn from_dense(matrix: &Array2<u8>) -> Self {
+        let (rows, cols) = matrix.dim();
+        let words_per_row = cols.div_ceil(64);
+        let mut words = vec![0u64; rows * words_per_row];
+        for ((row, col), &bit) in matrix.indexed_iter() {
+            words[row * words_per_row + col / 64] |= u64::from(bit & 1) << (col % 64);
+        }
+
+        Self {
+            rows,
+            cols,
+            words_per_row,
+            words,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// Computes `x · M` for a row vector `x` of `rows()` bits.
+    pub fn left_multiply(&self, x: &[u8]) -> Vec<u64> {
+        debug_assert_eq!(x.len(), self.rows);
+        self.left_multiply_packed(&pack_words(x))
+    }
+
+    /// [`Self::left_multiply`] for an already packed `x`, visiting only its
+    /// set bits.
+    pub fn left_multiply_packed(&self, x: &[u64]) -> Vec<u64> {
+        let mut acc = vec![0u64; self.words_per_row];
+        for (word_idx, &word) in x.iter().enumerate() {
+            let mut remaining = word;
+            while remaining != 0 {
+                let row = word_idx * 64 + remaining.trailing_zeros() as usize;
+                remaining &= remaining - 1;
+                for (a, &m) in acc.iter_mut().zip(self.row(row)) {
+                    *a ^= m;
+                }
+            }
+        }
+        acc
+    }
+
+    /// Whether row `row` has odd overlap with the packed vector `v`
+    #[inline]
+    pub fn row_parity(&self, row: usize, v: &[u64]) -> bool {
+        self.row(row)
+            .iter()
+            .zip(v)
+            .fold(0u32, |acc, (&m, &x)| acc ^ (m & x).count_ones())
+            & 1
+            == 1
+    }
+}
+
+/// Packed generator and parity-check matrices of a code, with a
+/// precomputed inverse for recovering the message from a codeword.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackedCode {
+    generator: PackedMatrix,
+    parity_check: PackedMatrix,
+    /// Codeword positions that determine the message (pivot columns of G)
+    information_set: Vec<usize>,
+    /// `m = c[information_set] · recovery`
+    recovery: PackedMatrix,
+}
+
+impl PackedCode {
+    pub fn new(matrices: &LDPCMatrices) -> Self {
+        let (information_set, recovery) = message_recovery(&matrices.generator);
+        Self {
+            generator: PackedMatrix::from_dense(&matrices.generator),
+            parity_check: PackedMatrix::from_dense(&matrices.parity_check),
+            information_set,
+            recovery: PackedMatrix::from_dense(&recovery),
+        }
+    }
+
+    pub fn generator(&self) -> &PackedMatrix {
+        &self.generator
+    }
+
+    pub fn parity_check(&self) -> &PackedMatrix {
+        &self.parity_check
+    }
+
+    /// Encodes `message` as `m · G`, one XOR per word of each selected row.
+    pub fn encode(&self, message: &[u8]) -> Vec<u8> {
+        assert_eq!(
+            self.generator.rows(),
+            message.len(),
+            "message length must match generator rank"
+        );
+        unpack_words(
+            &self.generator.left_multiply(message),
+            self.generator.cols(),
+        )
+    }
+
+    /// Counts the parity checks not satisfied by `codeword`.
+    pub fn syndrome_weight(&self, codeword: &[u8]) -> usize {
+        let packed = pack_words(codeword);
+        (0..self.parity_check.rows())
+            .filter(|&row| self.parity_check.row_parity(row, &packed))
+            .count()
+    }
+
+    /// Solves `m · G = c` for the message using the cached inverse.
+    ///
+    /// Matches [`super::decode_ldpc`]: the message is read from the first
+    /// independent codeword positions, and free variables are 0.
+    pub fn recover_message(&self, codeword: &[u8]) -> Vec<u8> {
+        let selected: Vec<u8> = self
+            .information_set
+            .iter()
+            .map(|&pos| codeword[pos])
+            .collect();
+        unpack_words(
+            &self.recovery.left_multiply(&selected),
+            self.recovery.cols(),
+        )
+    }
+}
+
+/// Reduces `[G | I]` to find the pivot columns of `G` and the row operations
+/// `E` with `E · G[:, pivots] = I`.
+fn message_recovery(generator: &Array2<u8>) -> (Vec<usize>, Array2<u8>) {
+    let (k, n) = generator.dim();
+    let mut rows: Vec<Vec<u8>> = generator
+        .outer_iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            let mut augmented: Vec<u8> = row.iter().map(|&bit| bit & 1).collect();
+            augmented.extend((0..k).map(|col| u8::from(col == idx)));
+            augmented
+        })
+        .collect();
+
+    let mut pivots = Vec::with_capacity(k);
+    for col in 0..n {
+        let rank = pivots.len();
+        if rank == k {
+            break;
+        }
+        let Some(found) = (rank..k).find(|&r| rows[r][col] == 1) else {
+            continue;
+        };
+        rows.swap(rank, found);
+        let pivot_row = rows[rank].clone();
+        for (idx, row) in rows.iter_mut().enumerate() {
+            if idx != rank && row[col] == 1 {
+                for (bit, &p) in row.iter_mut().zip(&pivot_row) {
+                    *bit ^= p;
+                }
+            }
+        }
+        pivots.push(col);
+    }
+
+    let mut recovery = Array2::<u8>::zeros((pivots.len(), k));
+    for (r, row) in rows.iter().take(pivots.len()).enumerate() {
+        for (c, &bit) in row[n..].iter().enumerate() {
+            recovery[(r, c)] = bit;
+        }
+    }
+    (pivots, recovery)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FrameLayout, LDPCConfig};
+    use crate::encoder::encode_with_generator;
+    use crate::ldpc::{decode_ldpc, syndrome_weight, LDPCSuite};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn packed_operations_match_dense_reference() {
+        let suite = LDPCSuite::new(&FrameLayout::default(), &LDPCConfig::default());
+        let matrices = &suite.matrices;
+        let mut rng = StdRng::seed_from_u64(9);
+
+        for _ in 0..20 {
+            let message: Vec<u8> = (0..matrices.message_bits)
+                .map(|_| rng.gen_range(0..=1))
+                .collect();
+            let codeword = suite.packed.encode(&message);
+            assert_eq!(
+                codeword,
+                encode_with_generator(&matrices.generator, &message)
+            );
+            assert_eq!(suite.packed.syndrome_weight(&codeword), 0);
+
+            let mut noisy = codeword.clone();
+            for _ in 0..3 {
+                let pos = rng.gen_range(0..noisy.len());
+                noisy[pos] ^= 1;
+            }
+            assert_eq!(
+                suite.packed.syndrome_weight(&noisy),
+                syndrome_weight(&matrices.parity_check, &noisy)
+            );
+            assert_eq!(
+                suite.packed.recover_message(&noisy),
+                decode_ldpc(matrices, &noisy, 0.0)
+            );
+        }
+    }
+
+    #[test]
+    fn recovery_inverts_a_non_systematic_generator() {
+        // Rows of G mixed so the message is not a prefix of the codeword
+        let generator = Array2::from_shape_vec(
+            (3, 6),
+            vec![1, 1, 0, 1, 0, 0, 0, 1, 1, 0, 1, 0, 1, 1, 1, 0, 0, 1],
+        )
+        .unwrap();
+        let matrices = LDPCMatrices {
+            parity_check: Array2::zeros((3, 6)),
+            generator,
+            message_bits: 3,
+            codeword_bits: 6,
+            parity_bits: 3,
+        };
+        let packed = PackedCode::new(&matrices);
+
+        for value in 0..8u8 {
+            let message: Vec<u8> = (0..3).map(|bit| (value >> bit) & 1).collect();
+            assert_eq!(packed.recover_message(&packed.encode(&message)), message);
+        }
+    }
+}
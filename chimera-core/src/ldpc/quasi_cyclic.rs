@@ -0,0 +1,189 @@
+//! Quasi-cyclic LDPC codes lifted from base matrices.
+//!
+//! A base matrix entry `s >= 0` expands to a `Z x Z` identity cyclically
+//! shifted by `s`, and `-1` to a `Z x Z` zero block. Base matrices are
+//! published for a reference lifting size `Z0`; other lifting sizes use the
+//! floor scaling `s * Z / Z0` (as in IEEE 802.16e), so a single base graph
+//! yields a family of block lengths.
+//!
+//! The lifting size is chosen so the expanded code has exactly the layout's
+//! `ecc_bits` checks; surplus information columns are then shortened (known
+//! zero bits removed from the codeword) to reach `codeword_bits`.
+
+use ndarray::Array2;
+
+use crate::config::{BaseGraph, FrameLayout};
+use crate::errors::LdpcError;
+
+/// IEEE 802.11n rate-1/2 base matrix for n = 648 (Z0 = 27)
+#[rustfmt::skip]
+const IEEE80211N_HALF: [[i32; 24]; 12] = [
+    [ 0, -1, -1, -1,  0,  0, -1, -1,  0, -1, -1,  0,  1,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [22,  0, -1, -1, 17, -1,  0,  0, 12, -1, -1, -1, -1,  0,  0, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 6, -1,  0, -1, 10, -1, -1, -1, 24, -1,  0, -1, -1, -1,  0,  0, -1, -1, -1, -1, -1, -1, -1, -1],
+    [ 2, -1, -1,  0, 20, -1, -1, -1, 25,  0, -1, -1, -1, -1, -1,  0,  0, -1, -1, -1, -1, -1, -1, -1],
+    [23, -1, -1, -1,  3, -1, -1, -1,  0, -1,  9, 11, -1, -1, -1, -1,  0,  0, -1, -1, -1, -1, -1, -1],
+    [24, -1, 23,  1, 17, -1,  3, -1, 10, -1, -1, -1, -1, -1, -1, -1, -1,  0,  0, -1, -1, -1, -1, -1],
+    [25, -1, -1, -1,  8, -1, -1, -1,  7, 18, -1, -1,  0, -1, -1, -1, -1, -1,  0,  0, -1, -1, -1, -1],
+    [13, 24, -1, -1,  0, -1,  8, -1,  6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,  0,  0, -1, -1, -1],
+    [ 7, 20, -1, 16, 22, 10, -1, -1, 23, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,  0,  0, -1, -1],
+    [11, -1, -1, -1, 19, -1, -1, -1, 13, -1,  3, 17, -1, -1, -1, -1, -1, -1, -1, -1, -1,  0,  0, -1],
+    [25, -1,  8, -1, 23, 18, -1, 14,  9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,  0,  0],
+    [ 3, -1, -1, -1, 16, -1, -1,  2, 25,  5, -1, -1,  1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,  0],
+];
+
+/// IEEE 802.11n rate-5/6 base matrix for n = 648 (Z0 = 27)
+#[rustfmt::skip]
+const IEEE80211N_FIVE_SIXTHS: [[i32; 24]; 4] = [
+    [17, 13,  8, 21,  9,  3, 18, 12, 10,  0,  4, 15, 19,  2,  5, 10, 26, 19, 13, 13,  1,  0, -1, -1],
+    [ 3, 12, 11, 14, 11, 25,  5, 18,  0,  9,  2, 26, 26, 10, 24,  7, 14, 20,  4,  2, -1,  0,  0, -1],
+    [22, 16,  4,  3, 10, 21, 12,  5, 21, 14, 19,  5, -1,  8,  5, 18, 11,  5,  5, 15,  0, -1,  0,  0],
+    [ 7,  7, 14, 14,  4, 16, 16, 24, 24, 10,  1,  7, 15,  6, 10, 26,  8, 18, 21, 14,  1, -1, -1,  0],
+];
+
+/// Shift table of `graph` and the lifting size its shifts are defined for
+fn base_matrix(graph: &BaseGraph) -> (Vec<Vec<i32>>, usize) {
+    match graph {
+        BaseGraph::Ieee80211nHalf => (IEEE80211N_HALF.iter().map(|row| row.to_vec()).collect(), 27),
+        BaseGraph::Ieee80211nFiveSixths => (
+            IEEE80211N_FIVE_SIXTHS
+                .iter()
+                .map(|row| row.to_vec())
+                .collect(),
+            27,
+        ),
+        BaseGraph::Custom { shifts, lifting } => (shifts.clone(), *lifting),
+    }
+}
+
+/// Expands a base matrix with lifting size `z`, scaling shifts defined for `z0`.
+pub fn expand(shifts: &[Vec<i32>], z0: usize, z: usize) -> Array2<u8> {
+    let rows = shifts.len();
+    let cols = shifts.first().map_or(0, Vec::len);
+    let mut matrix = Array2::<u8>::zeros((rows * z, cols * z));
+
+    for (block_row, row) in shifts.iter().enumerate() {
+        for (block_col, &shift) in row.iter().enumerate() {
+            if shift < 0 {
+                continue;
+            }
+            let shift = shift as usize * z / z0.max(1);
+            for i in 0..z {
+                matrix[(block_row * z + i, block_col * z + (i + shift) % z)] = 1;
+            }
+        }
+    }
+
+    matrix
+}
+
+/// Lifts `graph` to the layout's `ecc_bits` checks and shortens it to
+/// `codeword_bits` columns.
+///
+/// The last block rows of the base graph hold the parity part, so shortening
+/// removes the trailing information columns.
+pub fn build_parity_check(
+    layout: &FrameLayout,
+    graph: &BaseGraph,
+) -> Result<Array2<u8>, LdpcError> {
+    let (shifts, z0) = base_matrix(graph);
+    let base_rows = shifts.len();
+    let base_cols = shifts.first().map_or(0, Vec::len);
+    if base_rows == 0 || base_cols <= base_rows || shifts.iter().any(|row| row.len() != base_cols) {
+        return Err(LdpcError::IncompatibleMatrixDimensions {
+            rows: base_rows,
+            cols: base_cols,
+        });
+    }
+
+    let ecc_bits = layout.ecc_bits();
+    if ecc_bits == 0 || !ecc_bits.is_multiple_of(base_rows) {
+        return Err(LdpcError::MatrixConstructionFailed {
+            reason: format!(
+                "{ecc_bits} parity bits are not a multiple of the {base_rows} base rows"
+            ),
+        });
+    }
+    let z = ecc_bits / base_rows;
+
+    let info_bits = (base_cols - base_rows) * z;
+    if layout.message_bits() > info_bits {
+        return Err(LdpcError::InvalidCodeRate {
+            k: layout.message_bits(),
+            n: layout.codeword_bits(),
+        });
+    }
+
+    let lifted = expand(&shifts, z0, z);
+    let kept: Vec<usize> = (0..layout.message_bits())
+        .chain(info_bits..base_cols * z)
+        .collect();
+    Ok(lifted.select(ndarray::Axis(1), &kept))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{LDPCConfig, LdpcConstruction};
+    use crate::fec::{FecCodec, LdpcCodec};
+    use crate::ldpc::LDPCSuite;
+
+    #[test]
+    fn expanded_blocks_are_circulant_permutations() {
+        let (shifts, z0) = base_matrix(&BaseGraph::Ieee80211nHalf);
+        let h = expand(&shifts, z0, z0);
+        assert_eq!(h.dim(), (324, 648));
+        // Block (1, 0) has shift 22: row i connects to column (i + 22) % 27
+        for i in 0..27 {
+            assert_eq!(h[(27 + i, (i + 22) % 27)], 1);
+            assert_eq!(h.slice(ndarray::s![27 + i, 0..27]).sum(), 1);
+        }
+    }
+
+    #[test]
+    fn five_sixths_graph_fits_default_frame() {
+        let layout = FrameLayout::default();
+        let h = build_parity_check(&layout, &BaseGraph::Ieee80211nFiveSixths).unwrap();
+        assert_eq!(h.dim(), (layout.ecc_bits(), layout.codeword_bits()));
+
+        let cfg = LDPCConfig {
+            construction: LdpcConstruction::QuasiCyclic {
+                base_graph: BaseGraph::Ieee80211nFiveSixths,
+            },
+            ..LDPCConfig::default()
+        };
+        let suite = LDPCSuite::from_config(&layout, &cfg).unwrap();
+        let codec = LdpcCodec::from_suite(suite, cfg.decoder.clone());
+
+        let message: Vec<u8> = (0..codec.message_bits())
+            .map(|i| (i % 5 == 2) as u8)
+            .collect();
+        let mut llrs: Vec<f64> = codec
+            .encode(&message)
+            .iter()
+            .map(|&b| if b == 0 { 4.0 } else { -4.0 })
+            .collect();
+        for pos in [3, 50, 140] {
+            llrs[pos] = -llrs[pos] / 4.0;
+        }
+        let outcome = codec.decode_soft(&llrs);
+        assert!(outcome.success);
+        assert_eq!(outcome.message_bits, message);
+    }
+
+    #[test]
+    fn lifting_must_match_parity_bits() {
+        // 32 parity bits cannot be split over 12 base rows
+        assert!(matches!(
+            build_parity_check(&FrameLayout::default(), &BaseGraph::Ieee80211nHalf),
+            Err(LdpcError::MatrixConstructionFailed { .. })
+        ));
+        let cfg = LDPCConfig {
+            construction: LdpcConstruction::QuasiCyclic {
+                base_graph: BaseGraph::Ieee80211nHalf,
+            },
+            ..LDPCConfig::default()
+        };
+        assert!(LDPCSuite::from_config(&FrameLayout::default(), &cfg).is_err());
+    }
+}
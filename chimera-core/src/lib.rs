@@ -5,19 +5,24 @@
 //! the former Python reference implementation, but expose a fully typed
 //! and testable API.
 
+pub mod arq;
 pub mod audio_generator;
+pub mod checksum;
 pub mod config;
 pub mod decoder;
 pub mod diagnostics;
 pub mod encoder;
 pub mod errors;
 pub mod external_audio;
+pub mod fec;
 pub mod ldpc;
 pub mod logging;
+pub mod payload;
 pub mod pipeline;
 pub mod processor;
 pub mod protocol;
 pub mod signal_processing;
+pub mod sync;
 pub mod thz_carriers;
 pub mod utils;
 
@@ -0,0 +1,88 @@
+//! Message payload framing.
+//!
+//! A transmitted message is a 16-bit big-endian byte count followed by the
+//! payload bytes, so the receiver knows exactly where the payload ends and
+//! binary data (including trailing NULs) round-trips bit-exactly. Text is
+//! sent as its UTF-8 bytes.
+
+use crate::errors::{DecodingError, EncodingError};
+use crate::utils::bytes_to_bitstream;
+
+/// Width of the byte-count field that precedes every payload
+pub const LENGTH_FIELD_BITS: usize = 16;
+
+/// Bytes taken by the length field
+pub const LENGTH_FIELD_BYTES: usize = LENGTH_FIELD_BITS / 8;
+
+/// Largest payload the length field can describe
+pub const MAX_PAYLOAD_BYTES: usize = u16::MAX as usize;
+
+/// Message bits for `payload`: its byte count followed by the bytes, MSB first
+pub fn encode(payload: &[u8]) -> Result<Vec<u8>, EncodingError> {
+    let len = u16::try_from(payload.len()).map_err(|_| EncodingError::MessageTooLong {
+        message_len: payload.len(),
+        max_len: MAX_PAYLOAD_BYTES,
+    })?;
+    let mut bits = bytes_to_bitstream(&len.to_be_bytes());
+    bits.extend(bytes_to_bitstream(payload));
+    Ok(bits)
+}
+
+/// Payload carried by reassembled message bytes, cut to the declared length
+///
+/// Fails if the message is shorter than its length field (counted with the
+/// field itself) declares.
+pub fn decode(message: &[u8]) -> Result<&[u8], DecodingError> {
+    let declared = declared_len(message).unwrap_or(0);
+    message
+        .get(LENGTH_FIELD_BYTES..LENGTH_FIELD_BYTES + declared)
+        .ok_or(DecodingError::PayloadTruncated {
+            declared: LENGTH_FIELD_BYTES + declared,
+            available: message.len(),
+        })
+}
+
+/// The part of the payload received so far (for messages still arriving)
+pub fn decode_partial(message: &[u8]) -> &[u8] {
+    let declared = declared_len(message).unwrap_or(0);
+    let body = message.get(LENGTH_FIELD_BYTES..).unwrap_or_default();
+    &body[..declared.min(body.len())]
+}
+
+fn declared_len(message: &[u8]) -> Option<usize> {
+    let field = message.get(..LENGTH_FIELD_BYTES)?;
+    Some(usize::from(u16::from_be_bytes([field[0], field[1]])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::pack_bits;
+
+    #[test]
+    fn binary_payload_round_trips_exactly() {
+        let payload = [0x00, 0xFF, 0x80, 0x00, 0x7F, 0x00, 0x00];
+        let bits = encode(&payload).unwrap();
+        assert_eq!(bits.len(), LENGTH_FIELD_BITS + payload.len() * 8);
+
+        // Zero padding after the payload (the rest of the last frame) is not part of it
+        let mut message = pack_bits(&bits);
+        message.extend([0u8; 9]);
+        assert_eq!(decode(&message).unwrap(), payload);
+        assert_eq!(decode(&pack_bits(&encode(&[]).unwrap())).unwrap(), b"");
+    }
+
+    #[test]
+    fn short_messages_are_reported() {
+        let message = pack_bits(&encode(b"payload").unwrap());
+        let cut = &message[..5];
+        assert!(matches!(
+            decode(cut),
+            Err(DecodingError::PayloadTruncated { declared: 9, available: 5 })
+        ));
+        assert_eq!(decode_partial(cut), b"pay");
+        assert_eq!(decode_partial(&message[..1]), b"");
+        assert!(decode(&message[..1]).is_err());
+        assert!(encode(&vec![0; MAX_PAYLOAD_BYTES + 1]).is_err());
+    }
+}
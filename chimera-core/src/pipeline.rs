@@ -4,8 +4,11 @@
 //! and batch processing operations. It supports chunk-by-chunk processing for
 //! real-time audio applications and can also be used for offline batch processing.
 
-use crate::config::{LDPCConfig, InternalProtocolConfig, UserSimulationConfig};
-use crate::ldpc::LDPCSuite;
+use crate::config::{parse_address, LDPCConfig, InternalProtocolConfig, OuterCode, RealtimeController, UserSimulationConfig};
+use crate::fec::{build_codec, FecCodec};
+use crate::diagnostics::FrameDescriptor;
+use crate::ldpc::DecodeOutcome;
+use crate::protocol::CommandType;
 use crate::thz_carriers::{ThzCarrierProcessor, ThzCarrierConfig};
 use crate::signal_processing::{
     modulation::{ModulationConfig, symbols_to_carrier_signal},
@@ -19,6 +22,8 @@ use crate::diagnostics::{
 use num_complex::Complex;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
+use std::collections::VecDeque;
+use std::sync::Arc;
 
 // Cross-platform timing abstraction
 #[cfg(not(target_arch = "wasm32"))]
@@ -150,6 +155,26 @@ pub struct FSKState {
     pub bit_rate_hz: f64,
 }
 
+/// Change to what the pipeline transmits, held until the current message ends
+#[derive(Clone, Debug, PartialEq)]
+pub enum TxUpdate {
+    /// Payload bytes of the next message
+    Payload(Vec<u8>),
+    /// Command name, e.g. `send_data`
+    Command(String),
+    /// Target ID as hex digits
+    TargetId(String),
+}
+
+/// What the transmitter is sending
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TxState {
+    /// The payload as (lossy) UTF-8 text
+    pub message: String,
+    pub command: String,
+    pub target_id: String,
+}
+
 /// Output from a single processing chunk
 #[derive(Clone, Debug, Default)]
 pub struct RealtimeOutput {
@@ -167,17 +192,38 @@ pub struct RealtimeOutput {
     pub decoded_data: Vec<u8>,
     /// Decoded text
     pub decoded_text: String,
+    /// Payload bytes of the message decoded so far, cut to its length field
+    pub decoded_payload: Vec<u8>,
     
     /// Performance metrics
     pub frames_processed: usize,
     pub symbols_decoded: usize,
+    /// Codeword bits corrected by the LDPC decoder so far
     pub fec_corrections: usize,
+    /// Codeword bits received in error before FEC so far (measured against TX)
+    pub pre_fec_errors: usize,
+    /// Message bits still in error after FEC so far (measured against TX)
+    pub post_fec_errors: usize,
+    /// Decode outcome of the frame completed in this chunk, if any
+    pub decode_outcome: Option<DecodeOutcome>,
+    /// Copies chase-combined for the frame completed in this chunk (0 if none)
+    pub combining_depth: usize,
+    /// Header of the frame completed in this chunk, if any
+    pub frame_header: Option<FrameDescriptor>,
     
     /// Current frame data
     pub current_frame_data: FrameData,
     
     /// FSK layer state (nested 1 bit/second modulation)
     pub fsk_state: Option<FSKState>,
+    
+    /// Updates waiting for the current message to end
+    pub tx_queue_depth: usize,
+    /// Message, command and target ID being transmitted
+    pub transmitting: TxState,
+    /// Queued updates rejected at the message boundary (the previous message
+    /// keeps being sent)
+    pub tx_errors: Vec<String>,
 }
 
 /// Real-time capable DSP pipeline
@@ -186,7 +232,9 @@ pub struct RealtimePipeline {
     config: UserSimulationConfig,
     protocol: InternalProtocolConfig,
     ldpc_config: LDPCConfig,
-    ldpc_suite: LDPCSuite,
+    codec: Arc<dyn FecCodec>,
+    // Bytes being transmitted; `config.message` holds them as text
+    payload: Vec<u8>,
     
     // Runtime adjustable channel parameters
     snr_db: f64,
@@ -196,6 +244,13 @@ pub struct RealtimePipeline {
     encoder: Option<crate::encoder::StreamingFrameEncoder>,
     decoder: Option<crate::decoder::StreamingSymbolDecoder>,
     
+    // Updates applied at the next message boundary, oldest first
+    tx_queue: VecDeque<TxUpdate>,
+    // Updates rejected since the last chunk was returned
+    tx_errors: Vec<String>,
+    // Source of queued messages and sink of the message being sent
+    controller: Option<RealtimeController>,
+    
     // THz carrier processor for AID effect
     thz_processor: ThzCarrierProcessor,
     is_active_mode: bool,
@@ -206,7 +261,11 @@ pub struct RealtimePipeline {
     total_symbols_generated: usize,
     total_symbols_decoded: usize,
     total_errors: usize,
+    total_pre_fec_errors: usize,
+    total_bits_compared: usize,
     ber_accumulator: f32,
+    /// Last message rebuilt by the outer code (replaces per-frame text when set)
+    outer_message: Option<Vec<u8>>,
     symbols_per_update: usize,
     
     // Accumulators for diagnostics
@@ -222,23 +281,34 @@ pub struct RealtimePipeline {
 impl RealtimePipeline {
     /// Create a new real-time capable pipeline with the given configuration
     /// Default SNR=20dB, link_loss=0dB - use update_channel_params to adjust at runtime
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`build_codec`] error if `ldpc` does not fit the frame layout.
     pub fn new(
         sim: UserSimulationConfig,
         protocol: InternalProtocolConfig,
         ldpc: LDPCConfig,
-    ) -> Self {
+    ) -> crate::errors::Result<Self> {
         Self::with_channel_params(sim, protocol, ldpc, 20.0, 0.0)
     }
     
     /// Create pipeline with specific channel parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`build_codec`] error if `ldpc` does not fit the frame layout.
     pub fn with_channel_params(
         sim: UserSimulationConfig,
         protocol: InternalProtocolConfig,
         ldpc: LDPCConfig,
         snr_db: f64,
         link_loss_db: f64,
-    ) -> Self {
-        let ldpc_suite = LDPCSuite::new(&protocol.frame_layout, &ldpc);
+    ) -> crate::errors::Result<Self> {
+        let codec = build_codec(&protocol.frame_layout, &ldpc)?;
+        let payload = sim.message.as_bytes().to_vec();
+        let encoder = Self::message_encoder(&payload, &protocol, &codec, &ldpc.outer_code)?;
+        let decoder = Self::message_decoder(&protocol, &codec, &ldpc, &encoder)?;
         
         // Update less frequently to accumulate more samples for better spectrum resolution
         // At 16 sym/s, updating every 16 symbols = 1 second updates
@@ -258,30 +328,37 @@ impl RealtimePipeline {
         thz_config.bypass_simulation = sim.bypass_thz_simulation;
         let thz_processor = ThzCarrierProcessor::new(thz_config, crate::config::SystemConfig::SAMPLE_RATE as f64);
         
-        Self {
+        Ok(Self {
             config: sim,
             protocol,
             ldpc_config: ldpc,
-            ldpc_suite,
+            codec,
+            payload,
             snr_db,
             link_loss_db,
-            encoder: None,
-            decoder: None,
+            total_frames: encoder.total_frames,
+            encoder: Some(encoder),
+            decoder: Some(decoder),
+            tx_queue: VecDeque::new(),
+            tx_errors: Vec::new(),
+            controller: None,
             thz_processor,
             is_active_mode: false,
             frame_count: 0,
-            total_frames: 0,
             total_symbols_generated: 0,
             total_symbols_decoded: 0,
             total_errors: 0,
+            total_pre_fec_errors: 0,
+            total_bits_compared: 0,
             ber_accumulator: 0.0,
+            outer_message: None,
             symbols_per_update,
             tx_symbols_buffer: Vec::new(),
             rx_symbols_buffer: Vec::new(),
             rng,
             signal_power,
             noise_std,
-        }
+        })
     }
     
     /// Set modulation mode (idle vs active)
@@ -332,25 +409,22 @@ impl RealtimePipeline {
     pub fn process_chunk(&mut self, _input: &[u8]) -> RealtimeOutput {
         // No rate limiting - process and emit symbols as fast as possible for real-time visualization
         
-        // Initialize encoder on first call
-        if self.encoder.is_none() {
-            let payload_bits = crate::utils::string_to_bitstream(&self.config.message);
-            let encoder = crate::encoder::StreamingFrameEncoder::new(
-                &payload_bits,
-                self.protocol.clone(),
-                self.ldpc_suite.matrices.clone(),
-            );
-            self.total_frames = encoder.total_frames;
-            self.encoder = Some(encoder);
+        if let Some(controller) = &self.controller {
+            match controller.take_queued_messages() {
+                Ok(messages) => self
+                    .tx_queue
+                    .extend(messages.into_iter().map(|message| TxUpdate::Payload(message.into_bytes()))),
+                Err(e) => self.tx_errors.push(format!("Controller queue unavailable: {e}")),
+            }
         }
         
-        // Initialize decoder on first call
-        if self.decoder.is_none() {
-            let decoder = crate::decoder::StreamingSymbolDecoder::new(
-                self.protocol.clone(),
-                self.ldpc_suite.matrices.clone(),
-            );
-            self.decoder = Some(decoder);
+        // Queued updates replace the message once its last frame has been sent
+        // (or straight away if nothing has been sent yet)
+        if !self.tx_queue.is_empty()
+            && (self.total_symbols_generated == 0
+                || self.encoder.as_ref().is_some_and(|encoder| encoder.at_message_boundary()))
+        {
+            self.apply_tx_updates();
         }
         
         let encoder = self.encoder.as_mut().unwrap();
@@ -361,9 +435,15 @@ impl RealtimePipeline {
         let symbol_rate = self.protocol.qpsk_symbol_rate;
         let carrier_freq = self.protocol.carrier_freq_hz;
         
-        // Generate next batch of symbols
+        // Generate next batch of symbols, stopping at the end of the message
+        // when updates are waiting for it
+        let symbol_count = if self.tx_queue.is_empty() {
+            self.symbols_per_update
+        } else {
+            self.symbols_per_update.min(encoder.symbols_until_message_end())
+        };
         let (tx_symbols, frame_changed, current_frame, symbol_in_frame, _is_complete) = 
-            encoder.get_next_symbols(self.symbols_per_update);
+            encoder.get_next_symbols(symbol_count);
         
         if frame_changed {
             self.frame_count += 1;
@@ -398,6 +478,7 @@ impl RealtimePipeline {
             sample_rate,
             symbol_rate,
             carrier_freq,
+            ..DemodulationConfig::from_protocol(&self.protocol, sample_rate)
         };
         let rx_symbols: Vec<Complex<f64>> = crate::signal_processing::demodulation::audio_to_symbols(&base_audio, &demod_config);
         // Note: Using simple demodulation without SNR measurement from demod itself
@@ -406,10 +487,25 @@ impl RealtimePipeline {
         let (decoded_bits, frame_complete, dec_frame_index, _symbols_in_dec_frame, diagnostics) = 
             decoder.process_symbols(&rx_symbols);
         
-        // Track decoder frame completion
+        // Measure FEC performance of a completed frame against what was transmitted
+        let mut decode_outcome = None;
+        let mut ber_instantaneous = 0.0;
         if frame_complete {
-            // Decoder completed a frame - this is what tests expect
-            // Don't rely on encoder's frame count
+            if let Some(outcome) = decoder.last_decode_outcome().cloned() {
+                let message_bits = self.codec.message_bits();
+                let tx_frame = dec_frame_index.saturating_sub(1) % self.total_frames.max(1);
+                let tx_message = encoder.frame_message(tx_frame);
+                let tx_codeword = self.codec.encode(&tx_message);
+                
+                let count_errors = |a: &[u8], b: &[u8]| a.iter().zip(b).filter(|(x, y)| x != y).count();
+                let post_fec_errors = count_errors(&tx_message, &outcome.message_bits);
+                self.total_pre_fec_errors += count_errors(&tx_codeword, &outcome.channel_codeword);
+                self.total_errors += post_fec_errors;
+                self.total_bits_compared += message_bits;
+                self.ber_accumulator = self.total_errors as f32 / self.total_bits_compared as f32;
+                ber_instantaneous = post_fec_errors as f32 / message_bits as f32;
+                decode_outcome = Some(outcome);
+            }
         }
         
         // Extract FSK state information from decoder (not encoder!)
@@ -498,7 +594,7 @@ impl RealtimePipeline {
             carrier_freq_hz: self.protocol.carrier_freq_hz,
             symbol_rate_hz: self.protocol.qpsk_symbol_rate as u32,
             modulation_type: "QPSK".to_string(),
-            fec_rate: format!("{}/{}", self.ldpc_suite.matrices.message_bits, self.ldpc_suite.matrices.codeword_bits),
+            fec_rate: format!("{}/{}", self.codec.message_bits(), self.codec.codeword_bits()),
             frame_layout: FrameLayoutInfo {
                 sync_bytes: (self.protocol.frame_layout.sync_symbols * 2) / 8,
                 data_bytes: (self.protocol.frame_layout.data_payload_symbols * 2) / 8,
@@ -531,7 +627,7 @@ impl RealtimePipeline {
             frequency_offset_hz: 0.0,
             phase_offset_rad: 0.0,
             evm_percent,
-            ber_instantaneous: 0.0, // TODO: calculate from decoder
+            ber_instantaneous,
             ber_average: self.ber_accumulator,
             sync_status: decoder.is_synced(),
             lock_status: if !decoder.is_synced() { "SEARCHING".to_string() }
@@ -539,12 +635,18 @@ impl RealtimePipeline {
                         else { "LOCKED".to_string() },
         };
         
-        // Decoded text - convert bits to bytes first
-        let all_decoded_bits = decoder.get_decoded_payload();
-        let all_decoded_bytes = crate::utils::pack_bits(&all_decoded_bits);
-        output.decoded_text = String::from_utf8_lossy(&all_decoded_bytes)
-            .trim_end_matches('\u{0}')
-            .to_string();
+        // Decoded text - the outer-coded message once one is complete, otherwise every frame
+        // Failures are logged by the decoder; keep showing the last good message
+        if let Some(Ok(message)) = decoder.take_outer_message() {
+            self.outer_message = Some(message);
+        }
+        let all_decoded_bytes = match &self.outer_message {
+            Some(message) => message.clone(),
+            None if encoder.outer_code() != OuterCode::None => Vec::new(),
+            None => crate::utils::pack_bits(&decoder.get_decoded_payload()),
+        };
+        output.decoded_payload = crate::payload::decode_partial(&all_decoded_bytes).to_vec();
+        output.decoded_text = String::from_utf8_lossy(&output.decoded_payload).into_owned();
         
         // Decoded data from this chunk (if frame was completed)
         if !decoded_bits.is_empty() {
@@ -555,7 +657,12 @@ impl RealtimePipeline {
         // Performance metrics
         output.frames_processed = dec_frame_index; // Use decoder's frame count, not encoder's
         output.symbols_decoded = self.total_symbols_decoded;
-        output.fec_corrections = 0;
+        output.fec_corrections = decoder.total_bits_corrected();
+        output.pre_fec_errors = self.total_pre_fec_errors;
+        output.post_fec_errors = self.total_errors;
+        output.decode_outcome = decode_outcome;
+        output.combining_depth = diagnostics.combining_depth;
+        output.frame_header = diagnostics.frames.last().cloned();
         
         // Frame data for display
         let frame_bits = encoder.get_current_frame_bits();
@@ -640,9 +747,116 @@ impl RealtimePipeline {
             bit_rate_hz: 1.0,
         });
         
+        output.tx_queue_depth = self.tx_queue.len();
+        output.transmitting = self.tx_state();
+        output.tx_errors = std::mem::take(&mut self.tx_errors);
+        
         output
     }
     
+    /// Frame encoder for `payload` with the configured outer code
+    fn message_encoder(
+        payload: &[u8],
+        protocol: &InternalProtocolConfig,
+        codec: &Arc<dyn FecCodec>,
+        outer_code: &OuterCode,
+    ) -> crate::errors::Result<crate::encoder::StreamingFrameEncoder> {
+        let payload_bits = crate::payload::encode(payload)?;
+        let mut encoder =
+            crate::encoder::StreamingFrameEncoder::with_codec(&payload_bits, protocol.clone(), codec.clone())?;
+        encoder.enable_outer_code(outer_code)?;
+        Ok(encoder)
+    }
+    
+    /// Frame decoder for the messages `encoder` sends
+    fn message_decoder(
+        protocol: &InternalProtocolConfig,
+        codec: &Arc<dyn FecCodec>,
+        ldpc: &LDPCConfig,
+        encoder: &crate::encoder::StreamingFrameEncoder,
+    ) -> crate::errors::Result<crate::decoder::StreamingSymbolDecoder> {
+        Ok(crate::decoder::StreamingSymbolDecoder::with_codec(protocol.clone(), codec.clone())?
+            .with_outer_code(&encoder.outer_code())
+            .with_chase_combining(ldpc.combining_depth))
+    }
+    
+    /// Apply queued command and target ID updates up to and including the
+    /// next message, and restart the encoder and decoder with them
+    ///
+    /// Updates the encoder rejects (e.g. a message too long for the outer
+    /// code) are reported in [`RealtimeOutput::tx_errors`] and dropped.
+    fn apply_tx_updates(&mut self) {
+        let mut protocol = self.protocol.clone();
+        let mut payload = self.payload.clone();
+        while let Some(update) = self.tx_queue.pop_front() {
+            match update {
+                TxUpdate::Command(command) => protocol.command = command,
+                TxUpdate::TargetId(target_id) => protocol.target_id_hex = target_id,
+                TxUpdate::Payload(queued) => {
+                    payload = queued;
+                    break;
+                }
+            }
+        }
+        let message = Self::message_encoder(&payload, &protocol, &self.codec, &self.ldpc_config.outer_code)
+            .and_then(|encoder| {
+                let decoder = Self::message_decoder(&protocol, &self.codec, &self.ldpc_config, &encoder)?;
+                Ok((encoder, decoder))
+            });
+        let (encoder, decoder) = match message {
+            Ok(coders) => coders,
+            Err(e) => {
+                self.tx_errors.push(format!("Rejected TX update: {e}"));
+                return;
+            }
+        };
+        self.protocol = protocol;
+        self.config.message = String::from_utf8_lossy(&payload).into_owned();
+        self.payload = payload;
+        self.total_frames = encoder.total_frames;
+        self.encoder = Some(encoder);
+        self.decoder = Some(decoder);
+        self.outer_message = None;
+        if let Some(controller) = &self.controller {
+            let message = self.config.message.clone();
+            if let Err(e) = controller.update(|params| params.message = Some(message)) {
+                self.tx_errors.push(format!("Controller update failed: {e}"));
+            }
+        }
+    }
+    
+    /// Message, command and target ID being transmitted
+    pub fn tx_state(&self) -> TxState {
+        TxState {
+            message: self.config.message.clone(),
+            command: self.protocol.command.clone(),
+            target_id: self.protocol.target_id_hex.clone(),
+        }
+    }
+    
+    /// Payload bytes being transmitted
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+    
+    /// Updates waiting for the current message to end
+    pub fn tx_queue(&self) -> &VecDeque<TxUpdate> {
+        &self.tx_queue
+    }
+    
+    /// Take messages queued with [`RealtimeController::set_message`] and
+    /// report the message being sent in its `message` parameter
+    ///
+    /// Failures to reach the controller are reported in
+    /// [`RealtimeOutput::tx_errors`].
+    pub fn attach_controller(&mut self, controller: RealtimeController) {
+        let message = self.config.message.clone();
+        if let Err(e) = controller.update(|params| params.message = Some(message)) {
+            self.tx_errors.push(format!("Controller update failed: {e}"));
+        }
+        self.controller = Some(controller);
+    }
+    
     /// Get current pipeline configuration
     pub fn get_config(&self) -> PipelineConfig {
         PipelineConfig {
@@ -653,7 +867,22 @@ impl RealtimePipeline {
     
     /// Reconfigure the pipeline
     /// Note: snr_db and link_loss_db should be passed separately or use update_channel_params
-    pub fn reconfigure(&mut self, sim: UserSimulationConfig, protocol: InternalProtocolConfig, ldpc: LDPCConfig) {
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`build_codec`] error if `ldpc` does not fit the frame
+    /// layout; the pipeline is left unchanged.
+    pub fn reconfigure(
+        &mut self,
+        sim: UserSimulationConfig,
+        protocol: InternalProtocolConfig,
+        ldpc: LDPCConfig,
+    ) -> crate::errors::Result<()> {
+        let codec = build_codec(&protocol.frame_layout, &ldpc)?;
+        let payload = sim.message.as_bytes().to_vec();
+        let encoder = Self::message_encoder(&payload, &protocol, &codec, &ldpc.outer_code)?;
+        let decoder = Self::message_decoder(&protocol, &codec, &ldpc, &encoder)?;
+
         // Keep existing channel parameters
         let snr_db = self.snr_db;
         let link_loss_db = self.link_loss_db;
@@ -669,21 +898,28 @@ impl RealtimePipeline {
         self.config = sim;
         self.protocol = protocol.clone();
         self.ldpc_config = ldpc;
-        self.ldpc_suite = LDPCSuite::new(&protocol.frame_layout, &self.ldpc_config);
-        self.encoder = None;
-        self.decoder = None;
+        self.codec = codec;
+        self.payload = payload;
+        self.total_frames = encoder.total_frames;
+        self.encoder = Some(encoder);
+        self.decoder = Some(decoder);
+        self.tx_queue.clear();
+        self.tx_errors.clear();
         self.frame_count = 0;
-        self.total_frames = 0;
         self.total_symbols_generated = 0;
         self.total_symbols_decoded = 0;
         self.total_errors = 0;
+        self.total_pre_fec_errors = 0;
+        self.total_bits_compared = 0;
         self.ber_accumulator = 0.0;
+        self.outer_message = None;
         self.symbols_per_update = 4;
         self.tx_symbols_buffer.clear();
         self.rx_symbols_buffer.clear();
         self.rng = rng;
         self.signal_power = signal_power;
         self.noise_std = noise_std;
+        Ok(())
     }
     
     /// Update channel parameters (SNR and link loss) without resetting the pipeline
@@ -714,22 +950,38 @@ impl RealtimePipeline {
         self.link_loss_db
     }
     
-    /// Update message (placeholder - needs proper implementation to wait for transmission)
-    pub fn update_message(&mut self, _message: String) -> Result<(), String> {
-        // TODO: Implement message queue that waits for current transmission to complete
-        Err("Message updates not yet implemented".to_string())
+    /// Queue a payload to replace the current one once its last frame has
+    /// been sent; its length travels with it, so any bytes round-trip exactly
+    pub fn update_payload(&mut self, payload: Vec<u8>) -> Result<(), String> {
+        Self::message_encoder(&payload, &self.protocol, &self.codec, &self.ldpc_config.outer_code)
+            .map_err(|e| e.to_string())?;
+        self.tx_queue.push_back(TxUpdate::Payload(payload));
+        Ok(())
+    }
+    
+    /// Queue a text message; see [`Self::update_payload`]
+    pub fn update_message(&mut self, message: String) -> Result<(), String> {
+        self.update_payload(message.into_bytes())
     }
     
-    /// Update command (placeholder)
-    pub fn update_command(&mut self, _command: String) -> Result<(), String> {
-        // TODO: Implement command updates
-        Err("Command updates not yet implemented".to_string())
+    /// Queue a command (e.g. `send_data`) for the next message boundary
+    pub fn update_command(&mut self, command: String) -> Result<(), String> {
+        if CommandType::from_str(&command).is_none() {
+            return Err(format!("Unknown command: {command}"));
+        }
+        self.tx_queue.push_back(TxUpdate::Command(command));
+        Ok(())
     }
     
-    /// Update target ID (placeholder)
-    pub fn update_target_id(&mut self, _target_id: String) -> Result<(), String> {
-        // TODO: Implement target ID updates
-        Err("Target ID updates not yet implemented".to_string())
+    /// Queue a target ID (hex, optional `0x` prefix) for the next message boundary
+    pub fn update_target_id(&mut self, target_id: String) -> Result<(), String> {
+        let id = parse_address(&target_id).map_err(|e| e.to_string())?;
+        let field_bits = self.protocol.frame_layout.target_id_symbols * 2;
+        if field_bits < 64 && id >> field_bits != 0 {
+            return Err(format!("Target ID {target_id} does not fit the {field_bits}-bit target field"));
+        }
+        self.tx_queue.push_back(TxUpdate::TargetId(format!("{id:X}")));
+        Ok(())
     }
     
     /// Set TX gain (placeholder)
@@ -751,3 +1003,197 @@ pub struct PipelineConfig {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{RuntimeParams, UserConfig};
+
+    fn pipeline(message: &str) -> RealtimePipeline {
+        let sim = UserSimulationConfig {
+            message: message.to_string(),
+            ..UserSimulationConfig::default()
+        };
+        // A fast symbol rate keeps each chunk's audio, and so the tests, short
+        let protocol = InternalProtocolConfig {
+            qpsk_symbol_rate: 1600,
+            ..InternalProtocolConfig::default()
+        };
+        RealtimePipeline::new(sim, protocol, LDPCConfig::default()).unwrap()
+    }
+
+    /// Symbols in one pass over the pipeline's current message
+    fn message_symbols(pipeline: &RealtimePipeline) -> usize {
+        pipeline.total_frames * pipeline.protocol.frame_layout.total_symbols
+    }
+
+    #[test]
+    fn invalid_fec_configuration_is_an_error() {
+        let bad = LDPCConfig {
+            fec: crate::config::FecScheme::Repetition { factor: 0 },
+            ..LDPCConfig::default()
+        };
+        assert!(RealtimePipeline::new(UserSimulationConfig::default(), InternalProtocolConfig::default(), bad.clone()).is_err());
+
+        let mut pipeline = pipeline("Hello");
+        let codeword_bits = pipeline.codec.codeword_bits();
+        assert!(pipeline
+            .reconfigure(UserSimulationConfig::default(), InternalProtocolConfig::default(), bad)
+            .is_err());
+        assert_eq!(pipeline.codec.codeword_bits(), codeword_bits);
+    }
+
+    #[test]
+    fn outer_code_that_cannot_cover_the_message_is_an_error() {
+        let sim = UserSimulationConfig {
+            message: "Hi".to_string(),
+            ..UserSimulationConfig::default()
+        };
+        let protocol = InternalProtocolConfig {
+            qpsk_symbol_rate: 1600,
+            ..InternalProtocolConfig::default()
+        };
+        let ldpc = |parity_frames| LDPCConfig {
+            outer_code: OuterCode::ReedSolomon { parity_frames },
+            ..LDPCConfig::default()
+        };
+        assert!(RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc(255)).is_err());
+
+        // One data frame fits beside 250 parity frames; a long message does not
+        let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc(250)).unwrap();
+        let long = "x".repeat(1000);
+        assert!(pipeline.update_message(long.clone()).is_err());
+        assert!(pipeline.tx_queue().is_empty());
+
+        // Messages queued through the controller are rejected at the boundary
+        let controller = RealtimeController::new(RuntimeParams::new(&UserConfig::default(), None));
+        controller.set_message(long).unwrap();
+        pipeline.attach_controller(controller);
+        let output = pipeline.process_chunk(&[]);
+        assert_eq!(output.tx_errors.len(), 1);
+        assert_eq!(output.transmitting.message, "Hi");
+        assert!(pipeline.process_chunk(&[]).tx_errors.is_empty());
+    }
+
+    #[test]
+    fn queued_message_starts_at_message_boundary() {
+        let mut pipeline = pipeline("Hi");
+        let first = pipeline.process_chunk(&[]);
+        let first_message_symbols = message_symbols(&pipeline);
+        pipeline.update_message("Bye".to_string()).unwrap();
+
+        let mut sent = first.pre_channel.symbol_count;
+        let mut output = first;
+        while sent < first_message_symbols {
+            assert_eq!(output.transmitting.message, "Hi");
+            output = pipeline.process_chunk(&[]);
+            sent += output.pre_channel.symbol_count;
+            assert_eq!(output.tx_queue_depth, 1);
+        }
+        // The last chunk of the old message stops exactly at its end
+        assert_eq!(sent, first_message_symbols);
+
+        let output = pipeline.process_chunk(&[]);
+        assert_eq!(output.tx_queue_depth, 0);
+        assert_eq!(output.transmitting.message, "Bye");
+        assert_eq!(output.pre_channel.frame_count, 1);
+    }
+
+    #[test]
+    fn command_and_target_apply_with_the_next_message() {
+        let mut pipeline = pipeline("Hi");
+        pipeline.update_command("reset".to_string()).unwrap();
+        pipeline.update_target_id("0xcafe".to_string()).unwrap();
+        pipeline.update_message("one".to_string()).unwrap();
+        pipeline.update_message("two".to_string()).unwrap();
+
+        // Nothing has been sent yet, so updates up to the first message apply at once
+        let output = pipeline.process_chunk(&[]);
+        assert_eq!(
+            output.transmitting,
+            TxState {
+                message: "one".to_string(),
+                command: "reset".to_string(),
+                target_id: "CAFE".to_string(),
+            }
+        );
+        assert_eq!(output.tx_queue_depth, 1);
+    }
+
+    #[test]
+    fn binary_payload_is_sent_byte_for_byte() {
+        let mut pipeline = pipeline("Hi");
+        let payload = vec![0x00, 0xFF, 0x41, 0x00, 0xFF, 0x00, 0x00];
+        pipeline.update_payload(payload.clone()).unwrap();
+        assert_eq!(pipeline.tx_queue().back(), Some(&TxUpdate::Payload(payload.clone())));
+
+        let output = pipeline.process_chunk(&[]);
+        assert_eq!(pipeline.payload(), payload);
+        assert_eq!(output.transmitting.message, String::from_utf8_lossy(&payload));
+
+        // The frame carries the length field and the bytes, NULs included
+        let payload_bits = crate::payload::encode(&payload).unwrap();
+        let frame = pipeline.encoder.as_ref().unwrap().frame_message(0);
+        assert_eq!(frame[..payload_bits.len()], payload_bits[..]);
+        assert!(frame[payload_bits.len()..].iter().all(|&bit| bit == 0));
+    }
+
+    #[test]
+    fn invalid_updates_are_rejected() {
+        let mut pipeline = pipeline("Hi");
+        assert!(pipeline.update_command("launch".to_string()).is_err());
+        assert!(pipeline.update_target_id("xyz".to_string()).is_err());
+        assert!(pipeline.update_message("x".repeat(crate::payload::MAX_PAYLOAD_BYTES + 1)).is_err());
+        assert!(pipeline.tx_queue().is_empty());
+    }
+
+    #[test]
+    fn controller_messages_are_queued_and_reported() {
+        let controller = RealtimeController::new(RuntimeParams::new(&UserConfig::default(), None));
+        let mut pipeline = pipeline("Hi");
+        pipeline.attach_controller(controller.clone());
+        assert_eq!(controller.get_snapshot().unwrap().message.as_deref(), Some("Hi"));
+
+        controller.set_message("from controller".to_string()).unwrap();
+        let output = pipeline.process_chunk(&[]);
+        assert_eq!(output.transmitting.message, "from controller");
+        let params = controller.get_snapshot().unwrap();
+        assert!(params.queued_messages.is_empty());
+        assert_eq!(params.message.as_deref(), Some("from controller"));
+    }
+
+    #[test]
+    fn controller_queue_is_bounded() {
+        let controller = RealtimeController::new(RuntimeParams::new(&UserConfig::default(), None));
+        for i in 0..RuntimeParams::MAX_QUEUED_MESSAGES {
+            controller.set_message(format!("message {i}")).unwrap();
+        }
+        assert!(controller.set_message("one too many".to_string()).is_err());
+
+        // An attached pipeline drains the queue
+        let mut pipeline = pipeline("Hi");
+        pipeline.attach_controller(controller.clone());
+        pipeline.process_chunk(&[]);
+        controller.set_message("fits again".to_string()).unwrap();
+    }
+
+    #[test]
+    fn controller_failures_are_reported() {
+        let controller = RealtimeController::new(RuntimeParams::new(&UserConfig::default(), None));
+        let handle = controller.get_handle();
+        // Poison the parameter lock
+        std::thread::spawn(move || {
+            let _params = handle.write().unwrap();
+            panic!("poisoning the controller");
+        })
+        .join()
+        .unwrap_err();
+
+        let mut pipeline = pipeline("Hi");
+        pipeline.attach_controller(controller);
+        let output = pipeline.process_chunk(&[]);
+        assert_eq!(output.tx_errors.len(), 2, "{:?}", output.tx_errors);
+        assert!(output.tx_errors[0].starts_with("Controller update failed"));
+        assert!(output.tx_errors[1].starts_with("Controller queue unavailable"));
+    }
+}
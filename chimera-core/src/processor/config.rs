@@ -1,5 +1,6 @@
 //! Configuration types for the Chimera processor
 
+use crate::config::{AddressFilter, ChannelParams, InternalProtocolConfig, LDPCConfig};
 use crate::logging::LogConfig;
 
 /// Configuration for the Chimera processor
@@ -9,6 +10,20 @@ pub struct ProcessorConfig {
     pub symbol_rate: usize,
     pub carrier_freq: f64,
     
+    /// Frame layout, sync word, addressing and frame counters (its carrier
+    /// frequency and symbol rate are replaced by the fields above)
+    pub protocol: InternalProtocolConfig,
+    
+    /// Forward error correction code and decoder settings
+    pub ldpc: LDPCConfig,
+    
+    /// Target IDs the receiver delivers frames for
+    pub address_filter: AddressFilter,
+    
+    /// Noise (Es/N0) and loss added to the audio between modulator and
+    /// demodulator (`None` is a clean channel)
+    pub channel: Option<ChannelParams>,
+    
     /// Logging configuration
     pub logging: LogConfig,
     
@@ -23,6 +38,10 @@ impl Default for ProcessorConfig {
             sample_rate: 48000,
             symbol_rate: 16,
             carrier_freq: 12000.0,
+            protocol: InternalProtocolConfig::default(),
+            ldpc: LDPCConfig::default(),
+            address_filter: AddressFilter::default(),
+            channel: None,
             logging: LogConfig::default(),
             optimize_for_latency: true,
             min_chunk_size: None,
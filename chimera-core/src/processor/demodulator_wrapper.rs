@@ -4,7 +4,7 @@
 //! without modifying it.
 
 use crate::signal_processing::demodulation::{
-    DemodulationConfig, DemodulationResult, audio_to_symbols_with_snr
+    DemodulationConfig, DemodulationResult, audio_to_frames_with_snr, audio_to_symbols_with_snr
 };
 
 /// Wrapper around existing demodulator (DO NOT modify the actual demodulator)
@@ -13,18 +13,8 @@ pub struct DemodulatorWrapper {
 }
 
 impl DemodulatorWrapper {
-    pub fn new(
-        sample_rate: usize,
-        symbol_rate: usize,
-        carrier_freq: f64,
-    ) -> Self {
-        Self {
-            config: DemodulationConfig {
-                sample_rate,
-                symbol_rate,
-                carrier_freq,
-            },
-        }
+    pub fn new(config: DemodulationConfig) -> Self {
+        Self { config }
     }
     
     /// Convert audio signal to symbols
@@ -34,4 +24,9 @@ impl DemodulatorWrapper {
         // Use the existing demodulation function - DO NOT reimplement
         audio_to_symbols_with_snr(audio, &self.config)
     }
+    
+    /// Convert audio carrying a burst of frames to symbols, one result per frame
+    pub fn demodulate_frames(&self, audio: &[f32]) -> Vec<DemodulationResult> {
+        audio_to_frames_with_snr(audio, &self.config)
+    }
 }
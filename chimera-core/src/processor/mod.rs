@@ -11,24 +11,34 @@ pub mod output;
 pub mod modulator_wrapper;
 pub mod demodulator_wrapper;
 
+use std::borrow::Cow;
+use std::sync::Arc;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
+use rand_distr::{Distribution, Normal};
 
 use crate::encoder::StreamingFrameEncoder;
 use crate::decoder::StreamingSymbolDecoder;
-use crate::utils::string_to_bitstream;
-use crate::config::InternalProtocolConfig;
-use crate::ldpc::LDPCSuite;
+use crate::payload;
+use crate::config::{ChannelParams, InternalProtocolConfig, OuterCode};
+use crate::errors::{ConfigError, Result};
+use crate::protocol::CommandType;
+use crate::signal_processing::demodulation::DemodulationConfig;
+use crate::fec::{build_codec, check_codeword_fits, FecCodec};
+use crate::ldpc::DecodeOutcome;
 use crate::logging::SignalLogger;
 
 pub use config::{ProcessorConfig};
-pub use output::{ProcessorOutput, BatchOutput};
+pub use output::{ProcessorOutput, BatchOutput, FrameStatus};
 use modulator_wrapper::ModulatorWrapper;
 use demodulator_wrapper::DemodulatorWrapper;
 
 /// Batch processing result with diagnostics
 #[derive(Clone, Debug)]
 pub struct BatchResult {
+    /// Recovered payload bytes, exactly as long as the transmitted payload when decoding succeeds
+    pub recovered_payload: Vec<u8>,
+    /// The recovered payload as (lossy) UTF-8 text
     pub recovered_message: String,
     pub pre_fec_ber: f64,
     pub post_fec_ber: f64,
@@ -39,6 +49,17 @@ pub struct BatchResult {
     pub rx_symbols: Vec<num_complex::Complex<f64>>,
     pub snr_db: f32,
     pub success: bool,
+    /// FEC decode outcome of the last frame received (syndrome weights, corrected bits, iterations)
+    pub decode_outcome: Option<DecodeOutcome>,
+    /// Status of every frame of the message, in frame order
+    pub frames: Vec<FrameStatus>,
+    /// Frames ignored because they were addressed to another receiver
+    pub foreign_frames: usize,
+    /// Input bytes dropped because the message would need more frames than
+    /// the frame counters can number; `success` is false when any were
+    pub truncated_bytes: usize,
+    /// Why the message was not delivered intact, if it was not
+    pub error: Option<String>,
 }
 
 /// The canonical Chimera data processor
@@ -54,8 +75,8 @@ pub struct ChimeraProcessor {
     modulator: ModulatorWrapper,
     demodulator: DemodulatorWrapper,
     
-    // LDPC encoding/decoding
-    ldpc_suite: LDPCSuite,
+    // FEC encoding/decoding
+    codec: Arc<dyn FecCodec>,
     protocol: InternalProtocolConfig,
     
     // Decoder state (maintained across calls for incremental processing)
@@ -76,17 +97,31 @@ pub struct ChimeraProcessor {
 }
 
 impl ChimeraProcessor {
-    pub fn new(config: ProcessorConfig) -> Self {
-        // Create protocol config from processor config - use default and override
-        let mut protocol = InternalProtocolConfig::default();
+    /// Builds a processor for `config`
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`build_codec`] error if the FEC configuration does not fit
+    /// the frame layout (e.g. a repetition factor of 0), and
+    /// [`ConfigError::InvalidFrameLayout`] for an outer code: the processor
+    /// places and reassembles frames itself and does not apply one.
+    pub fn new(config: ProcessorConfig) -> Result<Self> {
+        if config.ldpc.outer_code != OuterCode::None {
+            return Err(ConfigError::InvalidFrameLayout {
+                reason: format!("the processor does not apply an outer code ({:?}); the realtime pipeline does", config.ldpc.outer_code),
+            }.into());
+        }
+        
+        // Create protocol config from processor config - rates override the protocol's
+        let mut protocol = config.protocol.clone();
         protocol.carrier_freq_hz = config.carrier_freq;
         protocol.qpsk_symbol_rate = config.symbol_rate;
         protocol.enable_qpsk = true; // QPSK is part of the spec, always enabled
         protocol.enable_fsk = true; // FSK is part of the spec, always enabled
         
-        // Create LDPC suite
-        let ldpc_config = crate::config::LDPCConfig::default();
-        let ldpc_suite = LDPCSuite::new(&protocol.frame_layout, &ldpc_config);
+        // Create the FEC codec selected by the config
+        let codec = build_codec(&protocol.frame_layout, &config.ldpc)?;
+        check_codeword_fits(&protocol.frame_layout, codec.as_ref())?;
         
         // Create modulator/demodulator wrappers
         let modulator = ModulatorWrapper::new(
@@ -95,20 +130,16 @@ impl ChimeraProcessor {
             config.carrier_freq,
         );
         
-        let demodulator = DemodulatorWrapper::new(
-            config.sample_rate,
-            config.symbol_rate,
-            config.carrier_freq,
-        );
+        let demodulator = DemodulatorWrapper::new(DemodulationConfig::from_protocol(&protocol, config.sample_rate));
         
         // Create logger from config
         let logger = SignalLogger::new(config.logging.clone());
         
-        Self {
+        Ok(Self {
             config,
             modulator,
             demodulator,
-            ldpc_suite,
+            codec,
             protocol,
             decoder: None,
             input_buffer: Vec::new(),
@@ -116,11 +147,11 @@ impl ChimeraProcessor {
             rng: StdRng::seed_from_u64(42),
             diagnostics_enabled: false,
             logger,
-        }
+        })
     }
     
     pub fn new_with_defaults() -> Self {
-        Self::new(ProcessorConfig::default())
+        Self::new(ProcessorConfig::default()).expect("default FEC configuration fits the default layout")
     }
     
     pub fn enable_diagnostics(&mut self) {
@@ -154,7 +185,7 @@ impl ChimeraProcessor {
         let input_copy = self.input_buffer.clone();
         self.input_buffer.clear();
         
-        self.process_internal(&input_copy, true)
+        self.process_internal(&input_copy, true, false)
     }
     
     /// Reset processor state (reusable for new streams)
@@ -173,7 +204,7 @@ impl ChimeraProcessor {
         if self.input_buffer.len() >= min_bytes {
             // Extract what we can process
             let to_process = self.input_buffer.drain(..).collect::<Vec<_>>();
-            self.process_internal(&to_process, false)
+            self.process_internal(&to_process, false, false)
         } else {
             ProcessorOutput::empty()
         }
@@ -183,63 +214,62 @@ impl ChimeraProcessor {
     /// 
     /// This follows the proven pattern from generate_audio_batch and run_simulation
     /// 
-    /// SINGLE FRAME ONLY: Messages longer than one frame are truncated.
-    fn process_internal(&mut self, input: &[u8], _is_flush: bool) -> ProcessorOutput {
+    /// The input is sent as a length-prefixed payload (see [`crate::payload`])
+    /// split into as many frames as it needs. Each frame's command field
+    /// carries its index and the frame count, which the receiver uses to put
+    /// the decoded frames back in order.
+    ///
+    /// Unless the input is a `retransmission` of the previous one, the
+    /// decoder's chase combiner is cleared first: frames of a new message
+    /// can carry the same header as undecoded frames of the last one.
+    fn process_internal(&mut self, input: &[u8], _is_flush: bool, retransmission: bool) -> ProcessorOutput {
         if input.is_empty() {
             return ProcessorOutput::empty();
         }
         
-        // TRUNCATE to single frame: max bytes = message_bits / 8
-        let max_bytes = self.ldpc_suite.matrices.message_bits / 8;
-        let truncated_input = if input.len() > max_bytes {
-            &input[..max_bytes]
-        } else {
-            input
-        };
+        // The frame counters limit how many frames one message can span
+        let max_bytes = (self.protocol.max_frames_per_message() * self.data_bits_per_frame() / 8)
+            .saturating_sub(payload::LENGTH_FIELD_BYTES)
+            .min(payload::MAX_PAYLOAD_BYTES);
+        let truncated_input = &input[..input.len().min(max_bytes)];
+        let truncated_bytes = input.len() - truncated_input.len();
         
-        // Convert input bytes to string then to bit stream (following existing pattern)
-        let message = String::from_utf8_lossy(truncated_input);
-        let payload_bits = string_to_bitstream(&message);
+        let payload_bits = payload::encode(truncated_input)
+            .expect("payload truncated to the length field's range");
         
         if self.diagnostics_enabled {
-            eprintln!("[PROCESSOR] Input: {:?} (truncated from {} to {} bytes)", 
-                     message, input.len(), truncated_input.len());
+            eprintln!("[PROCESSOR] Input: {} of {} bytes", truncated_input.len(), input.len());
             eprintln!("[PROCESSOR] Payload bits: {} bits", payload_bits.len());
         }
         
-        // Create encoder (following generate_audio_batch pattern)
-        let mut encoder = StreamingFrameEncoder::new(
-            &payload_bits,
-            self.protocol.clone(),
-            self.ldpc_suite.matrices.clone(),
-        );
+        // Create encoder (following generate_audio_batch pattern); the codec
+        // was checked against the frame layout when the processor was built
+        let new_encoder = || {
+            StreamingFrameEncoder::with_codec(&payload_bits, self.protocol.clone(), self.codec.clone())
+                .expect("codec checked against the frame layout")
+        };
+        let mut encoder = new_encoder();
+        let total_frames = encoder.total_frames;
         
         if self.diagnostics_enabled {
-            eprintln!("[PROCESSOR] Total frames: {} (SINGLE FRAME MODE)", encoder.total_frames);
+            eprintln!("[PROCESSOR] Total frames: {}", total_frames);
         }
         
-        // Generate TWO frames worth of symbols for stable receiver lock
-        // The first frame acts as a training sequence for AGC and Gardner loops.
-        // The second frame is received under stable, locked conditions, ensuring
-        // the correlator can find a complete frame well away from buffer boundaries.
-        let total_symbols = self.protocol.frame_layout.total_symbols; // 128 symbols per frame
-        let (frame1_symbols, _, _, _, _) = encoder.get_next_symbols(total_symbols);
-        
-        // Reset encoder to generate the same frame again (for test purposes)
-        encoder = StreamingFrameEncoder::new(
-            &payload_bits,
-            self.protocol.clone(),
-            self.ldpc_suite.matrices.clone(),
-        );
-        let (frame2_symbols, _, _, _, _) = encoder.get_next_symbols(total_symbols);
+        // Send a copy of frame 0 ahead of the message as a training sequence
+        // for the AGC and Gardner loops, so the correlator finds every message
+        // frame under stable, locked conditions.
+        let total_symbols = self.protocol.frame_layout.total_symbols;
+        let (training_symbols, _, _, _, _) = encoder.get_next_symbols(total_symbols);
         
-        // Concatenate both frames
-        let mut tx_symbols = Vec::new();
-        tx_symbols.extend_from_slice(&frame1_symbols);
-        tx_symbols.extend_from_slice(&frame2_symbols);
+        // Restart the encoder so the message frames follow in order
+        encoder = new_encoder();
+        let (message_symbols, _, _, _, _) = encoder.get_next_symbols(total_frames * total_symbols);
+        
+        let mut tx_symbols = training_symbols;
+        tx_symbols.extend_from_slice(&message_symbols);
         
         if self.diagnostics_enabled {
-            eprintln!("[PROCESSOR] TX symbols: {} symbols (2 frames with preambles)", tx_symbols.len());
+            eprintln!("[PROCESSOR] TX symbols: {} symbols (training frame + {} frames)", tx_symbols.len(), total_frames);
         }
         
         // Modulate symbols to audio using wrapper (calls existing modulator)
@@ -249,37 +279,141 @@ impl ChimeraProcessor {
             eprintln!("[PROCESSOR] Audio samples: {} samples", audio.len());
         }
         
-        // Demodulate audio to symbols using wrapper (RETURNS FULL FRAME)
-        let demod_result = self.demodulator.demodulate(&audio);
-        let rx_symbols = demod_result.symbols;
+        // Simulated channel between the modulator and demodulator
+        let received_audio = match self.config.channel.clone() {
+            Some(channel) => Cow::Owned(self.apply_channel(&audio, &channel)),
+            None => Cow::Borrowed(&audio[..]),
+        };
+        
+        // Demodulate audio to symbols using wrapper (one full frame per result)
+        let demodulated = self.demodulator.demodulate_frames(&received_audio);
+        let rx_symbols: Vec<_> = demodulated.iter().flat_map(|frame| frame.symbols.iter().copied()).collect();
+        let snr_db = if demodulated.is_empty() {
+            0.0
+        } else {
+            demodulated.iter().map(|frame| frame.snr_db).sum::<f32>() / demodulated.len() as f32
+        };
         
         if self.diagnostics_enabled {
-            eprintln!("[PROCESSOR] RX symbols: {} symbols (full frame including preamble)", rx_symbols.len());
-            eprintln!("[PROCESSOR] SNR: {} dB", demod_result.snr_db);
+            eprintln!("[PROCESSOR] RX frames: {} ({} symbols including preambles)", demodulated.len(), rx_symbols.len());
+            eprintln!("[PROCESSOR] SNR: {} dB", snr_db);
         }
         
         // Initialize decoder if needed (maintain state for incremental processing)
         if self.decoder.is_none() {
-            self.decoder = Some(StreamingSymbolDecoder::new(
-                self.protocol.clone(),
-                self.ldpc_suite.matrices.clone(),
-            ));
+            self.decoder = Some(
+                StreamingSymbolDecoder::with_codec(self.protocol.clone(), self.codec.clone())
+                    .expect("codec checked against the frame layout")
+                    .with_chase_combining(self.config.ldpc.combining_depth)
+                    .with_address_filter(self.config.address_filter.clone()),
+            );
         }
         
+        let frame_payload_bytes = self.data_bits_per_frame().div_ceil(8);
         let decoder = self.decoder.as_mut().unwrap();
+        if !retransmission {
+            decoder.reset_combining();
+        }
+        let message_bits = self.codec.message_bits();
+        let mut frames: Vec<FrameStatus> = (0..total_frames)
+            .map(|index| FrameStatus::missing(index, message_bits))
+            .collect();
+        // Missing frames stay zero-filled so later frames keep their offsets
+        let mut payloads: Vec<Vec<u8>> = vec![vec![0; frame_payload_bytes]; total_frames];
+        let mut decode_outcome = None;
+        let mut combining_depth = 0;
+        let mut foreign_frames = 0;
+        
+        for frame in &demodulated {
+            // Decode synchronized symbols (NO SYNC SEARCH - demod already did it)
+            let frame_bytes = decoder.process_synchronized_symbols(&frame.symbols);
+            let header = decoder.last_frame_header().cloned();
+            // Frames are placed by their counters alone; one whose header does
+            // not belong to this message has nowhere to go
+            let index = header
+                .as_ref()
+                .filter(|header| header.total_frames == total_frames && header.frame_index < total_frames)
+                .map(|header| header.frame_index);
+            
+            let outcome = match (&header, decoder.last_decode_outcome()) {
+                (Some(header), _) if header.foreign => {
+                    foreign_frames += 1;
+                    Err(format!("frame addressed to another receiver ({})", header.target_id_hex))
+                }
+                (_, None) => Err(format!("frame could not be decoded from {} symbols", frame.symbols.len())),
+                (_, Some(outcome)) => Ok(outcome.clone()),
+            };
+            let outcome = match outcome {
+                Ok(outcome) => outcome,
+                Err(error) => {
+                    if let Some(index) = index.filter(|&index| !frames[index].received) {
+                        frames[index] = FrameStatus::undecoded(index, message_bits, error, header);
+                    }
+                    continue;
+                }
+            };
+            
+            let Some(index) = index.filter(|&index| !frames[index].success) else {
+                continue;
+            };
+            
+            // Measure FEC performance against what was actually transmitted
+            let tx_message = encoder.frame_message(index);
+            let tx_codeword = self.codec.encode(&tx_message);
+            let error = match outcome.clone().into_result() {
+                Err(err) => Some(err.to_string()),
+                Ok(_) => decoder.last_frame_error().map(ToString::to_string),
+            };
+            frames[index] = FrameStatus {
+                index,
+                received: true,
+                success: outcome.success && error.is_none() && !frame_bytes.is_empty(),
+                pre_fec_errors: count_bit_errors(&tx_codeword, &outcome.channel_codeword),
+                post_fec_errors: count_bit_errors(&tx_message, &outcome.message_bits),
+                combining_depth: decoder.combining_depth(),
+                error,
+                header,
+            };
+            if !frame_bytes.is_empty() {
+                payloads[index] = frame_bytes;
+            }
+            decode_outcome = Some(outcome);
+            combining_depth = decoder.combining_depth();
+        }
         
-        // Decode synchronized symbols (NO SYNC SEARCH - demod already did it)
-        let decoded_bytes = decoder.process_synchronized_symbols(&rx_symbols);
+        // Reassemble the message in frame order and cut the payload out of it
+        let message = payloads.concat();
+        let (decoded_bytes, payload_error) = match payload::decode(&message) {
+            Ok(bytes) => (bytes.to_vec(), None),
+            Err(err) => (payload::decode_partial(&message).to_vec(), Some(err.to_string())),
+        };
+        let pre_fec_errors = frames.iter().map(|frame| frame.pre_fec_errors).sum();
+        let post_fec_errors = frames.iter().map(|frame| frame.post_fec_errors).sum();
+        let truncation_error = (truncated_bytes > 0).then(|| {
+            format!("input truncated to {} of {} bytes: a message spans at most {} frames",
+                    truncated_input.len(), input.len(), self.protocol.max_frames_per_message())
+        });
+        let success = truncation_error.is_none()
+            && payload_error.is_none()
+            && frames.iter().all(|frame| frame.success);
+        let error = truncation_error
+            .or_else(|| frames
+                .iter()
+                .find_map(|frame| frame.error.as_ref().map(|err| format!("frame {}/{}: {err}", frame.index + 1, total_frames))))
+            .or(payload_error);
         
         if self.diagnostics_enabled {
+            for frame in &frames {
+                eprintln!("[PROCESSOR] Frame {}/{}: received={} success={} pre-FEC errors={} post-FEC errors={}",
+                         frame.index + 1, total_frames, frame.received, frame.success,
+                         frame.pre_fec_errors, frame.post_fec_errors);
+            }
             eprintln!("[PROCESSOR] Decoded bytes: {} bytes", decoded_bytes.len());
             if !decoded_bytes.is_empty() {
                 eprintln!("[PROCESSOR] Decoded string: {:?}", String::from_utf8_lossy(&decoded_bytes));
             }
         }
         
-        let success = !decoded_bytes.is_empty();
-        
         // Extract logs
         let logs: Vec<String> = self.logger.entries()
             .iter()
@@ -292,77 +426,126 @@ impl ChimeraProcessor {
             tx_symbols,
             rx_symbols,
             audio,
-            snr_db: demod_result.snr_db,
+            snr_db,
             success,
-            error: None,
+            decode_outcome,
+            combining_depth,
+            pre_fec_errors,
+            post_fec_errors,
+            frames,
+            foreign_frames,
+            truncated_bytes,
+            error,
             logs,
         }
     }
     
+    /// Attenuate `audio` by the link loss and add white Gaussian noise at the channel's Es/N0
+    fn apply_channel(&mut self, audio: &[f32], channel: &ChannelParams) -> Vec<f32> {
+        let gain = 10f64.powf(-channel.link_loss_db / 20.0);
+        let power = audio.iter().map(|&sample| (sample as f64 * gain).powi(2)).sum::<f64>()
+            / audio.len().max(1) as f64;
+        // A passband signal of power P carries Es = P / Rs per symbol, and white
+        // noise of variance σ² at fs samples/s has N0 = 2σ² / fs
+        let samples_per_symbol = self.config.sample_rate as f64 / self.config.symbol_rate as f64;
+        let snr_linear = 10f64.powf(channel.snr_db / 10.0);
+        let noise = Normal::new(0.0, (power * samples_per_symbol / (2.0 * snr_linear)).sqrt())
+            .expect("finite channel noise level");
+        audio
+            .iter()
+            .map(|&sample| (sample as f64 * gain + noise.sample(&mut self.rng)) as f32)
+            .collect()
+    }
+    
+    /// The configuration this processor was built from
+    pub fn config(&self) -> &ProcessorConfig {
+        &self.config
+    }
+    
+    /// Most payload bytes a message can carry and still fit in one frame
+    pub fn frame_payload_bytes(&self) -> usize {
+        (self.data_bits_per_frame() / 8).saturating_sub(payload::LENGTH_FIELD_BYTES)
+    }
+    
+    /// Command sent in the frames of subsequent messages
+    pub fn set_command(&mut self, command: CommandType) {
+        self.protocol.command = command.as_str().to_string();
+    }
+    
+    /// Payload bits per frame, excluding the frame checksum
+    fn data_bits_per_frame(&self) -> usize {
+        self.codec
+            .message_bits()
+            .saturating_sub(self.protocol.frame_layout.checksum.bits())
+    }
+    
     /// Get minimum frame size in bytes
     fn min_frame_size(&self) -> usize {
         // Each frame carries message_bits of data
-        let bits_per_frame = self.ldpc_suite.matrices.message_bits;
+        let bits_per_frame = self.codec.message_bits();
         (bits_per_frame + 7) / 8 // Convert to bytes, rounding up
     }
     
-    /// Process a complete message in batch mode (for CLI and tests)
+    /// Process a complete text message in batch mode (for CLI and tests)
     /// 
-    /// This is the recommended interface for non-streaming applications.
-    /// It processes the entire message at once and returns complete diagnostics.
+    /// Convenience wrapper around [`Self::process_batch_bytes`] for UTF-8 text.
     pub fn process_batch(&mut self, message: &str) -> BatchResult {
         if self.diagnostics_enabled {
             eprintln!("[PROCESSOR] Batch mode: processing message '{}'", message);
         }
-        
-        // Convert message to bytes
-        let input = message.as_bytes();
-        
+        self.process_batch_bytes(message.as_bytes())
+    }
+    
+    /// Process a complete binary payload in batch mode
+    /// 
+    /// This is the recommended interface for non-streaming applications.
+    /// It processes the entire payload at once and returns complete diagnostics.
+    pub fn process_batch_bytes(&mut self, input: &[u8]) -> BatchResult {
+        self.batch_bytes(input, false)
+    }
+    
+    /// Send `input` again as a retransmission of the previous batch
+    ///
+    /// Like [`Self::process_batch_bytes`], except that frames the decoder
+    /// failed to decode last time are chase-combined with their new copies.
+    pub fn retransmit_batch_bytes(&mut self, input: &[u8]) -> BatchResult {
+        self.batch_bytes(input, true)
+    }
+    
+    fn batch_bytes(&mut self, input: &[u8], retransmission: bool) -> BatchResult {
         // Process through the pipeline
-        let output = self.process_internal(input, true);
+        let output = self.process_internal(input, true, retransmission);
         
-        // Calculate BER from original vs decoded
+        // BER measured by the processor against the transmitted codeword and message
         let original_bytes = input;
         let decoded_bytes = &output.decoded_bytes;
-        
-        let comparison_length = decoded_bytes.len().min(original_bytes.len());
-        
-        let post_fec_errors = if comparison_length > 0 {
-            original_bytes[..comparison_length]
-                .iter()
-                .zip(decoded_bytes[..comparison_length].iter())
-                .map(|(a, b)| (a ^ b).count_ones() as usize)
-                .sum()
+        let pre_fec_errors = output.pre_fec_errors;
+        let post_fec_errors = output.post_fec_errors;
+        
+        let received_frames = output.frames.iter().filter(|frame| frame.received).count();
+        let (pre_fec_ber, post_fec_ber) = if received_frames > 0 {
+            (
+                pre_fec_errors as f64 / (self.codec.codeword_bits() * received_frames) as f64,
+                post_fec_errors as f64 / (self.codec.message_bits() * output.frames.len()) as f64,
+            )
         } else {
-            0
+            (1.0, 1.0) // Complete failure if no frame decoded
         };
         
-        let post_fec_ber = if comparison_length > 0 {
-            post_fec_errors as f64 / (comparison_length * 8) as f64
-        } else {
-            1.0 // Complete failure if no bytes decoded
-        };
-        
-        // Estimate pre-FEC BER (before error correction)
-        // This would ideally come from comparing pre-LDPC bits, but we can estimate
-        // from SNR or use post-FEC as lower bound
-        let pre_fec_ber = post_fec_ber * 1.5; // Rough estimate
-        let pre_fec_errors = (pre_fec_ber * (comparison_length * 8) as f64) as usize;
-        
-        let recovered_message = String::from_utf8_lossy(&output.decoded_bytes)
-            .trim_end_matches('\0')
-            .to_string();
+        let recovered_message = String::from_utf8_lossy(decoded_bytes).into_owned();
         
         if self.diagnostics_enabled {
             eprintln!("[PROCESSOR] Batch complete:");
             eprintln!("  Original: {} bytes", original_bytes.len());
             eprintln!("  Decoded: {} bytes", decoded_bytes.len());
             eprintln!("  Recovered: '{}'", recovered_message);
+            eprintln!("  Pre-FEC BER: {:.6}", pre_fec_ber);
             eprintln!("  Post-FEC BER: {:.6}", post_fec_ber);
             eprintln!("  Post-FEC errors: {}", post_fec_errors);
         }
         
         BatchResult {
+            recovered_payload: output.decoded_bytes,
             recovered_message,
             pre_fec_ber,
             post_fec_ber,
@@ -372,7 +555,12 @@ impl ChimeraProcessor {
             tx_symbols: output.tx_symbols,
             rx_symbols: output.rx_symbols,
             snr_db: output.snr_db,
-            success: output.success && !decoded_bytes.is_empty(),
+            success: output.success,
+            decode_outcome: output.decode_outcome,
+            frames: output.frames,
+            foreign_frames: output.foreign_frames,
+            truncated_bytes: output.truncated_bytes,
+            error: output.error,
         }
     }
     
@@ -395,6 +583,10 @@ impl ChimeraProcessor {
     }
 }
 
+fn count_bit_errors(expected: &[u8], actual: &[u8]) -> usize {
+    expected.iter().zip(actual).filter(|(a, b)| a != b).count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,6 +597,33 @@ mod tests {
         assert!(!processor.diagnostics_enabled);
     }
     
+    #[test]
+    fn test_processor_rejects_invalid_fec_configuration() {
+        let config = ProcessorConfig {
+            ldpc: crate::config::LDPCConfig {
+                fec: crate::config::FecScheme::Repetition { factor: 0 },
+                ..Default::default()
+            },
+            ..ProcessorConfig::default()
+        };
+        assert!(ChimeraProcessor::new(config).is_err());
+    }
+    
+    #[test]
+    fn test_processor_rejects_outer_code() {
+        let config = ProcessorConfig {
+            ldpc: crate::config::LDPCConfig {
+                outer_code: OuterCode::ReedSolomon { parity_frames: 2 },
+                ..Default::default()
+            },
+            ..ProcessorConfig::default()
+        };
+        assert!(matches!(
+            ChimeraProcessor::new(config),
+            Err(crate::errors::ChimeraError::Config(ConfigError::InvalidFrameLayout { .. }))
+        ));
+    }
+    
     #[test]
     fn test_processor_reset() {
         let mut processor = ChimeraProcessor::new_with_defaults();
@@ -412,4 +631,228 @@ mod tests {
         processor.reset();
         assert!(processor.input_buffer.is_empty());
     }
+    
+    /// Processor sending over a noisy channel; its noise is seeded, so every
+    /// run sees the same errors
+    fn processor_at_snr(snr_db: f64) -> ChimeraProcessor {
+        ChimeraProcessor::new(ProcessorConfig {
+            channel: Some(ChannelParams {
+                snr_db,
+                ..ChannelParams::default()
+            }),
+            ..ProcessorConfig::default()
+        })
+        .unwrap()
+    }
+    
+    /// Last received frame of a clean "Hello" with every sixth symbol of the
+    /// first half of its payload rotated: too damaged to decode alone, not
+    /// enough to outvote a clean copy. Its header is what the processor's
+    /// decoder parses.
+    fn failed_hello_frame(processor: &mut ChimeraProcessor) -> Vec<num_complex::Complex64> {
+        let result = processor.process_batch("Hello");
+        assert!(result.success);
+        let layout = processor.protocol.frame_layout.clone();
+        let frame_start = result.rx_symbols.len() - layout.total_symbols;
+        let mut symbols = result.rx_symbols[frame_start..].to_vec();
+        let payload = layout.sync_symbols + layout.target_id_symbols + layout.command_type_symbols;
+        for symbol in symbols[payload..payload + layout.data_payload_symbols / 2].iter_mut().step_by(6) {
+            *symbol *= num_complex::Complex64::i();
+        }
+        symbols
+    }
+    
+    #[test]
+    fn test_new_batch_is_not_combined_with_a_failed_one() {
+        let mut processor = ChimeraProcessor::new_with_defaults();
+        let symbols = failed_hello_frame(&mut processor);
+        
+        // Message A fails twice and stays in the combiner; B has the same
+        // frame counters, target and command
+        let decoder = processor.decoder.as_mut().unwrap();
+        for _ in 0..2 {
+            decoder.process_synchronized_symbols(&symbols);
+            assert!(!decoder.last_decode_outcome().unwrap().success);
+        }
+        assert_eq!(decoder.combining_depth(), 2);
+        
+        let result = processor.process_batch("World");
+        assert!(result.success, "{:?}", result.frames);
+        assert_eq!(result.recovered_message, "World");
+        assert_eq!(result.frames[0].combining_depth, 1);
+    }
+    
+    #[test]
+    fn test_retransmission_is_combined_with_the_failed_copy() {
+        let mut processor = ChimeraProcessor::new_with_defaults();
+        let symbols = failed_hello_frame(&mut processor);
+        let decoder = processor.decoder.as_mut().unwrap();
+        decoder.reset_combining();
+        decoder.process_synchronized_symbols(&symbols);
+        assert!(!decoder.last_decode_outcome().unwrap().success);
+        
+        // The retransmitted copy is combined with the failed one
+        let result = processor.retransmit_batch_bytes(b"Hello");
+        assert!(result.success, "{:?}", result.frames);
+        assert_eq!(result.recovered_message, "Hello");
+        assert_eq!(result.frames[0].combining_depth, 2);
+    }
+    
+    #[test]
+    fn test_batch_reports_measured_fec_counts() {
+        // Enough noise for a few channel errors, all of which the code corrects
+        let mut processor = processor_at_snr(30.0);
+        let result = processor.process_batch("Hello");
+        
+        assert!(result.success, "frames: {:?}", result.frames);
+        assert_eq!(result.recovered_message, "Hello");
+        let outcome = result.decode_outcome.expect("frame should reach the decoder");
+        let codeword_bits = processor.codec.codeword_bits();
+        let message_bits = processor.codec.message_bits();
+        
+        // Counts are measured, so the BERs follow from them exactly
+        assert_eq!(result.pre_fec_ber, result.pre_fec_errors as f64 / codeword_bits as f64);
+        assert_eq!(result.post_fec_ber, result.post_fec_errors as f64 / message_bits as f64);
+        assert_eq!(outcome.channel_codeword.len(), codeword_bits);
+        
+        // The decoder flipped exactly the bits the channel corrupted
+        assert!(outcome.bits_flipped > 0);
+        assert!(outcome.syndrome_weight_before > 0);
+        assert_eq!(outcome.syndrome_weight_after, 0);
+        assert_eq!(result.pre_fec_errors, outcome.bits_flipped);
+        assert_eq!(result.post_fec_errors, 0);
+    }
+    
+    #[test]
+    fn test_batch_never_reports_failed_syndrome_as_success() {
+        // Too many channel errors for the code to correct
+        let mut processor = processor_at_snr(20.0);
+        let result = processor.process_batch("Hello");
+        
+        let outcome = result.decode_outcome.expect("frame should reach the decoder");
+        assert!(!outcome.success);
+        assert!(outcome.syndrome_weight_after > 0);
+        assert!(!result.success);
+        assert!(result.post_fec_errors > 0);
+    }
+    
+    #[test]
+    fn test_batch_spans_multiple_frames() {
+        let mut processor = ChimeraProcessor::new_with_defaults();
+        let message = "A message far longer than the sixteen bytes one frame carries";
+        let result = processor.process_batch(message);
+        
+        let expected_frames = (payload::LENGTH_FIELD_BYTES + message.len())
+            .div_ceil(processor.data_bits_per_frame() / 8);
+        assert_eq!(result.frames.len(), expected_frames);
+        for (index, frame) in result.frames.iter().enumerate() {
+            assert_eq!(frame.index, index);
+            assert!(frame.received, "frame {index} not received");
+        }
+        let frame_symbols = processor.protocol.frame_layout.total_symbols;
+        assert_eq!(result.tx_symbols.len(), (expected_frames + 1) * frame_symbols);
+        assert_eq!(result.rx_symbols.len(), expected_frames * frame_symbols);
+        
+        // Frames are reassembled in order, each contributing its full payload
+        assert!(result.success, "frames: {:?}", result.frames);
+        assert_eq!(result.recovered_message, message);
+    }
+    
+    #[test]
+    fn test_frame_headers_carry_their_counters() {
+        let long = "A message far longer than the sixteen bytes one frame carries";
+        for message in ["Hello", long] {
+            let mut processor = ChimeraProcessor::new_with_defaults();
+            let result = processor.process_batch(message);
+            let opcode = processor.protocol.get_command_opcode();
+            
+            // Every frame, the first included, is placed by its own header
+            assert!(result.success, "{message}: {:?}", result.frames);
+            let total_frames = result.frames.len();
+            for (index, frame) in result.frames.iter().enumerate() {
+                let header = frame.header.as_ref().expect("decoded frame has a header");
+                assert_eq!(header.frame_index, index, "{message}");
+                assert_eq!(header.total_frames, total_frames, "{message}");
+                assert_eq!(header.command_opcode, opcode, "{message}");
+            }
+        }
+    }
+    
+    #[test]
+    fn test_frame_checksums_round_trip() {
+        use crate::config::FrameChecksum;
+        
+        for checksum in [FrameChecksum::Crc16Ccitt, FrameChecksum::Crc32] {
+            let mut config = ProcessorConfig {
+                channel: Some(ChannelParams {
+                    snr_db: 30.0,
+                    ..ChannelParams::default()
+                }),
+                ..ProcessorConfig::default()
+            };
+            config.protocol.frame_layout.checksum = checksum;
+            let mut processor = ChimeraProcessor::new(config).unwrap();
+            let result = processor.process_batch("Hello");
+            
+            // The checksum covers the header, so it only matches if the
+            // header decoded as sent
+            assert!(result.success, "{checksum:?}: {:?}", result.frames);
+            assert_eq!(result.recovered_message, "Hello");
+            assert!(result.frames.iter().all(|frame| frame.error.is_none()), "{checksum:?}: {:?}", result.frames);
+        }
+    }
+    
+    #[test]
+    fn test_overlong_input_is_reported_as_truncated() {
+        let mut processor = ChimeraProcessor::new(ProcessorConfig {
+            protocol: InternalProtocolConfig {
+                max_frames: 2,
+                ..InternalProtocolConfig::default()
+            },
+            ..ProcessorConfig::default()
+        })
+        .unwrap();
+        let message = "A message far longer than the two frames it is allowed";
+        let result = processor.process_batch(message);
+        
+        // The frames that were sent still decode, but the message is cut short
+        assert_eq!(result.frames.len(), 2);
+        assert!(result.frames.iter().all(|frame| frame.success), "frames: {:?}", result.frames);
+        let max_bytes = 2 * processor.data_bits_per_frame() / 8 - payload::LENGTH_FIELD_BYTES;
+        assert_eq!(result.truncated_bytes, message.len() - max_bytes);
+        assert_eq!(result.recovered_message, message[..max_bytes]);
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("truncated"));
+    }
+    
+    #[test]
+    fn test_foreign_frames_are_reported() {
+        let mut processor = ChimeraProcessor::new(ProcessorConfig {
+            address_filter: crate::config::AddressFilter::exact(["0x12345678"]),
+            ..ProcessorConfig::default()
+        })
+        .unwrap();
+        let result = processor.process_batch("Hello");
+        
+        // The frame arrived, so it is not reported as missing
+        assert!(!result.success);
+        assert_eq!(result.foreign_frames, 1);
+        let frame = &result.frames[0];
+        assert!(frame.received && !frame.success);
+        assert_eq!(frame.error.as_deref(), Some("frame addressed to another receiver (DEADBEEF)"));
+        assert!(frame.header.as_ref().is_some_and(|header| header.foreign));
+    }
+    
+    #[test]
+    fn test_batch_bytes_keeps_binary_payload_length() {
+        for payload in [&[0xFF, 0x00, 0x10, 0x00, 0x00][..], &[0x00; 6], &[0xFF; 6]] {
+            let result = processor_at_snr(30.0).process_batch_bytes(payload);
+            
+            // Length field and payload share one frame
+            assert_eq!(result.frames.len(), 1);
+            // Trailing zeros survive and the frame padding does not
+            assert!(result.success, "{payload:02X?}: {:?}", result.frames);
+            assert_eq!(result.recovered_payload, payload);
+        }
+    }
 }
@@ -2,6 +2,9 @@
 
 use num_complex::Complex64;
 
+use crate::diagnostics::FrameDescriptor;
+use crate::ldpc::DecodeOutcome;
+
 /// Output from processor operations
 #[derive(Clone, Debug)]
 pub struct ProcessorOutput {
@@ -26,6 +29,28 @@ pub struct ProcessorOutput {
     /// Success flag
     pub success: bool,
     
+    /// LDPC decode outcome for the last frame received
+    pub decode_outcome: Option<DecodeOutcome>,
+    
+    /// Copies of the frame chase-combined into `decode_outcome`
+    pub combining_depth: usize,
+    
+    /// Codeword bits received in error before FEC (measured against the TX codewords)
+    pub pre_fec_errors: usize,
+    
+    /// Message bits still in error after FEC (measured against the TX messages)
+    pub post_fec_errors: usize,
+    
+    /// Status of every frame of the message, in frame order
+    pub frames: Vec<FrameStatus>,
+    
+    /// Frames ignored because they were addressed to another receiver
+    pub foreign_frames: usize,
+    
+    /// Input bytes dropped because the message would need more frames than
+    /// the frame counters can number
+    pub truncated_bytes: usize,
+    
     /// Error message if any
     pub error: Option<String>,
     
@@ -43,6 +68,13 @@ impl ProcessorOutput {
             audio: Vec::new(),
             snr_db: 0.0,
             success: false,
+            decode_outcome: None,
+            combining_depth: 0,
+            pre_fec_errors: 0,
+            post_fec_errors: 0,
+            frames: Vec::new(),
+            foreign_frames: 0,
+            truncated_bytes: 0,
             error: None,
             logs: Vec::new(),
         }
@@ -53,6 +85,59 @@ impl ProcessorOutput {
     }
 }
 
+/// Receive status of one frame of a message
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameStatus {
+    /// Position of the frame in the message
+    pub index: usize,
+    
+    /// Whether the frame was demodulated and reached the decoder, decoded or not
+    pub received: bool,
+    
+    /// Whether the frame decoded cleanly and passed its checksum
+    pub success: bool,
+    
+    /// Codeword bits received in error before FEC
+    pub pre_fec_errors: usize,
+    
+    /// Message bits still in error after FEC
+    pub post_fec_errors: usize,
+    
+    /// Copies soft-combined for the frame's decode (0 if not decoded)
+    pub combining_depth: usize,
+    
+    /// Why the frame was lost, if it was
+    pub error: Option<String>,
+    
+    /// Header parsed from the received frame
+    pub header: Option<FrameDescriptor>,
+}
+
+impl FrameStatus {
+    /// Status of a frame that never reached the decoder
+    pub fn missing(index: usize, message_bits: usize) -> Self {
+        Self {
+            index,
+            post_fec_errors: message_bits,
+            error: Some("frame not received".to_string()),
+            ..Self::default()
+        }
+    }
+    
+    /// Status of a frame that was received but never got as far as FEC
+    /// decoding, e.g. because it was addressed to another receiver
+    pub fn undecoded(index: usize, message_bits: usize, error: String, header: Option<FrameDescriptor>) -> Self {
+        Self {
+            index,
+            received: true,
+            post_fec_errors: message_bits,
+            error: Some(error),
+            header,
+            ..Self::default()
+        }
+    }
+}
+
 /// Batch processing output with additional metadata
 #[derive(Clone, Debug)]
 pub struct BatchOutput {
@@ -42,6 +42,10 @@ pub enum CommandType {
     Reset,
     /// Data transfer operation
     DataTransfer,
+    /// Positive acknowledgement of a received frame
+    Ack,
+    /// Negative acknowledgement: a frame arrived but could not be decoded
+    Nack,
 }
 
 impl CommandType {
@@ -54,6 +58,8 @@ impl CommandType {
             CommandType::GetParameter => 0x0004,
             CommandType::Reset => 0x0005,
             CommandType::DataTransfer => 0x0006,
+            CommandType::Ack => 0x0007,
+            CommandType::Nack => 0x0008,
         }
     }
     
@@ -66,6 +72,8 @@ impl CommandType {
             0x0004 => Some(CommandType::GetParameter),
             0x0005 => Some(CommandType::Reset),
             0x0006 => Some(CommandType::DataTransfer),
+            0x0007 => Some(CommandType::Ack),
+            0x0008 => Some(CommandType::Nack),
             _ => None,
         }
     }
@@ -79,6 +87,8 @@ impl CommandType {
             CommandType::GetParameter => "get_parameter",
             CommandType::Reset => "reset",
             CommandType::DataTransfer => "data_transfer",
+            CommandType::Ack => "ack",
+            CommandType::Nack => "nack",
         }
     }
     
@@ -91,6 +101,8 @@ impl CommandType {
             "get_parameter" => Some(CommandType::GetParameter),
             "reset" => Some(CommandType::Reset),
             "data_transfer" => Some(CommandType::DataTransfer),
+            "ack" => Some(CommandType::Ack),
+            "nack" => Some(CommandType::Nack),
             _ => None,
         }
     }
@@ -133,8 +145,9 @@ impl PhysicalLayer {
 
 /// Frame structure layout in QPSK symbols
 /// 
-/// These define the bit-level structure of each transmitted frame.
-/// Fixed for protocol compatibility.
+/// These define the bit-level structure of the default frame. The encoder,
+/// decoder and demodulator follow the runtime [`crate::config::FrameLayout`]
+/// and sync word, which default to these values.
 pub struct FrameLayout;
 
 impl FrameLayout {
@@ -53,21 +53,30 @@ pub fn symbols_to_carrier_signal(
     }
     
     // --- Step 3: Simple lowpass filter for bandwidth limiting (~20 Hz) ---
-    // Using a simple moving average filter (much faster than Butterworth)
-    let bandwidth_hz = 20.0;
+    // Using a simple moving average filter (much faster than Butterworth).
+    // Faster symbol rates widen it so the window stays shorter than a symbol.
+    let bandwidth_hz = (1.25 * config.symbol_rate as f64).max(20.0);
     let filter_len = ((config.sample_rate as f64 / bandwidth_hz) as usize).max(3) | 1; // Make odd
     let mut phase_smoothed = vec![0.0; num_samples];
     
+    // Running sums of sin/cos make each window sum O(1)
+    let mut prefix_sin = Vec::with_capacity(num_samples + 1);
+    let mut prefix_cos = Vec::with_capacity(num_samples + 1);
+    let (mut acc_sin, mut acc_cos) = (0.0, 0.0);
+    prefix_sin.push(acc_sin);
+    prefix_cos.push(acc_cos);
+    for &phase in &phase_raw {
+        acc_sin += phase.sin();
+        acc_cos += phase.cos();
+        prefix_sin.push(acc_sin);
+        prefix_cos.push(acc_cos);
+    }
+    
     for i in 0..num_samples {
         let start = i.saturating_sub(filter_len / 2);
         let end = (i + filter_len / 2 + 1).min(num_samples);
-        let mut sum_sin = 0.0;
-        let mut sum_cos = 0.0;
-        
-        for j in start..end {
-            sum_sin += phase_raw[j].sin();
-            sum_cos += phase_raw[j].cos();
-        }
+        let sum_sin = prefix_sin[end] - prefix_sin[start];
+        let sum_cos = prefix_cos[end] - prefix_cos[start];
         
         // Preserve phase continuity by converting through sin/cos
         phase_smoothed[i] = sum_sin.atan2(sum_cos);
@@ -0,0 +1,310 @@
+//! Sync words and their correlation properties.
+//!
+//! The correlator in the demodulator searches for the differentially encoded
+//! sync field, so a sync word's correlation properties are those of the
+//! transmitted symbols, not of the raw bits. The structured families here
+//! (Barker, m-sequence, Zadoff–Chu) are therefore designed as symbol
+//! sequences, one chip per symbol, and the sync field bits are chosen so
+//! the differential encoder reproduces them exactly.
+
+use num_complex::Complex64;
+
+use crate::config::SyncWord;
+use crate::encoder::phase_to_gray;
+use crate::errors::{ConfigError, Result};
+use crate::protocol::QPSKConstellation;
+use crate::utils::hex_to_bitstream;
+
+/// Barker codes by length
+const BARKER_CODES: [&[i8]; 7] = [
+    &[1, -1],
+    &[1, 1, -1],
+    &[1, 1, -1, 1],
+    &[1, 1, 1, -1, 1],
+    &[1, 1, 1, -1, -1, 1, -1],
+    &[1, 1, 1, -1, -1, -1, 1, -1, -1, 1, -1],
+    &[1, 1, 1, 1, 1, -1, -1, 1, 1, -1, 1, -1, 1],
+];
+
+/// Feedback taps of a primitive polynomial for each LFSR degree from 2
+const M_SEQUENCE_TAPS: [&[usize]; 15] = [
+    &[2, 1],
+    &[3, 2],
+    &[4, 3],
+    &[5, 3],
+    &[6, 5],
+    &[7, 6],
+    &[8, 6, 5, 4],
+    &[9, 5],
+    &[10, 7],
+    &[11, 9],
+    &[12, 6, 4, 1],
+    &[13, 4, 3, 1],
+    &[14, 5, 3, 1],
+    &[15, 14],
+    &[16, 15, 13, 4],
+];
+
+/// Longest m-sequence available, in symbols
+pub const MAX_M_SEQUENCE_SYMBOLS: usize = (1 << 16) - 1;
+
+/// Barker code of `length` chips, if one exists
+pub fn barker(length: usize) -> Option<&'static [i8]> {
+    BARKER_CODES.iter().copied().find(|code| code.len() == length)
+}
+
+/// One period of the maximal-length sequence of an LFSR of `degree` (2 to 16)
+pub fn m_sequence(degree: usize) -> Vec<i8> {
+    let taps = M_SEQUENCE_TAPS[degree - 2];
+    let mut register = vec![1u8; degree];
+    (0..(1 << degree) - 1)
+        .map(|_| {
+            let output = register[degree - 1];
+            let feedback = taps.iter().fold(0, |acc, &tap| acc ^ register[tap - 1]);
+            register.rotate_right(1);
+            register[0] = feedback;
+            if output == 1 { -1 } else { 1 }
+        })
+        .collect()
+}
+
+/// Zadoff–Chu sequence of `length` and `root`, as QPSK phase indices
+///
+/// Each chip's phase is rounded to the nearest constellation point, so the
+/// result is a polyphase-like QPSK word rather than an exact CAZAC sequence.
+pub fn zadoff_chu_phases(length: usize, root: usize) -> Vec<u8> {
+    let (n_total, u) = (length as f64, root as f64);
+    (0..length)
+        .map(|n| {
+            let n = n as f64;
+            let exponent = if length % 2 == 1 { n * (n + 1.0) } else { n * n };
+            let phase = -std::f64::consts::PI * u * exponent / n_total;
+            // Phase index k sits at 45° + k * 90°
+            let k = ((phase - std::f64::consts::FRAC_PI_4) / std::f64::consts::FRAC_PI_2).round();
+            k.rem_euclid(4.0) as u8
+        })
+        .collect()
+}
+
+/// Check `word` can fill a sync field of `sync_symbols`
+pub fn validate(word: &SyncWord, sync_sequence_hex: &str, sync_symbols: usize) -> Result<()> {
+    let invalid = |reason: String| Err(ConfigError::InvalidFrameLayout { reason }.into());
+    match *word {
+        SyncWord::Hex => {
+            // The sync word is left-padded with zeros to fill the sync field
+            let sync_bits = sync_symbols * 2;
+            let hex = sync_sequence_hex;
+            if sync_bits == 0
+                || !sync_bits.is_multiple_of(8)
+                || hex.is_empty()
+                || hex.len() % 2 != 0
+                || hex.len() * 4 > sync_bits
+                || !hex.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return invalid(format!("Sync word {hex:?} does not fit a {sync_bits}-bit sync field"));
+            }
+        }
+        SyncWord::Barker => {
+            if barker(sync_symbols).is_none() {
+                return invalid(format!(
+                    "No Barker code of {sync_symbols} symbols (lengths are 2, 3, 4, 5, 7, 11 and 13)"
+                ));
+            }
+        }
+        SyncWord::MSequence => {
+            if !(3..=MAX_M_SEQUENCE_SYMBOLS).contains(&sync_symbols) {
+                return invalid(format!(
+                    "m-sequence sync fields hold 3 to {MAX_M_SEQUENCE_SYMBOLS} symbols, not {sync_symbols}"
+                ));
+            }
+        }
+        SyncWord::ZadoffChu { root } => {
+            if sync_symbols < 2 || root == 0 || root >= sync_symbols || gcd(root, sync_symbols) != 1 {
+                return invalid(format!(
+                    "Zadoff-Chu root {root} must be coprime to and below the sync length {sync_symbols}"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// QPSK phase indices of the transmitted sync field
+///
+/// For [`SyncWord::Hex`] these follow from differentially encoding the hex
+/// bits; the other families are defined by their phases. Assumes `word`
+/// passed [`validate`].
+pub fn sync_phases(word: &SyncWord, sync_sequence_hex: &str, sync_symbols: usize) -> Vec<u8> {
+    let from_chips = |chips: &[i8]| chips.iter().map(|&chip| if chip > 0 { 0 } else { 2 }).collect();
+    match *word {
+        SyncWord::Hex => {
+            let bits = hex_to_bitstream(sync_sequence_hex, sync_symbols * 2);
+            let mut phase = 0u8;
+            bits.chunks(2)
+                .map(|pair| {
+                    let point = QPSKConstellation::bits_to_symbol(pair[0], pair[1]);
+                    // Differential encoding adds the data phase to the previous phase
+                    phase = (phase + phase_index(point)) & 0x03;
+                    phase
+                })
+                .collect()
+        }
+        SyncWord::Barker => from_chips(barker(sync_symbols).unwrap_or_default()),
+        SyncWord::MSequence => {
+            let degree = (2..=16).find(|&degree| (1 << degree) > sync_symbols).unwrap_or(16);
+            from_chips(&m_sequence(degree)[..sync_symbols])
+        }
+        SyncWord::ZadoffChu { root } => zadoff_chu_phases(sync_symbols, root),
+    }
+}
+
+/// Constellation phase index (0 at 45°, counting 90° steps) of a QPSK point
+fn phase_index(point: Complex64) -> u8 {
+    let k = ((point.arg() - std::f64::consts::FRAC_PI_4) / std::f64::consts::FRAC_PI_2).round();
+    k.rem_euclid(4.0) as u8
+}
+
+/// Sync field bits whose differential encoding transmits `phases`
+///
+/// Inverts the encoder's differential step from its reference phase 0.
+pub fn phases_to_bits(phases: &[u8]) -> Vec<u8> {
+    let mut previous = 0u8;
+    phases
+        .iter()
+        .flat_map(|&phase| {
+            let (b0, b1) = phase_to_gray(phase.wrapping_sub(previous) & 0x03);
+            previous = phase;
+            [b0, b1]
+        })
+        .collect()
+}
+
+/// Raw (pre-differential-encoding) bits of the sync field
+pub fn sync_field_bits(word: &SyncWord, sync_sequence_hex: &str, sync_symbols: usize) -> Vec<u8> {
+    match word {
+        SyncWord::Hex => hex_to_bitstream(sync_sequence_hex, sync_symbols * 2),
+        _ => phases_to_bits(&sync_phases(word, sync_sequence_hex, sync_symbols)),
+    }
+}
+
+/// Aperiodic autocorrelation summary of a sync template
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SidelobeReport {
+    /// Correlation magnitude at zero lag
+    pub peak: f64,
+    /// Largest correlation magnitude at a nonzero lag
+    pub max_sidelobe: f64,
+    /// Lag, in symbols, of the largest sidelobe
+    pub worst_lag: usize,
+    /// Peak-to-sidelobe ratio in dB (infinite without sidelobes)
+    pub pslr_db: f64,
+}
+
+/// Peak-to-sidelobe ratio of the aperiodic autocorrelation of `template`
+///
+/// A partial overlap of the preamble with itself at lag `k` is what the
+/// correlator sees `k` symbols off the true frame start, so a low PSLR means
+/// off-by-`k` locks under noise.
+pub fn sidelobe_report(template: &[Complex64]) -> SidelobeReport {
+    let correlation = |lag: usize| -> f64 {
+        template[lag..]
+            .iter()
+            .zip(template)
+            .map(|(late, early)| late * early.conj())
+            .sum::<Complex64>()
+            .norm()
+    };
+    let peak = if template.is_empty() { 0.0 } else { correlation(0) };
+    let (worst_lag, max_sidelobe) = (1..template.len())
+        .map(|lag| (lag, correlation(lag)))
+        .fold((0, 0.0), |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+    SidelobeReport {
+        peak,
+        max_sidelobe,
+        worst_lag,
+        pslr_db: 20.0 * (peak / max_sidelobe).log10(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::differential_encode_bits;
+    use crate::signal_processing::demodulation::generate_sync_template;
+
+    fn template(word: SyncWord, hex: &str, symbols: usize) -> Vec<Complex64> {
+        generate_sync_template(&sync_field_bits(&word, hex, symbols))
+    }
+
+    #[test]
+    fn m_sequences_have_full_period() {
+        for degree in 2..=16 {
+            let sequence = m_sequence(degree);
+            // A maximal sequence is balanced: one more -1 than +1
+            let ones = sequence.iter().filter(|&&chip| chip < 0).count();
+            assert_eq!(ones, 1 << (degree - 1), "degree {degree}");
+            // Its periodic autocorrelation is -1 at every nonzero shift
+            let shifted: i64 = (0..sequence.len())
+                .map(|i| (sequence[i] * sequence[(i + 1) % sequence.len()]) as i64)
+                .sum();
+            assert_eq!(shifted, -1, "degree {degree}");
+        }
+    }
+
+    #[test]
+    fn structured_words_are_transmitted_exactly() {
+        for word in [SyncWord::Barker, SyncWord::MSequence, SyncWord::ZadoffChu { root: 5 }] {
+            let phases = sync_phases(&word, "", 13);
+            let encoded = differential_encode_bits(&phases_to_bits(&phases));
+            let sent: Vec<u8> = encoded
+                .chunks(2)
+                .map(|pair| phase_index(QPSKConstellation::bits_to_symbol(pair[0], pair[1])))
+                .collect();
+            assert_eq!(sent, phases, "{word:?}");
+        }
+    }
+
+    #[test]
+    fn hex_phases_match_the_template() {
+        let phases = sync_phases(&SyncWord::Hex, "A5A5A5A5", 16);
+        let template = template(SyncWord::Hex, "A5A5A5A5", 16);
+        let expected: Vec<u8> = template.iter().map(|&point| phase_index(point)).collect();
+        assert_eq!(phases, expected);
+    }
+
+    #[test]
+    fn barker_13_reaches_its_ideal_pslr() {
+        let report = sidelobe_report(&template(SyncWord::Barker, "", 13));
+        assert!((report.peak - 13.0).abs() < 1e-9);
+        assert!((report.max_sidelobe - 1.0).abs() < 1e-9);
+        assert!((report.pslr_db - 20.0 * 13f64.log10()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn structured_words_beat_the_periodic_hex_word() {
+        let hex = sidelobe_report(&template(SyncWord::Hex, "A5A5A5A5", 16));
+        for word in [SyncWord::MSequence, SyncWord::ZadoffChu { root: 3 }] {
+            let report = sidelobe_report(&template(word, "", 16));
+            assert!(
+                report.pslr_db > hex.pslr_db + 3.0,
+                "{word:?}: {:.1} dB vs hex {:.1} dB",
+                report.pslr_db,
+                hex.pslr_db
+            );
+        }
+    }
+
+    #[test]
+    fn lengths_each_family_cannot_fill_are_rejected() {
+        assert!(validate(&SyncWord::Barker, "", 16).is_err());
+        assert!(validate(&SyncWord::Barker, "", 11).is_ok());
+        assert!(validate(&SyncWord::MSequence, "", 2).is_err());
+        assert!(validate(&SyncWord::ZadoffChu { root: 4 }, "", 16).is_err());
+        assert!(validate(&SyncWord::ZadoffChu { root: 3 }, "", 16).is_ok());
+        assert!(validate(&SyncWord::Hex, "A5A5A5A5A5", 16).is_err());
+    }
+}
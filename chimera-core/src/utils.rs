@@ -161,7 +161,12 @@ impl fmt::Display for LogCollector {
 }
 
 pub fn string_to_bitstream(text: &str) -> Vec<u8> {
-    text.as_bytes()
+    bytes_to_bitstream(text.as_bytes())
+}
+
+/// Expands bytes into bits, MSB first (inverse of [`pack_bits`])
+pub fn bytes_to_bitstream(bytes: &[u8]) -> Vec<u8> {
+    bytes
         .iter()
         .flat_map(|byte| (0..8).rev().map(move |bit| (byte >> bit) & 1))
         .collect()
@@ -55,7 +55,7 @@ fn test_evm_calculation() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"EVM test message";
@@ -107,7 +107,7 @@ fn test_snr_estimation() {
         let protocol = ProtocolConfig::default();
         let ldpc = LDPCConfig::default();
         
-        let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+        let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
         pipeline.set_modulation_mode(true);
         
         let test_data = b"SNR estimation test";
@@ -150,7 +150,7 @@ fn test_diagnostic_output_format() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"Format test";
@@ -191,7 +191,7 @@ fn test_frame_layout_info() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"Frame layout test";
@@ -226,7 +226,7 @@ fn test_fsk_state_reporting() {
     protocol.enable_fsk = true; // Ensure FSK is enabled
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"FSK state test";
@@ -24,7 +24,7 @@ fn test_extreme_snr_conditions() {
     let mut sim_low = SimulationConfig::default();
     sim_low.snr_db = -10.0;
     
-    let mut pipeline_low = RealtimePipeline::new(sim_low, protocol.clone(), ldpc.clone());
+    let mut pipeline_low = RealtimePipeline::new(sim_low, protocol.clone(), ldpc.clone()).unwrap();
     pipeline_low.set_modulation_mode(true);
     
     let test_data = b"Low SNR test";
@@ -44,7 +44,7 @@ fn test_extreme_snr_conditions() {
     let mut sim_high = SimulationConfig::default();
     sim_high.snr_db = 30.0;
     
-    let mut pipeline_high = RealtimePipeline::new(sim_high, protocol.clone(), ldpc.clone());
+    let mut pipeline_high = RealtimePipeline::new(sim_high, protocol.clone(), ldpc.clone()).unwrap();
     pipeline_high.set_modulation_mode(true);
     
     let output_high = pipeline_high.process_chunk(test_data);
@@ -229,7 +229,7 @@ fn test_empty_input_handling() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     println!("Empty Input Handling Test:");
@@ -260,7 +260,7 @@ fn test_rapid_reconfiguration() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone());
+    let mut pipeline = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone()).unwrap();
     
     println!("Rapid Reconfiguration Test:");
     
@@ -15,7 +15,7 @@ fn test_frame_sync_acquisition_time() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"Sync acquisition test message";
@@ -65,7 +65,7 @@ fn test_sync_status_consistency() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"Sync stability test";
@@ -109,7 +109,7 @@ fn test_lock_status_reporting() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"Lock status test";
@@ -147,7 +147,7 @@ fn test_symbol_timing_with_noise() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"Timing recovery under noise";
@@ -183,7 +183,7 @@ fn test_frequency_offset_tracking() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"Frequency tracking test";
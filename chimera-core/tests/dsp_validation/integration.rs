@@ -21,7 +21,7 @@ fn test_complete_transmission_chain() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     println!("Complete Transmission Chain Test:");
@@ -93,7 +93,7 @@ fn test_ber_vs_snr_curve() {
         sim.bypass_thz_simulation = true;
         sim.plaintext_source = "BER test message".to_string();
         
-        let mut pipeline = RealtimePipeline::new(sim, protocol.clone(), ldpc.clone());
+        let mut pipeline = RealtimePipeline::new(sim, protocol.clone(), ldpc.clone()).unwrap();
         pipeline.set_modulation_mode(true);
         
         // Process multiple chunks to accumulate BER statistics
@@ -151,7 +151,7 @@ fn test_graceful_degradation_with_noise() {
         sim.bypass_thz_simulation = true;
         sim.plaintext_source = "Degradation test".to_string();
         
-        let mut pipeline = RealtimePipeline::new(sim, protocol.clone(), ldpc.clone());
+        let mut pipeline = RealtimePipeline::new(sim, protocol.clone(), ldpc.clone()).unwrap();
         pipeline.set_modulation_mode(true);
         
         // Process a few chunks
@@ -189,7 +189,7 @@ fn test_long_running_stability() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     println!("Long Running Stability Test:");
@@ -243,7 +243,7 @@ fn test_mode_switching_stability() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     
     println!("Mode Switching Stability Test:");
     
@@ -292,7 +292,7 @@ fn test_unified_pipeline_flexibility() {
     
     // Pattern 1: Process many small iterations (real-time mode)
     println!("Pattern 1: Many small iterations (simulated real-time)");
-    let mut pipeline1 = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone());
+    let mut pipeline1 = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone()).unwrap();
     pipeline1.set_modulation_mode(true);
     
     let mut decoded1 = String::new();
@@ -315,7 +315,7 @@ fn test_unified_pipeline_flexibility() {
     
     // Pattern 2: Process fewer larger iterations (batch-like mode)
     println!("\nPattern 2: Fewer iterations (simulated batch)");
-    let mut pipeline2 = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone());
+    let mut pipeline2 = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone()).unwrap();
     pipeline2.set_modulation_mode(true);
     
     let mut decoded2 = String::new();
@@ -17,7 +17,7 @@ fn test_ldpc_error_correction_at_high_snr() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"LDPC high SNR test - should decode perfectly";
@@ -66,7 +66,7 @@ fn test_ldpc_correction_capability() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"LDPC correction test at moderate SNR";
@@ -110,7 +110,7 @@ fn test_ldpc_ber_improvement() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"BER improvement test";
@@ -157,7 +157,7 @@ fn test_ldpc_frame_decode_success_rate() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"Frame decode success test";
@@ -193,7 +193,7 @@ fn test_ldpc_configuration_parameters() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let test_data = b"LDPC config test";
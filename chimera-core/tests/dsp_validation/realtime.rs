@@ -17,7 +17,7 @@ fn test_chunk_boundary_continuity() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let mut all_audio = Vec::new();
@@ -67,7 +67,7 @@ fn test_variable_chunk_sizes() {
     
     // Test several iterations - pipeline doesn't directly control chunk size
     // but should handle any processing pattern
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let mut outputs = Vec::new();
@@ -113,7 +113,7 @@ fn test_streaming_consistency() {
     println!("Streaming Consistency Test:");
     
     // Run 1: Process in small iterations
-    let mut pipeline1 = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone());
+    let mut pipeline1 = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone()).unwrap();
     pipeline1.set_modulation_mode(true);
     let mut decoded1 = String::new();
     
@@ -128,7 +128,7 @@ fn test_streaming_consistency() {
     }
     
     // Run 2: Process differently
-    let mut pipeline2 = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline2 = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline2.set_modulation_mode(true);
     let mut decoded2 = String::new();
     
@@ -159,7 +159,7 @@ fn test_pipeline_latency() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     #[cfg(not(target_arch = "wasm32"))]
@@ -218,7 +218,7 @@ fn test_pipeline_state_consistency() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     println!("Pipeline State Consistency Test:");
@@ -260,7 +260,7 @@ fn test_diagnostic_output_continuity() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     println!("Diagnostic Output Continuity Test:");
@@ -25,6 +25,7 @@ fn raman_whisper_protocol() -> ProtocolConfig {
             command_type_symbols: 16,
             data_payload_symbols: 64,
             ecc_symbols: 16,
+            ..chimera_core::config::FrameLayout::default()
         },
     }
 }
@@ -34,6 +35,7 @@ fn raman_whisper_ldpc() -> LDPCConfig {
         dv: 2,
         dc: 10,
         seed: Some(42),
+        ..LDPCConfig::default()
     }
 }
 
@@ -115,7 +117,7 @@ fn test_streaming_vs_batch_consistency() {
     let batch_result = run_simulation(&sim, &protocol, &ldpc);
     
     // Run through pipeline directly (streaming)
-    let mut pipeline = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone());
+    let mut pipeline = RealtimePipeline::new(sim.clone(), protocol.clone(), ldpc.clone()).unwrap();
     pipeline.set_modulation_mode(true);
     
     let mut streaming_audio = Vec::new();
@@ -210,7 +212,7 @@ fn debug_pipeline_decode_failure() {
     sim.plaintext_source = message.to_string();
     sim.snr_db = 100.0; // Perfect channel
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol.clone(), ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol.clone(), ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let dummy_input = b"";
@@ -273,7 +275,7 @@ fn debug_sync_detection_details() {
         .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1))
         .collect();
     
-    let mut encoder = StreamingFrameEncoder::new(&message_bits, protocol.clone(), matrices.clone());
+    let mut encoder = StreamingFrameEncoder::new(&message_bits, protocol.clone(), matrices.clone()).unwrap();
     
     // Get transmitted symbols (one complete frame)
     let symbols_per_frame = protocol.frame_layout.total_symbols;
@@ -296,7 +298,7 @@ fn debug_sync_detection_details() {
     }
     
     // Now try to decode with perfect symbols (no noise)
-    let mut decoder = StreamingSymbolDecoder::new(protocol.clone(), matrices);
+    let mut decoder = StreamingSymbolDecoder::new(protocol.clone(), matrices).unwrap();
     
     println!("\n=== RECEPTION (NO NOISE) ===");
     
@@ -378,7 +380,7 @@ fn debug_full_pipeline_sync() {
         .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1))
         .collect();
     
-    let mut encoder = StreamingFrameEncoder::new(&message_bits, protocol.clone(), matrices.clone());
+    let mut encoder = StreamingFrameEncoder::new(&message_bits, protocol.clone(), matrices.clone()).unwrap();
     
     // Get transmitted symbols (one complete frame)
     let symbols_per_frame = protocol.frame_layout.total_symbols;
@@ -420,6 +422,7 @@ fn debug_full_pipeline_sync() {
         sample_rate,
         symbol_rate: protocol.qpsk_symbol_rate as usize,
         carrier_freq: protocol.carrier_freq_hz,
+        ..DemodulationConfig::default()
     };
     
     let rx_symbols = audio_to_symbols(&noisy_audio, &demodulation_config);
@@ -503,7 +506,7 @@ fn debug_full_pipeline_sync() {
     
     // Try decoding
     println!("\n=== STEP 6: DECODING ===");
-    let mut decoder = StreamingSymbolDecoder::new(protocol.clone(), matrices);
+    let mut decoder = StreamingSymbolDecoder::new(protocol.clone(), matrices).unwrap();
     let (decoded_bits, frame_complete, _frame_idx, _symbols_in_frame, _diagnostics) = 
         decoder.process_symbols(&rx_symbols);
     
@@ -585,7 +588,7 @@ fn test_minimum_audio_for_decode() {
     sim.plaintext_source = message.to_string();
     sim.snr_db = 100.0;
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol, ldpc).unwrap();
     pipeline.set_modulation_mode(true);
     
     let dummy_input = b"";
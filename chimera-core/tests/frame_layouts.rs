@@ -0,0 +1,108 @@
+//! Frame layouts other than the default must survive the whole stack
+//!
+//! Each test runs one of the web presets' layouts and sync words, or one of
+//! the sync word families, through the processor: the encoder builds the frames, the demodulator must find every
+//! preamble and slice frames of the configured length, and the decoder must
+//! see the configured frame counters.
+
+use chimera_core::config::{ChannelParams, FrameLayout, InternalProtocolConfig, LDPCConfig, SyncWord};
+use chimera_core::processor::{ChimeraProcessor, ProcessorConfig};
+
+fn run_layout(protocol: InternalProtocolConfig, ldpc: LDPCConfig, message: &str) {
+    protocol.validate().expect("preset protocol should be valid");
+    let layout = protocol.frame_layout.clone();
+    let config = ProcessorConfig {
+        symbol_rate: protocol.qpsk_symbol_rate,
+        carrier_freq: protocol.carrier_freq_hz,
+        protocol,
+        ldpc,
+        // The processor seeds its channel noise, so every run sees the same errors
+        channel: Some(ChannelParams {
+            snr_db: 50.0,
+            ..ChannelParams::default()
+        }),
+        ..ProcessorConfig::default()
+    };
+    let mut processor = ChimeraProcessor::new(config).unwrap();
+    let result = processor.process_batch(message);
+
+    assert!(result.frames.len() > 1, "message should span several frames");
+    for frame in &result.frames {
+        assert!(frame.received, "frame {} not found by the demodulator", frame.index);
+    }
+    assert_eq!(result.tx_symbols.len(), (result.frames.len() + 1) * layout.total_symbols);
+    assert_eq!(result.rx_symbols.len(), result.frames.len() * layout.total_symbols);
+    assert!(result.success, "frames: {:?}", result.frames);
+    assert_eq!(result.recovered_message, message);
+}
+
+#[test]
+fn raman_whisper_layout() {
+    run_layout(
+        InternalProtocolConfig::default(),
+        LDPCConfig::default(),
+        "Baseline frames for terrestrial operations",
+    );
+}
+
+#[test]
+fn burst_telemetry_layout() {
+    let protocol = InternalProtocolConfig {
+        qpsk_symbol_rate: 32,
+        qpsk_bandwidth_hz: 28.0,
+        frame_layout: FrameLayout {
+            total_symbols: 96,
+            sync_symbols: 24,
+            target_id_symbols: 16,
+            command_type_symbols: 8,
+            data_payload_symbols: 40,
+            ecc_symbols: 8,
+            ..FrameLayout::default()
+        },
+        sync_sequence_hex: "7E7E7E7E".into(),
+        target_id_hex: "CAFEBABE".into(),
+        max_frames: 64,
+        current_frame_shift: 4,
+        total_frames_shift: 10,
+        ..InternalProtocolConfig::default()
+    };
+    let ldpc = LDPCConfig { dv: 3, dc: 9, ..LDPCConfig::default() };
+    run_layout(protocol, ldpc, "Telemetry burst payload");
+}
+
+#[test]
+fn deep_space_probe_layout() {
+    let protocol = InternalProtocolConfig {
+        qpsk_symbol_rate: 12,
+        qpsk_bandwidth_hz: 18.0,
+        fsk_bit_rate: 0.5,
+        fsk_freq_zero_hz: 11_998.4,
+        fsk_freq_one_hz: 12_001.6,
+        frame_layout: FrameLayout {
+            total_symbols: 256,
+            sync_symbols: 32,
+            target_id_symbols: 32,
+            command_type_symbols: 32,
+            data_payload_symbols: 96,
+            ecc_symbols: 64,
+            ..FrameLayout::default()
+        },
+        sync_sequence_hex: "55AA55AA".into(),
+        target_id_hex: "0D15EA5E".into(),
+        max_frames: 32,
+        current_frame_shift: 20,
+        total_frames_shift: 28,
+        ..InternalProtocolConfig::default()
+    };
+    let ldpc = LDPCConfig { dv: 2, dc: 12, seed: Some(1337), ..LDPCConfig::default() };
+    run_layout(protocol, ldpc, "Deep-space probe telemetry with reinforced parity");
+}
+
+#[test]
+fn zadoff_chu_sync_word() {
+    let protocol = InternalProtocolConfig {
+        sync_word: SyncWord::ZadoffChu { root: 1 },
+        ..InternalProtocolConfig::default()
+    };
+    run_layout(protocol, LDPCConfig::default(), "Frames found by a Zadoff-Chu preamble");
+}
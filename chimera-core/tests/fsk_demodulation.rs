@@ -116,7 +116,7 @@ fn test_fsk_demodulation_alternating_pattern() {
         &payload_bits,
         protocol.clone(),
         ldpc_suite.matrices.clone(),
-    );
+    ).unwrap();
     
     // Generate symbols for a full frame (128 symbols = 8 seconds at 16 sym/s)
     let (tx_symbols, _, _, _, _) = encoder.get_next_symbols(128);
@@ -133,7 +133,7 @@ fn test_fsk_demodulation_alternating_pattern() {
     let rx_symbols = audio_to_symbols(&audio, sample_rate, symbol_rate, 12000.0);
     
     // Process through decoder (which includes FSK demodulation)
-    let mut decoder = StreamingSymbolDecoder::new(protocol, ldpc_suite.matrices);
+    let mut decoder = StreamingSymbolDecoder::new(protocol, ldpc_suite.matrices).unwrap();
     
     // Process symbols in chunks to trigger multiple FSK updates
     for chunk in rx_symbols.chunks(16) {
@@ -166,7 +166,7 @@ fn test_fsk_frequency_detection_binary_0() {
         &payload_bits,
         protocol.clone(),
         ldpc_suite.matrices.clone(),
-    );
+    ).unwrap();
     
     // Generate 32 symbols (2 seconds worth)
     let (tx_symbols, _, _, _, _) = encoder.get_next_symbols(32);
@@ -179,7 +179,7 @@ fn test_fsk_frequency_detection_binary_0() {
     let audio = generate_fsk_qpsk_audio(&tx_symbols, &fsk_bits, sample_rate, symbol_rate);
     let rx_symbols = audio_to_symbols(&audio, sample_rate, symbol_rate, 12000.0);
     
-    let mut decoder = StreamingSymbolDecoder::new(protocol, ldpc_suite.matrices);
+    let mut decoder = StreamingSymbolDecoder::new(protocol, ldpc_suite.matrices).unwrap();
     decoder.process_symbols(&rx_symbols);
     
     let freq = decoder.get_fsk_frequency();
@@ -202,7 +202,7 @@ fn test_fsk_frequency_detection_binary_1() {
         &payload_bits,
         protocol.clone(),
         ldpc_suite.matrices.clone(),
-    );
+    ).unwrap();
     
     // Generate 32 symbols (2 seconds worth)
     let (tx_symbols, _, _, _, _) = encoder.get_next_symbols(32);
@@ -215,7 +215,7 @@ fn test_fsk_frequency_detection_binary_1() {
     let audio = generate_fsk_qpsk_audio(&tx_symbols, &fsk_bits, sample_rate, symbol_rate);
     let rx_symbols = audio_to_symbols(&audio, sample_rate, symbol_rate, 12000.0);
     
-    let mut decoder = StreamingSymbolDecoder::new(protocol, ldpc_suite.matrices);
+    let mut decoder = StreamingSymbolDecoder::new(protocol, ldpc_suite.matrices).unwrap();
     decoder.process_symbols(&rx_symbols);
     
     let freq = decoder.get_fsk_frequency();
@@ -240,7 +240,7 @@ fn test_fsk_end_to_end_with_qpsk() {
         &payload_bits,
         protocol.clone(),
         ldpc_suite.matrices.clone(),
-    );
+    ).unwrap();
     
     // Generate enough symbols for meaningful FSK data (64 symbols = 4 seconds)
     let (tx_symbols, _, _, _, _) = encoder.get_next_symbols(64);
@@ -258,7 +258,7 @@ fn test_fsk_end_to_end_with_qpsk() {
     let rx_symbols = audio_to_symbols(&audio, sample_rate, symbol_rate, 12000.0);
     
     // Decode with chunked processing
-    let mut decoder = StreamingSymbolDecoder::new(protocol, ldpc_suite.matrices);
+    let mut decoder = StreamingSymbolDecoder::new(protocol, ldpc_suite.matrices).unwrap();
     for chunk in rx_symbols.chunks(16) {
         decoder.process_symbols(chunk);
     }
@@ -10,7 +10,7 @@ fn test_processor_simple_message_clean_channel() {
     config.channel.snr_db = 100.0; // Essentially no noise
     config.channel.enable_noise = false;
     
-    let mut processor = ChimeraProcessor::new(config);
+    let mut processor = ChimeraProcessor::new(config).unwrap();
     processor.enable_diagnostics(); // Enable debug output
     
     let input = b"Hello";
@@ -63,8 +63,8 @@ fn test_processor_deterministic_no_channel() {
     config.channel.enable_noise = false;
     config.channel.enable_fading = false;
     
-    let mut processor1 = ChimeraProcessor::new(config.clone());
-    let mut processor2 = ChimeraProcessor::new(config);
+    let mut processor1 = ChimeraProcessor::new(config.clone()).unwrap();
+    let mut processor2 = ChimeraProcessor::new(config).unwrap();
     
     let input = b"Test";
     
@@ -86,7 +86,7 @@ fn test_processor_hello_with_noise() {
     config.channel.snr_db = 20.0;
     config.channel.enable_noise = true;
     
-    let mut processor = ChimeraProcessor::new(config);
+    let mut processor = ChimeraProcessor::new(config).unwrap();
     processor.enable_diagnostics();
     
     let result = processor.process_batch("Hello");
@@ -109,7 +109,7 @@ fn test_processor_cli_equivalent() {
     config.channel.enable_noise = false;
     // FSK is now always enabled (part of spec)
     
-    let mut processor = ChimeraProcessor::new(config);
+    let mut processor = ChimeraProcessor::new(config).unwrap();
     processor.enable_diagnostics();
     
     // Use exact CLI message truncated to 16 bytes
@@ -20,7 +20,7 @@ proptest! {
             &payload_bits,
             protocol.clone(),
             ldpc_suite.matrices.clone(),
-        );
+        ).unwrap();
         
         // Generate all symbols for all frames
         let total_symbols = protocol.frame_layout.total_symbols * encoder.total_frames;
@@ -30,7 +30,7 @@ proptest! {
         let mut decoder = StreamingSymbolDecoder::new(
             protocol.clone(),
             ldpc_suite.matrices.clone(),
-        );
+        ).unwrap();
         
         decoder.process_symbols(&tx_symbols);
         let decoded_payload = decoder.get_decoded_payload();
@@ -96,6 +96,7 @@ proptest! {
             command_type_symbols: command,
             data_payload_symbols: data,
             ecc_symbols: ecc,
+            ..FrameLayout::default()
         };
         
         // Should always validate when sum matches
@@ -124,14 +125,14 @@ proptest! {
             &payload_bits,
             protocol.clone(),
             ldpc_suite.matrices.clone(),
-        );
+        ).unwrap();
         let (symbols1, _, _, _, _) = encoder1.get_next_symbols(64);
         
         let mut encoder2 = StreamingFrameEncoder::new(
             &payload_bits,
             protocol.clone(),
             ldpc_suite.matrices.clone(),
-        );
+        ).unwrap();
         let (symbols2, _, _, _, _) = encoder2.get_next_symbols(64);
         
         // Should produce identical symbols
@@ -185,7 +186,7 @@ mod additional_tests {
             &payload_bits,
             protocol.clone(),
             ldpc_suite.matrices.clone(),
-        );
+        ).unwrap();
         
         // Should still generate at least one frame
         assert!(encoder.total_frames >= 1);
@@ -19,7 +19,7 @@ fn test_streaming_rate_limiting() {
     let protocol = ProtocolConfig::default();
     let ldpc = LDPCConfig::default();
     
-    let mut pipeline = RealtimePipeline::new(sim, protocol.clone(), ldpc);
+    let mut pipeline = RealtimePipeline::new(sim, protocol.clone(), ldpc).unwrap();
     
     // Note: Rate limiting has been removed for real-time performance.
     // The streaming pipeline now emits symbols as fast as possible, with rate limiting
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use chimera_core::config::{FrameLayout, LDPCConfig, InternalProtocolConfig, UserSimulationConfig};
+use chimera_core::config::{LDPCConfig, InternalProtocolConfig, ProtocolRegistry, UserSimulationConfig};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
@@ -68,76 +68,35 @@ impl FramePreset {
         }
     }
 
+    /// Name of the preset in the core protocol registry
+    pub fn protocol_name(&self) -> &'static str {
+        match self {
+            FramePreset::RamanWhisper => "whisper",
+            FramePreset::BurstTelemetry => "burst-telemetry",
+            FramePreset::DeepSpaceProbe => "deep-space-probe",
+        }
+    }
+
     pub fn bundle(&self) -> PresetBundle {
-        let mut protocol = InternalProtocolConfig::default();
+        // Protocol and FEC come from the registry shared with the CLI and library
+        let preset = ProtocolRegistry::builtin()
+            .get(self.protocol_name())
+            .cloned()
+            .expect("every frame preset is a built-in protocol preset");
         let mut simulation = UserSimulationConfig::default();
-        let mut ldpc = LDPCConfig::default();
 
-        match self {
-            FramePreset::RamanWhisper => {
-                // Keep defaults but ensure descriptive message.
-                simulation.message = "This is a longer message demonstrating the protocol-compliant, rate-4/5 LDPC error correction.".into();
-            }
-            FramePreset::BurstTelemetry => {
-                protocol.qpsk_symbol_rate = 32;
-                protocol.qpsk_bandwidth_hz = 28.0;
-                protocol.frame_layout = FrameLayout {
-                    total_symbols: 96,
-                    sync_symbols: 24,
-                    target_id_symbols: 16,
-                    command_type_symbols: 8,
-                    data_payload_symbols: 40,
-                    ecc_symbols: 8,
-                };
-                protocol.sync_sequence_hex = "7E7E7E7E".into();
-                protocol.target_id_hex = "CAFEBABE".into();
-                protocol.command = "send_data".into();
-                protocol.max_frames = 64;
-                protocol.current_frame_shift = 12;
-                protocol.total_frames_shift = 18;
-
-                // Note: SNR/link_loss are runtime parameters now
-                simulation.message =
-                    "Telemetry burst payload with accelerated downlink cadence.".into();
-
-                ldpc.dv = 3;
-                ldpc.dc = 9;
-            }
-            FramePreset::DeepSpaceProbe => {
-                protocol.qpsk_symbol_rate = 12;
-                protocol.qpsk_bandwidth_hz = 18.0;
-                protocol.fsk_bit_rate = 0.5;
-                protocol.fsk_freq_zero_hz = 11_998.4;
-                protocol.fsk_freq_one_hz = 12_001.6;
-                protocol.frame_layout = FrameLayout {
-                    total_symbols: 256,
-                    sync_symbols: 32,
-                    target_id_symbols: 32,
-                    command_type_symbols: 32,
-                    data_payload_symbols: 96,
-                    ecc_symbols: 64,
-                };
-                protocol.sync_sequence_hex = "55AA55AA".into();
-                protocol.target_id_hex = "0D15EA5E".into();
-                protocol.command = "send_data".into(); // Changed from command_opcode to command string
-                protocol.max_frames = 32;
-                protocol.current_frame_shift = 20;
-                protocol.total_frames_shift = 28;
-
-                // Note: SNR is now a runtime parameter, not in simulation config
-                simulation.message =
-                    "Deep-space probe telemetry with reinforced parity blocks.".into();
-
-                ldpc.dv = 2;
-                ldpc.dc = 12;
-                ldpc.seed = Some(1337);
-            }
+        simulation.message = match self {
+            FramePreset::RamanWhisper => "This is a longer message demonstrating the protocol-compliant, rate-4/5 LDPC error correction.",
+            // Note: SNR/link_loss are runtime parameters now
+            FramePreset::BurstTelemetry => "Telemetry burst payload with accelerated downlink cadence.",
+            FramePreset::DeepSpaceProbe => "Deep-space probe telemetry with reinforced parity blocks.",
         }
+        .into();
 
         PresetBundle {
-            protocol,
+            protocol: preset.protocol,
             simulation,
-            ldpc,
+            ldpc: preset.ldpc,
         }
     }
 
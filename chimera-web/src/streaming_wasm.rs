@@ -128,6 +128,12 @@ impl WASMStreamingDSP {
                 symbols_per_bit: fsk.symbols_per_bit,
                 bit_rate_hz: fsk.bit_rate_hz,
             }),
+            
+            // Transmitter
+            tx_queue_depth: output.tx_queue_depth as u32,
+            tx_message: output.transmitting.message,
+            tx_command: output.transmitting.command,
+            tx_target_id: output.transmitting.target_id,
         })
     }
     
@@ -137,13 +143,9 @@ impl WASMStreamingDSP {
         let config: StreamConfigWASM = serde_json::from_str(config_json)
             .map_err(|e| JsValue::from_str(&format!("Failed to parse config: {}", e)))?;
         
-        self.pipeline.reconfigure(
-            config.simulation,
-            config.protocol,
-            config.ldpc,
-        );
-        
-        Ok(())
+        self.pipeline
+            .reconfigure(config.simulation, config.protocol, config.ldpc)
+            .map_err(|e| JsValue::from_str(&format!("Failed to configure pipeline: {}", e)))
     }
     
     /// Get current configuration as JSON
@@ -175,21 +177,21 @@ impl WASMStreamingDSP {
         self.pipeline.update_channel_params(self.pipeline.get_snr(), link_loss_db);
     }
     
-    /// Update message (waits for current transmission to complete before applying)
+    /// Queue a message to replace the current one once its last frame has been sent
     #[wasm_bindgen]
     pub fn update_message(&mut self, message: String) -> Result<(), JsValue> {
         self.pipeline.update_message(message)
             .map_err(|e| JsValue::from_str(&format!("Failed to update message: {}", e)))
     }
     
-    /// Update command by string name (e.g., "send_data", "data_transfer")
+    /// Queue a command by string name (e.g., "send_data", "data_transfer") for the next message
     #[wasm_bindgen]
     pub fn update_command(&mut self, command: String) -> Result<(), JsValue> {
         self.pipeline.update_command(command)
             .map_err(|e| JsValue::from_str(&format!("Failed to update command: {}", e)))
     }
     
-    /// Update target ID
+    /// Queue a target ID (hex) for the next message
     #[wasm_bindgen]
     pub fn update_target_id(&mut self, target_id: String) -> Result<(), JsValue> {
         self.pipeline.update_target_id(target_id)
@@ -305,6 +307,12 @@ pub struct WASMStreamOutput {
     
     // FSK layer state (optional, decoded from received signal)
     fsk_state: Option<WASMFSKState>,
+    
+    // Transmitter (message, command and target ID updates wait for the message boundary)
+    tx_queue_depth: u32,
+    tx_message: String,
+    tx_command: String,
+    tx_target_id: String,
 }
 
 #[wasm_bindgen]
@@ -488,6 +496,27 @@ impl WASMStreamOutput {
         self.fec_corrections
     }
     
+    // Transmitter getters
+    #[wasm_bindgen(getter)]
+    pub fn tx_queue_depth(&self) -> u32 {
+        self.tx_queue_depth
+    }
+    
+    #[wasm_bindgen(getter)]
+    pub fn tx_message(&self) -> String {
+        self.tx_message.clone()
+    }
+    
+    #[wasm_bindgen(getter)]
+    pub fn tx_command(&self) -> String {
+        self.tx_command.clone()
+    }
+    
+    #[wasm_bindgen(getter)]
+    pub fn tx_target_id(&self) -> String {
+        self.tx_target_id.clone()
+    }
+    
     // Current frame data getters
     #[wasm_bindgen(getter)]
     pub fn frame_number(&self) -> u32 {